@@ -5,7 +5,10 @@ use std::time::Duration;
 use criterion::{criterion_group, Criterion};
 use criterion::{criterion_main, SamplingMode, Throughput};
 
-use cds::{avltree::AVLTree, btree::BTree};
+use cds::{
+    avltree::AVLTree, bplustree::BPlusTree, btree::BTree, skiplist::SkipList, splaytree::SplayTree,
+    wbtree::WBTree,
+};
 
 use util::sequential::{bench_logs_btreemap, bench_logs_sequential_map, fuzz_sequential_logs};
 
@@ -43,7 +46,12 @@ fn bench_vs_btreemap(c: &mut Criterion) {
 
         bench_logs_btreemap(logs.clone(), &mut group);
         bench_logs_sequential_map::<BTree<_, _>>("BTree", logs.clone(), &mut group);
-        bench_logs_sequential_map::<AVLTree<_, _>>("AVLTree", logs, &mut group);
+        bench_logs_sequential_map::<BTree<_, _, 32>>("BTree(B=32)", logs.clone(), &mut group);
+        bench_logs_sequential_map::<BPlusTree<_, _>>("BPlusTree", logs.clone(), &mut group);
+        bench_logs_sequential_map::<AVLTree<_, _>>("AVLTree", logs.clone(), &mut group);
+        bench_logs_sequential_map::<SkipList<_, _>>("SkipList", logs.clone(), &mut group);
+        bench_logs_sequential_map::<WBTree<_, _>>("WBTree", logs.clone(), &mut group);
+        bench_logs_sequential_map::<SplayTree<_, _>>("SplayTree", logs, &mut group);
     }
 }
 
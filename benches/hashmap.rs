@@ -0,0 +1,75 @@
+use std::{collections::HashMap, time::Duration};
+
+use criterion::{criterion_group, criterion_main, Criterion, SamplingMode, Throughput};
+use rand::{prelude::SliceRandom, thread_rng};
+
+use cds::{
+    hashmap::{ChainedHashMap, SwissHashMap},
+    map::SequentialMap,
+};
+
+const MAP_ALREADY_INSERTED: u64 = 1_000_000;
+const MAP_TOTAL_OPS: usize = 100_000;
+
+fn bench_lookup_sequential_map<M: SequentialMap<u64, u64>>(
+    name: &str,
+    keys: &[u64],
+    lookups: &[u64],
+    c: &mut criterion::BenchmarkGroup<criterion::measurement::WallTime>,
+) {
+    let mut map = M::new();
+
+    for &key in keys {
+        let _ = map.insert(&key, key);
+    }
+
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            for key in lookups {
+                assert!(map.lookup(key).is_some());
+            }
+        })
+    });
+}
+
+fn bench_hashmap_lookup(c: &mut Criterion) {
+    let mut rng = thread_rng();
+    let keys = (0..MAP_ALREADY_INSERTED).collect::<Vec<_>>();
+    let lookups = (0..MAP_TOTAL_OPS)
+        .map(|_| *keys.choose(&mut rng).unwrap())
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group(format!(
+        "Inserted {:+e}, Lookup {:+e}",
+        MAP_ALREADY_INSERTED, MAP_TOTAL_OPS
+    ));
+    group.measurement_time(Duration::from_secs(15));
+    group.sampling_mode(SamplingMode::Flat);
+    group.sample_size(20);
+    group.throughput(Throughput::Elements(MAP_TOTAL_OPS as u64));
+
+    bench_lookup_sequential_map::<ChainedHashMap<_, _>>(
+        "ChainedHashMap",
+        &keys,
+        &lookups,
+        &mut group,
+    );
+    bench_lookup_sequential_map::<SwissHashMap<_, _>>("SwissHashMap", &keys, &lookups, &mut group);
+
+    let map = keys
+        .iter()
+        .map(|&key| (key, key))
+        .collect::<HashMap<_, _>>();
+    group.bench_function("std::collections::HashMap", |b| {
+        b.iter(|| {
+            for key in &lookups {
+                assert!(map.contains_key(key));
+            }
+        })
+    });
+}
+
+criterion_group!(bench, bench_hashmap_lookup);
+criterion_main! {
+    bench,
+}
@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, SamplingMode};
+use rand::{thread_rng, Rng};
+
+use cds::heap::{BinaryHeap, PairingHeap, PriorityQueue, RadixHeap};
+
+const NODE_NUM: usize = 100_000;
+const EDGE_NUM: usize = 10;
+
+type Graph = Vec<Vec<(usize, u32)>>;
+
+fn random_graph() -> Graph {
+    let mut rng = thread_rng();
+
+    (0..NODE_NUM)
+        .map(|_| {
+            (0..EDGE_NUM)
+                .map(|_| (rng.gen_range(0..NODE_NUM), rng.gen_range(0..1_000)))
+                .collect()
+        })
+        .collect()
+}
+
+fn dijkstra<H: PriorityQueue<(u32, usize)>>(graph: &Graph) -> Vec<u32> {
+    let mut dist = vec![u32::MAX; graph.len()];
+    let mut heap = H::new();
+    dist[0] = 0;
+    heap.push((0, 0));
+
+    while let Some((d, node)) = heap.pop() {
+        if d > dist[node] {
+            continue;
+        }
+
+        for &(next, weight) in &graph[node] {
+            if d + weight < dist[next] {
+                dist[next] = d + weight;
+                heap.push((d + weight, next));
+            }
+        }
+    }
+
+    dist
+}
+
+fn dijkstra_radix(graph: &Graph) -> Vec<u32> {
+    let mut dist = vec![u32::MAX; graph.len()];
+    let mut heap = RadixHeap::new();
+    dist[0] = 0;
+    heap.push(0, 0).unwrap();
+
+    while let Some((d, node)) = heap.pop() {
+        if d > dist[node] {
+            continue;
+        }
+
+        for &(next, weight) in &graph[node] {
+            if d + weight < dist[next] {
+                dist[next] = d + weight;
+                heap.push(d + weight, next).unwrap();
+            }
+        }
+    }
+
+    dist
+}
+
+fn bench_dijkstra(c: &mut Criterion) {
+    let graph = random_graph();
+
+    let mut group = c.benchmark_group(format!(
+        "Dijkstra (nodes: {:+e}, edges per node: {})",
+        NODE_NUM, EDGE_NUM
+    ));
+    group.measurement_time(Duration::from_secs(15));
+    group.sampling_mode(SamplingMode::Flat);
+    group.sample_size(20);
+
+    group.bench_with_input(
+        BenchmarkId::new("BinaryHeap", NODE_NUM),
+        &graph,
+        |b, graph| b.iter(|| dijkstra::<BinaryHeap<_>>(graph)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("PairingHeap", NODE_NUM),
+        &graph,
+        |b, graph| b.iter(|| dijkstra::<PairingHeap<_>>(graph)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("RadixHeap", NODE_NUM),
+        &graph,
+        |b, graph| b.iter(|| dijkstra_radix(graph)),
+    );
+}
+
+criterion_group!(bench, bench_dijkstra);
+criterion_main! {
+    bench,
+}
@@ -0,0 +1,58 @@
+mod util;
+
+use std::time::Duration;
+
+use criterion::{criterion_group, Criterion};
+use criterion::{criterion_main, SamplingMode, Throughput};
+
+use cds::linkedlist::{DoublyLinkedList, LinkedList, UnrolledLinkedList};
+
+use util::sequential::{bench_logs_sequential_map, fuzz_sequential_logs};
+
+const MAP_ALREADY_INSERTED: u64 = 10_000;
+const MAP_TOTAL_OPS: usize = 1_000;
+
+const OPS_RATE: [(usize, usize, usize); 4] = [(100, 0, 0), (0, 100, 0), (0, 0, 100), (30, 50, 20)];
+
+fn bench_linkedlist(c: &mut Criterion) {
+    for (insert, lookup, remove) in OPS_RATE {
+        let logs = fuzz_sequential_logs(
+            200,
+            MAP_ALREADY_INSERTED,
+            MAP_TOTAL_OPS * insert / 100,
+            MAP_TOTAL_OPS * lookup / 100,
+            MAP_TOTAL_OPS * remove / 100,
+        );
+
+        let mut group = c.benchmark_group(format!(
+            "Inserted {:+e}, Ops (I: {}%, L: {}%, R: {}%, total: {:+e})",
+            MAP_ALREADY_INSERTED, insert, lookup, remove, MAP_TOTAL_OPS
+        ));
+        group.measurement_time(Duration::from_secs(15));
+        group.sampling_mode(SamplingMode::Flat);
+        group.sample_size(20);
+        group.throughput(Throughput::Elements(MAP_TOTAL_OPS as u64));
+
+        bench_logs_sequential_map::<LinkedList<_, _>>("LinkedList", logs.clone(), &mut group);
+        bench_logs_sequential_map::<DoublyLinkedList<_, _>>(
+            "DoublyLinkedList",
+            logs.clone(),
+            &mut group,
+        );
+        bench_logs_sequential_map::<UnrolledLinkedList<_, _>>(
+            "UnrolledLinkedList",
+            logs.clone(),
+            &mut group,
+        );
+        bench_logs_sequential_map::<UnrolledLinkedList<_, _, 64>>(
+            "UnrolledLinkedList(B=64)",
+            logs,
+            &mut group,
+        );
+    }
+}
+
+criterion_group!(bench, bench_linkedlist);
+criterion_main! {
+    bench,
+}
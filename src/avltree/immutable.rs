@@ -0,0 +1,274 @@
+use std::{
+    cmp::{max, Ordering},
+    sync::Arc,
+};
+
+/// persistent AVL tree whose versions share the untouched subtrees
+///
+/// Insert and remove copy only the nodes on the path from the root, and return a new version.
+/// So, cloning the tree is O(1), and the old versions are still valid as snapshots.
+pub struct ImmutableAVLTree<K, V> {
+    root: Subtree<K, V>,
+    len: usize,
+}
+
+type Subtree<K, V> = Option<Arc<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    height: isize,
+    left: Subtree<K, V>,
+    right: Subtree<K, V>,
+}
+
+impl<K, V> Clone for ImmutableAVLTree<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<K, V> Default for ImmutableAVLTree<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn height<K, V>(tree: &Subtree<K, V>) -> isize {
+    if let Some(node) = tree {
+        node.height
+    } else {
+        0
+    }
+}
+
+impl<K: Clone, V: Clone> Node<K, V> {
+    /// make the new node with the childs
+    fn make(key: K, value: V, left: Subtree<K, V>, right: Subtree<K, V>) -> Arc<Self> {
+        Arc::new(Node {
+            key,
+            value,
+            height: max(height(&left), height(&right)) + 1,
+            left,
+            right,
+        })
+    }
+
+    /// make the new node with the childs whose heights differ at most 2, rotating by the rule of AVL
+    fn balance(key: K, value: V, left: Subtree<K, V>, right: Subtree<K, V>) -> Arc<Self> {
+        let factor = height(&left) - height(&right);
+
+        if factor > 1 {
+            let left = left.unwrap();
+
+            if height(&left.left) >= height(&left.right) {
+                // rotate right
+                let right = Node::make(key, value, left.right.clone(), right);
+                Node::make(
+                    left.key.clone(),
+                    left.value.clone(),
+                    left.left.clone(),
+                    Some(right),
+                )
+            } else {
+                // rotate left on the left child, then rotate right
+                let mid = left.right.as_ref().unwrap();
+                let new_left = Node::make(
+                    left.key.clone(),
+                    left.value.clone(),
+                    left.left.clone(),
+                    mid.left.clone(),
+                );
+                let new_right = Node::make(key, value, mid.right.clone(), right);
+                Node::make(
+                    mid.key.clone(),
+                    mid.value.clone(),
+                    Some(new_left),
+                    Some(new_right),
+                )
+            }
+        } else if factor < -1 {
+            let right = right.unwrap();
+
+            if height(&right.right) >= height(&right.left) {
+                // rotate left
+                let left = Node::make(key, value, left, right.left.clone());
+                Node::make(
+                    right.key.clone(),
+                    right.value.clone(),
+                    Some(left),
+                    right.right.clone(),
+                )
+            } else {
+                // rotate right on the right child, then rotate left
+                let mid = right.left.as_ref().unwrap();
+                let new_left = Node::make(key, value, left, mid.left.clone());
+                let new_right = Node::make(
+                    right.key.clone(),
+                    right.value.clone(),
+                    mid.right.clone(),
+                    right.right.clone(),
+                );
+                Node::make(
+                    mid.key.clone(),
+                    mid.value.clone(),
+                    Some(new_left),
+                    Some(new_right),
+                )
+            }
+        } else {
+            Node::make(key, value, left, right)
+        }
+    }
+
+    /// remove the node that has the smallest key, then return (new subtree, (key, value))
+    fn remove_first(node: &Arc<Self>) -> (Subtree<K, V>, (K, V)) {
+        match &node.left {
+            Some(left) => {
+                let (left, first) = Node::remove_first(left);
+                let node = Node::balance(
+                    node.key.clone(),
+                    node.value.clone(),
+                    left,
+                    node.right.clone(),
+                );
+
+                (Some(node), first)
+            }
+            None => (node.right.clone(), (node.key.clone(), node.value.clone())),
+        }
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> Node<K, V> {
+    fn insert(tree: &Subtree<K, V>, key: &K, value: V) -> Result<Arc<Self>, V> {
+        let node = match tree {
+            Some(node) => node,
+            None => return Ok(Node::make(key.clone(), value, None, None)),
+        };
+
+        let (left, right) = match key.cmp(&node.key) {
+            Ordering::Less => (
+                Some(Node::insert(&node.left, key, value)?),
+                node.right.clone(),
+            ),
+            Ordering::Equal => return Err(value),
+            Ordering::Greater => (
+                node.left.clone(),
+                Some(Node::insert(&node.right, key, value)?),
+            ),
+        };
+
+        Ok(Node::balance(
+            node.key.clone(),
+            node.value.clone(),
+            left,
+            right,
+        ))
+    }
+
+    fn remove(tree: &Subtree<K, V>, key: &K) -> Result<(Subtree<K, V>, V), ()> {
+        let node = tree.as_ref().ok_or(())?;
+
+        let (left, right, value) = match key.cmp(&node.key) {
+            Ordering::Less => {
+                let (left, value) = Node::remove(&node.left, key)?;
+                (left, node.right.clone(), value)
+            }
+            Ordering::Greater => {
+                let (right, value) = Node::remove(&node.right, key)?;
+                (node.left.clone(), right, value)
+            }
+            Ordering::Equal => {
+                let value = node.value.clone();
+
+                // replace the node with the smallest node of the right subtree
+                let subtree = match (&node.left, &node.right) {
+                    (left, None) => left.clone(),
+                    (None, right) => right.clone(),
+                    (left, Some(right)) => {
+                        let (right, (key, value)) = Node::remove_first(right);
+                        Some(Node::balance(key, value, left.clone(), right))
+                    }
+                };
+
+                return Ok((subtree, value));
+            }
+        };
+
+        let node = Node::balance(node.key.clone(), node.value.clone(), left, right);
+        Ok((Some(node), value))
+    }
+}
+
+impl<K, V> ImmutableAVLTree<K, V> {
+    pub fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    /// get the height of the tree
+    pub fn get_height(&self) -> usize {
+        height(&self.root) as usize
+    }
+
+    /// get the number of the (key, value)s on the tree
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> ImmutableAVLTree<K, V> {
+    /// Insert (key, value) into the new version of the tree.
+    ///
+    /// If success, return Ok(new version).
+    /// If fail, return Err(value) that you tried to insert.
+    pub fn insert(&self, key: &K, value: V) -> Result<Self, V> {
+        let root = Node::insert(&self.root, key, value)?;
+
+        Ok(Self {
+            root: Some(root),
+            len: self.len + 1,
+        })
+    }
+
+    /// Lookup (key, value) from the tree with the key.
+    ///
+    /// If success, return the reference of the value.
+    /// If fail, return None.
+    pub fn lookup(&self, key: &K) -> Option<&V> {
+        let mut current = self.root.as_ref();
+
+        while let Some(node) = current {
+            match key.cmp(&node.key) {
+                Ordering::Less => current = node.left.as_ref(),
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Greater => current = node.right.as_ref(),
+            }
+        }
+
+        None
+    }
+
+    /// Remove (key, value) from the new version of the tree.
+    ///
+    /// If success, return Ok((new version, value)).
+    /// If fail, return Err(()).
+    pub fn remove(&self, key: &K) -> Result<(Self, V), ()> {
+        let (root, value) = Node::remove(&self.root, key)?;
+
+        Ok((
+            Self {
+                root,
+                len: self.len - 1,
+            },
+            value,
+        ))
+    }
+}
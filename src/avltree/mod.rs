@@ -1,13 +1,17 @@
+mod immutable;
 mod rwlock;
 mod seqlock;
 
+pub use immutable::ImmutableAVLTree;
 pub use rwlock::RwLockAVLTree;
 pub use seqlock::SeqLockAVLTree;
 
 use crate::map::SequentialMap;
 use std::{
-    cmp::max,
-    fmt::Debug,
+    cmp::{max, Ordering},
+    fmt::{Debug, Display, Write},
+    iter::FromIterator,
+    marker::PhantomData,
     mem,
     ops::DerefMut,
     ptr::{drop_in_place, NonNull},
@@ -18,6 +22,9 @@ pub struct AVLTree<K, V> {
     root: NonNull<Node<K, V>>, // root node is dummy for simplicity
 }
 
+unsafe impl<K: Send, V: Send> Send for AVLTree<K, V> {}
+unsafe impl<K: Sync, V: Sync> Sync for AVLTree<K, V> {}
+
 impl<K: Debug, V: Debug> Debug for AVLTree<K, V> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         unsafe {
@@ -28,6 +35,16 @@ impl<K: Debug, V: Debug> Debug for AVLTree<K, V> {
     }
 }
 
+/// show the tree with indentation, where each line is `dir: (key, value) height: _, factor: _`
+impl<K: Debug, V: Debug> Display for AVLTree<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match unsafe { self.root.as_ref().right.as_ref() } {
+            Some(node) => node.fmt_tree(f, 0, "Root"),
+            None => writeln!(f, "Root: null"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum Dir {
     Left,
@@ -40,10 +57,14 @@ struct Node<K, V> {
     key: K,
     value: V,
     height: isize,
+    size: usize, // the number of the nodes on the subtree including itself
     left: Option<Box<Node<K, V>>>,
     right: Option<Box<Node<K, V>>>,
 }
 
+// the subtree which may be empty
+type Subtree<K, V> = Option<Box<Node<K, V>>>;
+
 impl<K: Default, V: Default> Default for Node<K, V> {
     fn default() -> Self {
         Self::new(K::default(), V::default())
@@ -56,6 +77,7 @@ impl<K, V> Node<K, V> {
             key,
             value,
             height: 1,
+            size: 1,
             left: None,
             right: None,
         }
@@ -70,21 +92,31 @@ impl<K, V> Node<K, V> {
         }
     }
 
-    /// renew the height of the node from the childs
+    /// renew the height and the size of the node from the childs
     fn renew_height(&mut self) {
-        let left_height = if let Some(node) = &self.left {
-            node.height
+        let (left_height, left_size) = if let Some(node) = &self.left {
+            (node.height, node.size)
         } else {
-            0
+            (0, 0)
         };
 
-        let right_height = if let Some(node) = &self.right {
-            node.height
+        let (right_height, right_size) = if let Some(node) = &self.right {
+            (node.height, node.size)
         } else {
-            0
+            (0, 0)
         };
 
         self.height = max(left_height, right_height) + 1;
+        self.size = left_size + right_size + 1;
+    }
+
+    /// get the size of the left subtree
+    fn left_size(&self) -> usize {
+        if let Some(node) = &self.left {
+            node.size
+        } else {
+            0
+        }
     }
 
     /// get difference of the heights from the childs
@@ -125,6 +157,253 @@ impl<K, V> Node<K, V> {
 
         new_parent
     }
+
+    /// rebalance the node by the rule of AVL, then return new parent
+    ///
+    /// The childs should be balanced, and the difference of their heights should be at most 2.
+    fn balance(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+        match node.get_factor() {
+            -2 => {
+                let child_factor = node.right.as_ref().unwrap().get_factor();
+
+                if child_factor > 0 {
+                    let right_child = node.right.take().unwrap();
+                    let mut right_child = Node::rotate_right(right_child);
+                    right_child.right.as_mut().unwrap().renew_height();
+                    node.right = Some(right_child);
+                }
+
+                let mut new_parent = Node::rotate_left(node);
+                new_parent.left.as_mut().unwrap().renew_height();
+                new_parent.renew_height();
+                new_parent
+            }
+            -1..=1 => {
+                node.renew_height();
+                node
+            }
+            2 => {
+                let child_factor = node.left.as_ref().unwrap().get_factor();
+
+                if child_factor < 0 {
+                    let left_child = node.left.take().unwrap();
+                    let mut left_child = Node::rotate_left(left_child);
+                    left_child.left.as_mut().unwrap().renew_height();
+                    node.left = Some(left_child);
+                }
+
+                let mut new_parent = Node::rotate_right(node);
+                new_parent.right.as_mut().unwrap().renew_height();
+                new_parent.renew_height();
+                new_parent
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// get the height of the tree
+    fn height_of(tree: &Subtree<K, V>) -> isize {
+        if let Some(node) = tree {
+            node.height
+        } else {
+            0
+        }
+    }
+
+    /// join two trees with the middle node whose key is between them, then return new root
+    ///
+    /// All keys of left should be less than the key of mid, and all keys of right should be greater.
+    fn join(
+        left: Subtree<K, V>,
+        mut mid: Box<Node<K, V>>,
+        right: Subtree<K, V>,
+    ) -> Box<Node<K, V>> {
+        let (left_height, right_height) = (Node::height_of(&left), Node::height_of(&right));
+
+        if left_height > right_height + 1 {
+            // descend to the right spine of left until the heights become similar
+            let mut left = left.unwrap();
+            let left_right = left.right.take();
+            left.right = Some(Node::join(left_right, mid, right));
+            Node::balance(left)
+        } else if right_height > left_height + 1 {
+            // descend to the left spine of right until the heights become similar
+            let mut right = right.unwrap();
+            let right_left = right.left.take();
+            right.left = Some(Node::join(left, mid, right_left));
+            Node::balance(right)
+        } else {
+            mid.left = left;
+            mid.right = right;
+            mid.renew_height();
+            mid
+        }
+    }
+
+    /// build the perfectly balanced tree from the first len nodes of the iterator
+    fn build<I: Iterator<Item = Box<Node<K, V>>>>(len: usize, iter: &mut I) -> Subtree<K, V> {
+        if len == 0 {
+            return None;
+        }
+
+        let left_len = len / 2;
+        let left = Node::build(left_len, iter);
+        let mut node = iter.next().unwrap();
+        let right = Node::build(len - left_len - 1, iter);
+
+        node.left = left;
+        node.right = right;
+        node.renew_height();
+
+        Some(node)
+    }
+
+    /// remove the node that has the smallest key on the tree, then return (new root, the node)
+    fn remove_first(mut node: Box<Node<K, V>>) -> (Subtree<K, V>, Box<Node<K, V>>) {
+        match node.left.take() {
+            Some(left) => {
+                let (left, first) = Node::remove_first(left);
+                node.left = left;
+                (Some(Node::balance(node)), first)
+            }
+            None => (node.right.take(), node),
+        }
+    }
+}
+
+impl<K: Debug, V: Debug> Node<K, V> {
+    /// write the subtree on pre-order with indentation by the depth
+    fn fmt_tree(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        depth: usize,
+        dir: &str,
+    ) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:indent$}{}: ({:?}, {:?}) height: {}, factor: {}",
+            "",
+            dir,
+            self.key,
+            self.value,
+            self.height,
+            self.get_factor(),
+            indent = depth * 4
+        )?;
+
+        if let Some(left) = &self.left {
+            left.fmt_tree(f, depth + 1, "L")?;
+        }
+
+        if let Some(right) = &self.right {
+            right.fmt_tree(f, depth + 1, "R")?;
+        }
+
+        Ok(())
+    }
+
+    /// write the subtree as the nodes and edges of Graphviz DOT, then return the id of the node
+    fn write_dot(&self, out: &mut String, id: &mut usize) -> usize {
+        let current = *id;
+        *id += 1;
+
+        let label = format!(
+            "{:?}: {:?}\\nheight: {}, factor: {}",
+            self.key,
+            self.value,
+            self.height,
+            self.get_factor()
+        );
+        // escape the quotes from the debug string of key and value
+        let label = label.replace('"', "\\\"");
+        writeln!(out, "    n{} [label=\"{}\"];", current, label).unwrap();
+
+        for (child, dir) in [(&self.left, "L"), (&self.right, "R")] {
+            if let Some(child) = child {
+                let child_id = child.write_dot(out, id);
+                writeln!(
+                    out,
+                    "    n{} -> n{} [label=\"{}\"];",
+                    current, child_id, dir
+                )
+                .unwrap();
+            }
+        }
+
+        current
+    }
+}
+
+impl<K: Ord + Debug, V> Node<K, V> {
+    /// check the invariants of the subtree whose keys should be in (from, to), then return (height, size)
+    fn validate(&self, from: Option<&K>, to: Option<&K>) -> Result<(isize, usize), String> {
+        if from.map_or(false, |from| *from >= self.key) || to.map_or(false, |to| self.key >= *to) {
+            return Err(format!(
+                "The key {:?} is out of the range ({:?}, {:?}).",
+                self.key, from, to
+            ));
+        }
+
+        let (left_height, left_size) = match &self.left {
+            Some(left) => left.validate(from, Some(&self.key))?,
+            None => (0, 0),
+        };
+
+        let (right_height, right_size) = match &self.right {
+            Some(right) => right.validate(Some(&self.key), to)?,
+            None => (0, 0),
+        };
+
+        let height = max(left_height, right_height) + 1;
+        if self.height != height {
+            return Err(format!(
+                "The height of the key {:?} is {}, but it should be {}.",
+                self.key, self.height, height
+            ));
+        }
+
+        let factor = left_height - right_height;
+        if factor.abs() > 1 {
+            return Err(format!(
+                "The key {:?} is unbalanced with the factor {}.",
+                self.key, factor
+            ));
+        }
+
+        let size = left_size + right_size + 1;
+        if self.size != size {
+            return Err(format!(
+                "The size of the key {:?} is {}, but it should be {}.",
+                self.key, self.size, size
+            ));
+        }
+
+        Ok((height, size))
+    }
+}
+
+impl<K: Ord, V> Node<K, V> {
+    /// split the tree into (the tree less than key, the node of key, the tree greater than key)
+    fn split(tree: Subtree<K, V>, key: &K) -> (Subtree<K, V>, Subtree<K, V>, Subtree<K, V>) {
+        let mut node = if let Some(node) = tree {
+            node
+        } else {
+            return (None, None, None);
+        };
+
+        let (left, right) = (node.left.take(), node.right.take());
+
+        if *key < node.key {
+            let (less, found, greater) = Node::split(left, key);
+            (less, found, Some(Node::join(greater, node, right)))
+        } else if *key > node.key {
+            let (less, found, greater) = Node::split(right, key);
+            (Some(Node::join(left, node, less)), found, greater)
+        } else {
+            node.renew_height();
+            (left, Some(node), right)
+        }
+    }
 }
 
 /// manage the current state of the node
@@ -165,7 +444,7 @@ where
     }
 
     /// get the mutable reference of the next node by the direction
-    fn next_node_mut(&mut self) -> &mut Option<Box<Node<K, V>>> {
+    fn next_node_mut(&mut self) -> &mut Subtree<K, V> {
         unsafe {
             match self.dir {
                 Dir::Left => &mut self.current.as_mut().left,
@@ -214,34 +493,67 @@ where
         self.dir = Dir::Eq;
     }
 
-    /// rebalance the nodes by the rule of AVL using the cursor's ancestors
-    fn rebalance(&mut self) {
-        let parent_rotate_left = |mut node: Box<Node<K, V>>| -> Box<Node<K, V>> {
-            let child_factor = node.right.as_ref().unwrap().get_factor();
-
-            if child_factor > 0 {
-                let right_child = node.right.take().unwrap();
-                let mut right_child = Node::rotate_right(right_child);
-                right_child.right.as_mut().unwrap().renew_height();
-                node.right = Some(right_child);
-            }
+    /// insert the node on the next of the cursor, then rebalance
+    ///
+    /// Return the pointer of the inserted node, which is not moved by rebalancing.
+    fn insert_next(&mut self, mut node: Box<Node<K, V>>) -> NonNull<Node<K, V>> {
+        let ptr = NonNull::from(node.as_mut());
+        *(self.next_node_mut()) = Some(node);
 
-            Node::rotate_left(node)
-        };
+        unsafe {
+            self.current.as_mut().renew_height();
+        }
+        self.rebalance();
+
+        ptr
+    }
 
-        let parent_rotate_right = |mut node: Box<Node<K, V>>| -> Box<Node<K, V>> {
-            let child_factor = node.left.as_ref().unwrap().get_factor();
+    /// remove the node that the cursor arrived at(dir == Eq), then rebalance
+    fn remove_current(&mut self) -> (K, V) {
+        let current = unsafe { self.current.as_ref() };
 
-            if child_factor < 0 {
-                let left_child = node.left.take().unwrap();
-                let mut left_child = Node::rotate_left(left_child);
-                left_child.left.as_mut().unwrap().renew_height();
-                node.left = Some(left_child);
+        let (left, right) = (current.left.is_some(), current.right.is_some());
+
+        // special case: find largest node from left subtree, swap, and remove
+        if left && right {
+            let (mut parent, dir) = self.ancestors.last_mut().unwrap();
+            let child = unsafe { parent.as_mut().child_mut(*dir).as_mut().unwrap() };
+
+            self.move_greatest_on_left_subtree();
+
+            let (mut swap_node_parent, dir) = self.ancestors.pop().unwrap();
+            let swap_node_ptr = unsafe { swap_node_parent.as_mut().child_mut(dir) };
+            let swap_node = swap_node_ptr.as_mut().unwrap();
+
+            mem::swap(&mut child.key, &mut swap_node.key);
+            mem::swap(&mut child.value, &mut swap_node.value);
+
+            let swap_node = *swap_node_ptr.take().unwrap();
+            if swap_node.left.is_some() {
+                *swap_node_ptr = swap_node.left;
             }
 
-            Node::rotate_right(node)
-        };
+            self.rebalance();
 
+            return (swap_node.key, swap_node.value);
+        }
+
+        let (mut parent, dir) = self.ancestors.pop().unwrap();
+        let child = unsafe { parent.as_mut().child_mut(dir) };
+        let node = *child.take().unwrap();
+
+        if left {
+            *child = node.left;
+        } else if right {
+            *child = node.right;
+        }
+
+        self.rebalance();
+        (node.key, node.value)
+    }
+
+    /// rebalance the nodes by the rule of AVL using the cursor's ancestors
+    fn rebalance(&mut self) {
         while let Some((mut node, dir)) = self.ancestors.pop() {
             // the root node for target node
             let root = unsafe { node.as_mut() };
@@ -252,25 +564,35 @@ where
                 _ => unreachable!(),
             };
 
-            let factor = target.as_ref().unwrap().get_factor();
+            *target = Some(Node::balance(target.take().unwrap()));
+        }
+    }
+}
 
-            match factor {
-                -2 => {
-                    let mut new_target = parent_rotate_left(target.take().unwrap());
-                    new_target.left.as_mut().unwrap().renew_height();
-                    new_target.renew_height();
-                    *target = Some(new_target);
-                }
-                -1..=1 => target.as_mut().unwrap().renew_height(),
-                2 => {
-                    let mut new_target = parent_rotate_right(target.take().unwrap());
-                    new_target.right.as_mut().unwrap().renew_height();
-                    new_target.renew_height();
-                    *target = Some(new_target);
-                }
-                _ => unreachable!(),
-            }
+impl<K: Ord + Debug, V> AVLTree<K, V> {
+    /// check the ordering of the keys, and the height, the balance factor and the size of every node
+    ///
+    /// If the tree is valid, return Ok(()), or Err(the description of the first violation).
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(node) = unsafe { self.root.as_ref().right.as_ref() } {
+            node.validate(None, None)?;
         }
+
+        Ok(())
+    }
+}
+
+impl<K: Debug, V: Debug> AVLTree<K, V> {
+    /// export the tree as Graphviz DOT, where each node has its key, value, height and factor
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph AVLTree {\n    node [shape=box];\n");
+
+        if let Some(node) = unsafe { self.root.as_ref().right.as_ref() } {
+            node.write_dot(&mut out, &mut 0);
+        }
+
+        out.push_str("}\n");
+        out
     }
 }
 
@@ -308,6 +630,24 @@ where
         }
     }
 
+    /// find the cursor arrived at the node that has the smallest(Left) or the greatest(Right) key
+    ///
+    /// If the tree is empty, return None.
+    fn find_edge(&self, dir: Dir) -> Option<Cursor<K, V>> {
+        let mut cursor = Cursor::new(self);
+
+        cursor.next_node()?;
+        cursor.move_next();
+
+        cursor.dir = dir;
+        while cursor.next_node().is_some() {
+            cursor.move_next();
+        }
+
+        cursor.dir = Dir::Eq;
+        Some(cursor)
+    }
+
     /// get the height of the tree
     pub fn get_height(&self) -> usize {
         if let Some(node) = unsafe { self.root.as_ref().right.as_ref() } {
@@ -316,6 +656,264 @@ where
             0
         }
     }
+
+    /// get the number of the (key, value)s on the tree
+    pub fn len(&self) -> usize {
+        if let Some(node) = unsafe { self.root.as_ref().right.as_ref() } {
+            node.size
+        } else {
+            0
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// get the iterator of (&key, &value)s in the order of the key
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            stack: Vec::with_capacity(self.get_height()),
+            current: unsafe { self.root.as_ref().right.as_deref() },
+            len: self.len(),
+        }
+    }
+
+    /// get the iterator of (&key, &mut value)s in the order of the key
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            stack: Vec::with_capacity(self.get_height()),
+            current: unsafe { self.root.as_mut().right.as_deref_mut().map(NonNull::from) },
+            len: self.len(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// transform every value in place by the function without changing the structure of the tree
+    pub fn map_values_in_place<F: FnMut(&K, &mut V)>(&mut self, mut f: F) {
+        for (key, value) in self.iter_mut() {
+            f(key, value);
+        }
+    }
+
+    /// empty the tree, then return the iterator of the owned (key, value)s in the order of the key
+    ///
+    /// The (key, value)s that are not yielded are dropped with the iterator.
+    pub fn drain(&mut self) -> Drain<K, V> {
+        let (len, height) = (self.len(), self.get_height());
+        let root = unsafe { self.root.as_mut().right.take() };
+
+        Drain {
+            stack: Vec::with_capacity(height),
+            current: root,
+            len,
+        }
+    }
+
+    /// get the (key, value) that has the smallest key
+    pub fn first(&self) -> Option<(&K, &V)> {
+        let cursor = self.find_edge(Dir::Left)?;
+        let node = unsafe { cursor.current.as_ref() };
+
+        Some((&node.key, &node.value))
+    }
+
+    /// get the (key, value) that has the greatest key
+    pub fn last(&self) -> Option<(&K, &V)> {
+        let cursor = self.find_edge(Dir::Right)?;
+        let node = unsafe { cursor.current.as_ref() };
+
+        Some((&node.key, &node.value))
+    }
+
+    /// remove the (key, value) that has the smallest key, then return it
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        Some(self.find_edge(Dir::Left)?.remove_current())
+    }
+
+    /// remove the (key, value) that has the greatest key, then return it
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        Some(self.find_edge(Dir::Right)?.remove_current())
+    }
+
+    /// get the (key, value) that has the greatest key less than or equal to the key
+    pub fn floor(&self, key: &K) -> Option<(&K, &V)> {
+        let mut current = unsafe { self.root.as_ref().right.as_ref() };
+        let mut result = None;
+
+        while let Some(node) = current {
+            match key.cmp(&node.key) {
+                Ordering::Less => current = node.left.as_ref(),
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                Ordering::Greater => {
+                    result = Some((&node.key, &node.value));
+                    current = node.right.as_ref();
+                }
+            }
+        }
+
+        result
+    }
+
+    /// get the (key, value) that has the smallest key greater than or equal to the key
+    pub fn ceiling(&self, key: &K) -> Option<(&K, &V)> {
+        let mut current = unsafe { self.root.as_ref().right.as_ref() };
+        let mut result = None;
+
+        while let Some(node) = current {
+            match key.cmp(&node.key) {
+                Ordering::Less => {
+                    result = Some((&node.key, &node.value));
+                    current = node.left.as_ref();
+                }
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                Ordering::Greater => current = node.right.as_ref(),
+            }
+        }
+
+        result
+    }
+
+    /// get the k-th smallest (key, value) on the tree (k starts from 0)
+    ///
+    /// If k >= len, return None.
+    pub fn select(&self, mut k: usize) -> Option<(&K, &V)> {
+        let mut current = unsafe { self.root.as_ref().right.as_ref() };
+
+        while let Some(node) = current {
+            let left_size = node.left_size();
+
+            if k < left_size {
+                current = node.left.as_ref();
+            } else if k == left_size {
+                return Some((&node.key, &node.value));
+            } else {
+                k -= left_size + 1;
+                current = node.right.as_ref();
+            }
+        }
+
+        None
+    }
+
+    /// get the number of the keys on the tree that are less than the key
+    pub fn rank(&self, key: &K) -> usize {
+        let mut current = unsafe { self.root.as_ref().right.as_ref() };
+        let mut rank = 0;
+
+        while let Some(node) = current {
+            if *key <= node.key {
+                current = node.left.as_ref();
+            } else {
+                rank += node.left_size() + 1;
+                current = node.right.as_ref();
+            }
+        }
+
+        rank
+    }
+
+    /// build the tree from the iterator sorted by the key in O(n)
+    ///
+    /// The keys should be strictly increasing, that is, sorted and not duplicated.
+    pub fn from_sorted_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let items: Vec<(K, V)> = iter.into_iter().collect();
+        debug_assert!(
+            items.windows(2).all(|w| w[0].0 < w[1].0),
+            "The keys should be strictly increasing."
+        );
+
+        let mut tree = Self::new();
+
+        unsafe {
+            tree.root.as_mut().right = Node::build(
+                items.len(),
+                &mut items
+                    .into_iter()
+                    .map(|(key, value)| Box::new(Node::new(key, value))),
+            );
+        }
+
+        tree
+    }
+
+    /// get the entry of the key to manipulate it in place with one descent
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        let cursor = self.find(&key);
+
+        if cursor.dir == Dir::Eq {
+            Entry::Occupied(OccupiedEntry {
+                cursor,
+                _tree: self,
+            })
+        } else {
+            Entry::Vacant(VacantEntry {
+                key,
+                cursor,
+                _tree: self,
+            })
+        }
+    }
+
+    /// retain only the (key, value)s that the predicate returns true
+    ///
+    /// The tree is traversed once, then rebuilt as perfectly balanced with the remaining nodes.
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) {
+        let mut nodes = self.drain();
+        let mut remains = Vec::with_capacity(nodes.len);
+
+        while let Some(mut node) = nodes.next_node() {
+            if f(&node.key, &mut node.value) {
+                remains.push(node);
+            }
+        }
+
+        unsafe {
+            self.root.as_mut().right = Node::build(remains.len(), &mut remains.into_iter());
+        }
+    }
+
+    /// split the tree into two at the key, then return the tree that has the keys greater than or equal to the key
+    pub fn split_off(&mut self, key: &K) -> Self {
+        let root = unsafe { self.root.as_mut() };
+        let (less, found, greater) = Node::split(root.right.take(), key);
+        root.right = less;
+
+        let mut other = Self::new();
+
+        unsafe {
+            other.root.as_mut().right = match found {
+                Some(node) => Some(Node::join(None, node, greater)),
+                None => greater,
+            };
+        }
+
+        other
+    }
+
+    /// join the other tree whose keys are all greater than the keys of this tree
+    ///
+    /// If success, return Ok(()).
+    /// If fail since the keys are overlapped, return Err(other).
+    pub fn join(&mut self, mut other: Self) -> Result<(), Self> {
+        if let (Some((last, _)), Some((first, _))) = (self.last(), other.first()) {
+            if last >= first {
+                return Err(other);
+            }
+        }
+
+        let (root, other_root) = unsafe { (self.root.as_mut(), other.root.as_mut()) };
+
+        root.right = match (root.right.take(), other_root.right.take()) {
+            (left, Some(right)) => {
+                let (right, mid) = Node::remove_first(right);
+                Some(Node::join(left, mid, right))
+            }
+            (left, None) => left,
+        };
+
+        Ok(())
+    }
 }
 
 impl<K, V> SequentialMap<K, V> for AVLTree<K, V>
@@ -342,12 +940,7 @@ where
             return Err(node.value);
         }
 
-        *(cursor.next_node_mut()) = Some(node);
-
-        unsafe {
-            cursor.current.as_mut().renew_height();
-        }
-        cursor.rebalance();
+        cursor.insert_next(node);
 
         Ok(())
     }
@@ -364,6 +957,18 @@ where
         }
     }
 
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut cursor = self.find(key);
+
+        unsafe {
+            if cursor.dir == Dir::Eq {
+                Some(&mut cursor.current.as_mut().value)
+            } else {
+                None
+            }
+        }
+    }
+
     fn remove(&mut self, key: &K) -> Result<V, ()> {
         let mut cursor = self.find(key);
 
@@ -371,46 +976,341 @@ where
             return Err(());
         }
 
-        let current = unsafe { cursor.current.as_ref() };
+        Ok(cursor.remove_current().1)
+    }
+}
 
-        let (left, right) = (current.left.is_some(), current.right.is_some());
+/// the iterator of the references of (key, value)s on in-order
+pub struct Iter<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>, // the nodes whose left subtrees are being visited
+    current: Option<&'a Node<K, V>>, // the subtree to visit next
+    len: usize,                 // the number of the remaining nodes
+}
 
-        // special case: find largest node from left subtree, swap, and remove
-        if left && right {
-            let (mut parent, dir) = cursor.ancestors.last_mut().unwrap();
-            let child = unsafe { parent.as_mut().child_mut(*dir).as_mut().unwrap() };
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
 
-            cursor.move_greatest_on_left_subtree();
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.current {
+            self.stack.push(node);
+            self.current = node.left.as_deref();
+        }
 
-            let (mut swap_node_parent, dir) = cursor.ancestors.pop().unwrap();
-            let swap_node_ptr = unsafe { swap_node_parent.as_mut().child_mut(dir) };
-            let swap_node = swap_node_ptr.as_mut().unwrap();
+        let node = self.stack.pop()?;
+        self.current = node.right.as_deref();
+        self.len -= 1;
 
-            mem::swap(&mut child.key, &mut swap_node.key);
-            mem::swap(&mut child.value, &mut swap_node.value);
+        Some((&node.key, &node.value))
+    }
 
-            let swap_node = swap_node_ptr.take().unwrap();
-            if swap_node.left.is_some() {
-                *swap_node_ptr = swap_node.left;
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+/// the iterator of (&key, &mut value)s on in-order
+pub struct IterMut<'a, K, V> {
+    stack: Vec<NonNull<Node<K, V>>>,
+    current: Option<NonNull<Node<K, V>>>,
+    len: usize,
+    _marker: PhantomData<&'a mut Node<K, V>>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            while let Some(mut node) = self.current {
+                self.stack.push(node);
+                self.current = node.as_mut().left.as_deref_mut().map(NonNull::from);
             }
 
-            cursor.rebalance();
+            // each node is yielded only once, so the mutable references are not aliased.
+            let node = self.stack.pop()?.as_mut();
+            self.current = node.right.as_deref_mut().map(NonNull::from);
+            self.len -= 1;
 
-            return Ok(swap_node.value);
+            Some((&node.key, &mut node.value))
         }
+    }
 
-        let (mut parent, dir) = cursor.ancestors.pop().unwrap();
-        let child = unsafe { parent.as_mut().child_mut(dir) };
-        let node = child.take().unwrap();
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
 
-        if left {
-            *child = node.left;
-        } else if right {
-            *child = node.right;
+impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {}
+
+impl<'a, K, V> IntoIterator for &'a AVLTree<K, V>
+where
+    K: Default + Ord + Clone,
+    V: Default,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut AVLTree<K, V>
+where
+    K: Default + Ord + Clone,
+    V: Default,
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// the iterator that takes the nodes out of the tree on in-order
+pub struct Drain<K, V> {
+    stack: Vec<Box<Node<K, V>>>, // the nodes whose left subtrees are being visited
+    current: Subtree<K, V>,      // the subtree to visit next
+    len: usize,                  // the number of the remaining nodes
+}
+
+impl<K, V> Drain<K, V> {
+    /// take the next node whose childs are detached
+    fn next_node(&mut self) -> Option<Box<Node<K, V>>> {
+        while let Some(mut node) = self.current.take() {
+            self.current = node.left.take();
+            self.stack.push(node);
+        }
+
+        let mut node = self.stack.pop()?;
+        self.current = node.right.take();
+        self.len -= 1;
+
+        Some(node)
+    }
+}
+
+impl<K, V> Iterator for Drain<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = *self.next_node()?;
+
+        Some((node.key, node.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Drain<K, V> {}
+
+/// the view of a single key on the tree, which is either occupied or vacant
+pub enum Entry<'a, K, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// the entry of the key that exists on the tree
+pub struct OccupiedEntry<'a, K, V> {
+    cursor: Cursor<K, V>, // arrived at the node of the key
+    _tree: &'a mut AVLTree<K, V>,
+}
+
+/// the entry of the key that does not exist on the tree
+pub struct VacantEntry<'a, K, V> {
+    key: K,
+    cursor: Cursor<K, V>, // the next node of the cursor is the empty place for the key
+    _tree: &'a mut AVLTree<K, V>,
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: Default + Ord + Clone,
+    V: Default,
+{
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// insert the value if vacant, then return the mutable reference of the value
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// insert the value from the function if vacant, then return the mutable reference of the value
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// insert the default value if vacant, then return the mutable reference of the value
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+
+    /// modify the value by the function if occupied
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+
+        self
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V>
+where
+    K: Default + Ord + Clone,
+    V: Default,
+{
+    pub fn key(&self) -> &K {
+        unsafe { &self.cursor.current.as_ref().key }
+    }
+
+    pub fn get(&self) -> &V {
+        unsafe { &self.cursor.current.as_ref().value }
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        unsafe { &mut self.cursor.current.as_mut().value }
+    }
+
+    /// convert the entry into the mutable reference of the value with the lifetime of the tree
+    pub fn into_mut(mut self) -> &'a mut V {
+        unsafe { &mut self.cursor.current.as_mut().value }
+    }
+
+    /// replace the value, then return the old value
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// remove (key, value) from the tree, then return the value
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// remove (key, value) from the tree, then return them
+    pub fn remove_entry(mut self) -> (K, V) {
+        self.cursor.remove_current()
+    }
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V>
+where
+    K: Default + Ord + Clone,
+    V: Default,
+{
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// insert (key, value) into the tree, then return the mutable reference of the value
+    pub fn insert(mut self, value: V) -> &'a mut V {
+        let node = Box::new(Node::new(self.key, value));
+        let mut node = self.cursor.insert_next(node);
+
+        unsafe { &mut node.as_mut().value }
+    }
+}
+
+impl<K, V> Extend<(K, V)> for AVLTree<K, V>
+where
+    K: Default + Ord + Clone,
+    V: Default,
+{
+    /// Insert all (key, value) pairs. If the key already exists, the value is overwritten.
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            if let Err(value) = self.insert(&key, value) {
+                *self.lookup_mut(&key).unwrap() = value;
+            }
+        }
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for AVLTree<K, V>
+where
+    K: Default + Ord + Clone,
+    V: Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K, V> serde::Serialize for AVLTree<K, V>
+where
+    K: serde::Serialize + Default + Ord + Clone,
+    V: serde::Serialize + Default,
+{
+    /// serialize as the map whose entries are in the order of the key
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> serde::Deserialize<'de> for AVLTree<K, V>
+where
+    K: serde::Deserialize<'de> + Default + Ord + Clone,
+    V: serde::Deserialize<'de> + Default,
+{
+    /// deserialize from the map, building the tree in O(n) if the keys are sorted
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor<K, V>(PhantomData<(K, V)>);
+
+        impl<'de, K, V> serde::de::Visitor<'de> for Visitor<K, V>
+        where
+            K: serde::Deserialize<'de> + Default + Ord + Clone,
+            V: serde::Deserialize<'de> + Default,
+        {
+            type Value = AVLTree<K, V>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                // the hint is from the input, so do not trust a huge one
+                let mut items = Vec::with_capacity(map.size_hint().unwrap_or(0).min(4096));
+
+                while let Some(item) = map.next_entry()? {
+                    items.push(item);
+                }
+
+                if items.windows(2).all(|w| w[0].0 < w[1].0) {
+                    Ok(AVLTree::from_sorted_iter(items))
+                } else {
+                    Ok(items.into_iter().collect())
+                }
+            }
         }
 
-        cursor.rebalance();
-        Ok(node.value)
+        deserializer.deserialize_map(Visitor(PhantomData))
     }
 }
 
@@ -0,0 +1,330 @@
+mod rankselect;
+
+pub use rankselect::RankSelect;
+
+use std::{
+    fmt,
+    iter::FromIterator,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign},
+};
+
+const WORD: usize = 64;
+
+/// growable vector of the bits packed in u64 words
+///
+/// The bits after `len` in the last word are always 0, so the words can be compared and counted as they are.
+/// The bitwise operations between the vectors of different lengths regard the missing bits as 0.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize,
+}
+
+/// get the number of the words for the bits
+fn words_for(bits: usize) -> usize {
+    (bits + WORD - 1) / WORD
+}
+
+impl BitVec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// make the vector of the bits with the same value
+    pub fn from_elem(len: usize, bit: bool) -> Self {
+        let mut vec = Self {
+            words: vec![if bit { !0 } else { 0 }; words_for(len)],
+            len,
+        };
+
+        vec.mask_last();
+        vec
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// get the words of the bits, where the bit i is (words[i / 64] >> (i % 64)) & 1
+    pub fn as_words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// clear the bits after len in the last word
+    fn mask_last(&mut self) {
+        if self.len % WORD != 0 {
+            *self.words.last_mut().unwrap() &= (1 << (self.len % WORD)) - 1;
+        }
+    }
+
+    pub fn push(&mut self, bit: bool) {
+        if self.len % WORD == 0 {
+            self.words.push(0);
+        }
+
+        self.len += 1;
+        self.assign(self.len - 1, bit);
+    }
+
+    pub fn pop(&mut self) -> Option<bool> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let bit = self.test(self.len - 1);
+        self.clear(self.len - 1);
+        self.len -= 1;
+
+        if self.len % WORD == 0 {
+            self.words.pop();
+        }
+
+        Some(bit)
+    }
+
+    /// resize to the length, filling the new bits with the value
+    pub fn resize(&mut self, len: usize, bit: bool) {
+        if len <= self.len {
+            self.words.truncate(words_for(len));
+            self.len = len;
+            self.mask_last();
+            return;
+        }
+
+        if bit && self.len % WORD != 0 {
+            *self.words.last_mut().unwrap() |= !0 << (self.len % WORD);
+        }
+
+        self.words.resize(words_for(len), if bit { !0 } else { 0 });
+        self.len = len;
+        self.mask_last();
+    }
+
+    /// get the bit at the index
+    pub fn test(&self, index: usize) -> bool {
+        assert!(index < self.len, "the index is out of range");
+        self.words[index / WORD] >> (index % WORD) & 1 == 1
+    }
+
+    /// set the bit at the index to 1
+    pub fn set(&mut self, index: usize) {
+        assert!(index < self.len, "the index is out of range");
+        self.words[index / WORD] |= 1 << (index % WORD);
+    }
+
+    /// set the bit at the index to 0
+    pub fn clear(&mut self, index: usize) {
+        assert!(index < self.len, "the index is out of range");
+        self.words[index / WORD] &= !(1 << (index % WORD));
+    }
+
+    pub fn toggle(&mut self, index: usize) {
+        assert!(index < self.len, "the index is out of range");
+        self.words[index / WORD] ^= 1 << (index % WORD);
+    }
+
+    pub fn assign(&mut self, index: usize, bit: bool) {
+        if bit {
+            self.set(index);
+        } else {
+            self.clear(index);
+        }
+    }
+
+    /// set all bits to the value
+    pub fn fill(&mut self, bit: bool) {
+        self.words
+            .iter_mut()
+            .for_each(|word| *word = if bit { !0 } else { 0 });
+        self.mask_last();
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn count_zeros(&self) -> usize {
+        self.len - self.count_ones()
+    }
+
+    /// get the number of 1s in [0, index) in O(index / 64)
+    pub fn rank1(&self, index: usize) -> usize {
+        assert!(index <= self.len, "the index is out of range");
+
+        let full: usize = self.words[..index / WORD]
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum();
+
+        if index % WORD == 0 {
+            full
+        } else {
+            let mask = (1 << (index % WORD)) - 1;
+            full + (self.words[index / WORD] & mask).count_ones() as usize
+        }
+    }
+
+    /// get the number of 0s in [0, index) in O(index / 64)
+    pub fn rank0(&self, index: usize) -> usize {
+        index - self.rank1(index)
+    }
+
+    /// get the iterator of the bits
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            vec: self,
+            index: 0,
+        }
+    }
+
+    /// get the iterator of the indices of 1s in the increasing order
+    pub fn ones(&self) -> Ones<'_> {
+        Ones {
+            words: &self.words,
+            base: 0,
+            current: self.words.first().copied().unwrap_or(0),
+        }
+    }
+
+    /// apply the operation to the words, regarding the missing bits as 0
+    fn combine(&mut self, other: &BitVec, op: impl Fn(u64, u64) -> u64) {
+        if self.len < other.len {
+            self.resize(other.len, false);
+        }
+
+        for (index, word) in self.words.iter_mut().enumerate() {
+            *word = op(*word, other.words.get(index).copied().unwrap_or(0));
+        }
+    }
+}
+
+impl fmt::Debug for BitVec {
+    /// format as the bits from the index 0
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.iter()
+            .try_for_each(|bit| f.write_str(if bit { "1" } else { "0" }))
+    }
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl Extend<bool> for BitVec {
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|bit| self.push(bit));
+    }
+}
+
+impl BitAndAssign<&BitVec> for BitVec {
+    fn bitand_assign(&mut self, other: &BitVec) {
+        self.combine(other, |a, b| a & b);
+    }
+}
+
+impl BitOrAssign<&BitVec> for BitVec {
+    fn bitor_assign(&mut self, other: &BitVec) {
+        self.combine(other, |a, b| a | b);
+    }
+}
+
+impl BitXorAssign<&BitVec> for BitVec {
+    fn bitxor_assign(&mut self, other: &BitVec) {
+        self.combine(other, |a, b| a ^ b);
+    }
+}
+
+impl BitAnd for &BitVec {
+    type Output = BitVec;
+
+    fn bitand(self, other: &BitVec) -> BitVec {
+        let mut result = self.clone();
+        result &= other;
+        result
+    }
+}
+
+impl BitOr for &BitVec {
+    type Output = BitVec;
+
+    fn bitor(self, other: &BitVec) -> BitVec {
+        let mut result = self.clone();
+        result |= other;
+        result
+    }
+}
+
+impl BitXor for &BitVec {
+    type Output = BitVec;
+
+    fn bitxor(self, other: &BitVec) -> BitVec {
+        let mut result = self.clone();
+        result ^= other;
+        result
+    }
+}
+
+pub struct Iter<'a> {
+    vec: &'a BitVec,
+    index: usize,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.vec.len {
+            return None;
+        }
+
+        self.index += 1;
+        Some(self.vec.test(self.index - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = self.vec.len - self.index;
+        (rest, Some(rest))
+    }
+}
+
+impl<'a> IntoIterator for &'a BitVec {
+    type Item = bool;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct Ones<'a> {
+    words: &'a [u64],
+    base: usize,  // the index of the first bit of the current word
+    current: u64, // the rest of the current word
+}
+
+impl<'a> Iterator for Ones<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current == 0 {
+            self.base += WORD;
+            self.current = *self.words.get(self.base / WORD)?;
+        }
+
+        let index = self.base + self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+
+        Some(index)
+    }
+}
@@ -0,0 +1,519 @@
+use std::{
+    fmt::Debug,
+    mem,
+    ops::{Bound, RangeBounds},
+    ptr,
+};
+
+use crate::map::SequentialMap;
+
+const MAX_KEYS: usize = 16;
+const MIN_KEYS: usize = MAX_KEYS / 2;
+
+/// B+ tree whose (key, value)s are all on the leaves, and the leaves are linked in the order of the key
+///
+/// The inner nodes only have the keys to route, so the range scan goes through the leaves only.
+pub struct BPlusTree<K, V> {
+    root: Node<K, V>,
+    len: usize,
+}
+
+unsafe impl<K: Send, V: Send> Send for BPlusTree<K, V> {}
+unsafe impl<K: Sync, V: Sync> Sync for BPlusTree<K, V> {}
+
+/// the key to route and the right node, which are made by splitting the overflowed node
+type Split<K, V> = (K, Node<K, V>);
+
+enum Node<K, V> {
+    Inner(Box<Inner<K, V>>),
+    Leaf(Box<Leaf<K, V>>),
+}
+
+/// the inner node whose children[i] has the keys in [keys[i - 1], keys[i])
+struct Inner<K, V> {
+    keys: Vec<K>,
+    children: Vec<Node<K, V>>,
+}
+
+struct Leaf<K, V> {
+    keys: Vec<K>,
+    values: Vec<V>,
+    next: *mut Leaf<K, V>,
+}
+
+impl<K, V> Leaf<K, V> {
+    fn new() -> Self {
+        Self {
+            keys: Vec::with_capacity(MAX_KEYS + 1),
+            values: Vec::with_capacity(MAX_KEYS + 1),
+            next: ptr::null_mut(),
+        }
+    }
+}
+
+impl<K, V> Node<K, V> {
+    fn len(&self) -> usize {
+        match self {
+            Node::Inner(inner) => inner.keys.len(),
+            Node::Leaf(leaf) => leaf.keys.len(),
+        }
+    }
+}
+
+impl<K: Ord, V> Inner<K, V> {
+    /// get the index of the child that may have the key
+    fn child_index(&self, key: &K) -> usize {
+        self.keys.partition_point(|k| k <= key)
+    }
+}
+
+impl<K: Ord + Clone, V> Node<K, V> {
+    /// insert (key, value) into the subtree
+    ///
+    /// If the node is overflowed, split it and return Ok(Some((the key to route, the right node))).
+    fn insert(&mut self, key: &K, value: V) -> Result<Option<Split<K, V>>, V> {
+        match self {
+            Node::Leaf(leaf) => {
+                let index = match leaf.keys.binary_search(key) {
+                    Ok(_) => return Err(value),
+                    Err(index) => index,
+                };
+
+                leaf.keys.insert(index, key.clone());
+                leaf.values.insert(index, value);
+
+                if leaf.keys.len() <= MAX_KEYS {
+                    return Ok(None);
+                }
+
+                let mut right = Box::new(Leaf::new());
+                right.keys.extend(leaf.keys.drain(MIN_KEYS..));
+                right.values.extend(leaf.values.drain(MIN_KEYS..));
+                right.next = leaf.next;
+                leaf.next = right.as_mut();
+
+                Ok(Some((right.keys[0].clone(), Node::Leaf(right))))
+            }
+            Node::Inner(inner) => {
+                let index = inner.child_index(key);
+
+                let (split_key, split_node) = match inner.children[index].insert(key, value)? {
+                    Some(split) => split,
+                    None => return Ok(None),
+                };
+
+                inner.keys.insert(index, split_key);
+                inner.children.insert(index + 1, split_node);
+
+                if inner.keys.len() <= MAX_KEYS {
+                    return Ok(None);
+                }
+
+                let keys = inner.keys.split_off(MIN_KEYS + 1);
+                let children = inner.children.split_off(MIN_KEYS + 1);
+                let split_key = inner.keys.pop().unwrap();
+
+                Ok(Some((
+                    split_key,
+                    Node::Inner(Box::new(Inner { keys, children })),
+                )))
+            }
+        }
+    }
+
+    /// remove (key, value) from the subtree, then fill the underflowed child if exists
+    fn remove(&mut self, key: &K) -> Result<V, ()> {
+        match self {
+            Node::Leaf(leaf) => {
+                let index = leaf.keys.binary_search(key).map_err(|_| ())?;
+                leaf.keys.remove(index);
+
+                Ok(leaf.values.remove(index))
+            }
+            Node::Inner(inner) => {
+                let index = inner.child_index(key);
+                let value = inner.children[index].remove(key)?;
+
+                if inner.children[index].len() < MIN_KEYS {
+                    inner.fill(index);
+                }
+
+                Ok(value)
+            }
+        }
+    }
+}
+
+impl<K: Clone, V> Inner<K, V> {
+    /// fill the underflowed child by borrowing from the sibling, or merging with the sibling
+    fn fill(&mut self, index: usize) {
+        if index > 0 && self.children[index - 1].len() > MIN_KEYS {
+            self.borrow_from_left(index);
+        } else if index + 1 < self.children.len() && self.children[index + 1].len() > MIN_KEYS {
+            self.borrow_from_right(index);
+        } else if index > 0 {
+            self.merge(index - 1);
+        } else {
+            self.merge(index);
+        }
+    }
+
+    fn borrow_from_left(&mut self, index: usize) {
+        let (left, right) = self.children.split_at_mut(index);
+
+        match (&mut left[index - 1], &mut right[0]) {
+            (Node::Leaf(left), Node::Leaf(current)) => {
+                current.keys.insert(0, left.keys.pop().unwrap());
+                current.values.insert(0, left.values.pop().unwrap());
+                self.keys[index - 1] = current.keys[0].clone();
+            }
+            (Node::Inner(left), Node::Inner(current)) => {
+                let key = mem::replace(&mut self.keys[index - 1], left.keys.pop().unwrap());
+                current.keys.insert(0, key);
+                current.children.insert(0, left.children.pop().unwrap());
+            }
+            _ => unreachable!("The siblings should have the same depth."),
+        }
+    }
+
+    fn borrow_from_right(&mut self, index: usize) {
+        let (left, right) = self.children.split_at_mut(index + 1);
+
+        match (&mut left[index], &mut right[0]) {
+            (Node::Leaf(current), Node::Leaf(right)) => {
+                current.keys.push(right.keys.remove(0));
+                current.values.push(right.values.remove(0));
+                self.keys[index] = right.keys[0].clone();
+            }
+            (Node::Inner(current), Node::Inner(right)) => {
+                let key = mem::replace(&mut self.keys[index], right.keys.remove(0));
+                current.keys.push(key);
+                current.children.push(right.children.remove(0));
+            }
+            _ => unreachable!("The siblings should have the same depth."),
+        }
+    }
+
+    /// merge the children[index + 1] into the children[index]
+    fn merge(&mut self, index: usize) {
+        let key = self.keys.remove(index);
+        let right = self.children.remove(index + 1);
+
+        match (&mut self.children[index], right) {
+            (Node::Leaf(left), Node::Leaf(mut right)) => {
+                left.keys.append(&mut right.keys);
+                left.values.append(&mut right.values);
+                left.next = right.next;
+            }
+            (Node::Inner(left), Node::Inner(mut right)) => {
+                left.keys.push(key);
+                left.keys.append(&mut right.keys);
+                left.children.append(&mut right.children);
+            }
+            _ => unreachable!("The siblings should have the same depth."),
+        }
+    }
+}
+
+impl<K: Ord + Debug, V> Node<K, V> {
+    /// check the invariants of the subtree whose keys should be in [from, to), then return (depth, size)
+    ///
+    /// The leaves should be pushed in the order of the key to check their links.
+    fn validate(
+        &self,
+        from: Option<&K>,
+        to: Option<&K>,
+        is_root: bool,
+        leaves: &mut Vec<*const Leaf<K, V>>,
+    ) -> Result<(usize, usize), String> {
+        let keys = match self {
+            Node::Inner(inner) => &inner.keys,
+            Node::Leaf(leaf) => &leaf.keys,
+        };
+
+        if keys.len() > MAX_KEYS || (!is_root && keys.len() < MIN_KEYS) {
+            return Err(format!(
+                "The node {:?} has invalid number of the keys.",
+                keys
+            ));
+        }
+
+        if keys.windows(2).any(|w| w[0] >= w[1])
+            || keys
+                .first()
+                .map_or(false, |k| from.map_or(false, |from| from > k))
+            || keys
+                .last()
+                .map_or(false, |k| to.map_or(false, |to| k >= to))
+        {
+            return Err(format!(
+                "The keys {:?} are not sorted in the range [{:?}, {:?}).",
+                keys, from, to
+            ));
+        }
+
+        match self {
+            Node::Leaf(leaf) => {
+                if leaf.keys.len() != leaf.values.len() {
+                    return Err(format!(
+                        "The leaf {:?} has different number of the values.",
+                        keys
+                    ));
+                }
+
+                if let Some(&prev) = leaves.last() {
+                    if !ptr::eq(unsafe { (*prev).next }, leaf.as_ref()) {
+                        return Err(format!(
+                            "The leaf {:?} is not linked from the previous.",
+                            keys
+                        ));
+                    }
+                }
+
+                leaves.push(leaf.as_ref());
+                Ok((0, leaf.keys.len()))
+            }
+            Node::Inner(inner) => {
+                if inner.children.len() != inner.keys.len() + 1 {
+                    return Err(format!(
+                        "The inner {:?} has invalid number of the children.",
+                        keys
+                    ));
+                }
+
+                let mut depth = None;
+                let mut size = 0;
+
+                for (index, child) in inner.children.iter().enumerate() {
+                    let from = if index > 0 {
+                        Some(&inner.keys[index - 1])
+                    } else {
+                        from
+                    };
+                    let to = inner.keys.get(index).or(to);
+                    let (child_depth, child_size) = child.validate(from, to, false, leaves)?;
+
+                    if *depth.get_or_insert(child_depth) != child_depth {
+                        return Err(format!(
+                            "The children of the inner {:?} have different depths.",
+                            keys
+                        ));
+                    }
+
+                    size += child_size;
+                }
+
+                Ok((depth.unwrap() + 1, size))
+            }
+        }
+    }
+}
+
+impl<K, V> Default for BPlusTree<K, V> {
+    fn default() -> Self {
+        Self {
+            root: Node::Leaf(Box::new(Leaf::new())),
+            len: 0,
+        }
+    }
+}
+
+impl<K, V> BPlusTree<K, V> {
+    /// get the number of the (key, value)s on the tree
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<K: Ord, V> BPlusTree<K, V> {
+    /// find the leaf that may have the key
+    fn find_leaf(&self, key: &K) -> &Leaf<K, V> {
+        let mut current = &self.root;
+
+        loop {
+            match current {
+                Node::Inner(inner) => current = &inner.children[inner.child_index(key)],
+                Node::Leaf(leaf) => return leaf,
+            }
+        }
+    }
+
+    /// find the leftmost leaf
+    fn first_leaf(&self) -> &Leaf<K, V> {
+        let mut current = &self.root;
+
+        loop {
+            match current {
+                Node::Inner(inner) => current = &inner.children[0],
+                Node::Leaf(leaf) => return leaf,
+            }
+        }
+    }
+
+    /// get the iterator of (&key, &value)s in the order of the key
+    pub fn iter(&self) -> Range<'_, K, V>
+    where
+        K: Clone,
+    {
+        self.range(..)
+    }
+
+    /// get the iterator of (&key, &value)s in the range in the order of the key
+    ///
+    /// It descends to the first leaf once, then follows the links of the leaves.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V>
+    where
+        K: Clone,
+    {
+        let (leaf, index) = match range.start_bound() {
+            Bound::Included(start) => {
+                let leaf = self.find_leaf(start);
+                (leaf, leaf.keys.partition_point(|k| k < start))
+            }
+            Bound::Excluded(start) => {
+                let leaf = self.find_leaf(start);
+                (leaf, leaf.keys.partition_point(|k| k <= start))
+            }
+            Bound::Unbounded => (self.first_leaf(), 0),
+        };
+
+        Range {
+            leaf: Some(leaf),
+            index,
+            end: range.end_bound().cloned(),
+        }
+    }
+}
+
+impl<K: Ord + Debug, V> BPlusTree<K, V> {
+    /// check the ordering of the keys, the size of every node, the depth of the leaves and the links of the leaves
+    ///
+    /// If the tree is valid, return Ok(()), or Err(the description of the first violation).
+    pub fn validate(&self) -> Result<(), String> {
+        let mut leaves = Vec::new();
+        let (_, size) = self.root.validate(None, None, true, &mut leaves)?;
+
+        if size != self.len {
+            return Err(format!(
+                "The tree has {} keys, but the len is {}.",
+                size, self.len
+            ));
+        }
+
+        if let Some(&last) = leaves.last() {
+            if unsafe { !(*last).next.is_null() } {
+                return Err("The last leaf is linked to another.".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<K, V> SequentialMap<K, V> for BPlusTree<K, V>
+where
+    K: Ord + Clone,
+{
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(&mut self, key: &K, value: V) -> Result<(), V> {
+        if let Some((split_key, split_node)) = self.root.insert(key, value)? {
+            let left = mem::replace(&mut self.root, Node::Leaf(Box::new(Leaf::new())));
+
+            self.root = Node::Inner(Box::new(Inner {
+                keys: vec![split_key],
+                children: vec![left, split_node],
+            }));
+        }
+
+        self.len += 1;
+        Ok(())
+    }
+
+    fn lookup(&self, key: &K) -> Option<&V> {
+        let leaf = self.find_leaf(key);
+
+        leaf.keys
+            .binary_search(key)
+            .ok()
+            .map(|index| &leaf.values[index])
+    }
+
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut current = &mut self.root;
+
+        loop {
+            match current {
+                Node::Inner(inner) => {
+                    let index = inner.child_index(key);
+                    current = &mut inner.children[index];
+                }
+                Node::Leaf(leaf) => {
+                    return match leaf.keys.binary_search(key) {
+                        Ok(index) => Some(&mut leaf.values[index]),
+                        Err(_) => None,
+                    };
+                }
+            }
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Result<V, ()> {
+        let value = self.root.remove(key)?;
+        self.len -= 1;
+
+        // the root that has only one child is replaced by the child
+        if let Node::Inner(inner) = &mut self.root {
+            if inner.keys.is_empty() {
+                let child = inner.children.pop().unwrap();
+                self.root = child;
+            }
+        }
+
+        Ok(value)
+    }
+}
+
+/// the iterator of the references of (key, value)s in the range, following the links of the leaves
+pub struct Range<'a, K, V> {
+    leaf: Option<&'a Leaf<K, V>>,
+    index: usize,
+    end: Bound<K>,
+}
+
+impl<'a, K: Ord, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let leaf = self.leaf?;
+
+            if self.index < leaf.keys.len() {
+                let key = &leaf.keys[self.index];
+
+                let in_range = match &self.end {
+                    Bound::Included(end) => key <= end,
+                    Bound::Excluded(end) => key < end,
+                    Bound::Unbounded => true,
+                };
+
+                if !in_range {
+                    self.leaf = None;
+                    return None;
+                }
+
+                self.index += 1;
+                return Some((key, &leaf.values[self.index - 1]));
+            }
+
+            self.leaf = unsafe { leaf.next.as_ref() };
+            self.index = 0;
+        }
+    }
+}
@@ -0,0 +1,303 @@
+/*
+ Refer to
+ The Art of Multiprocessor Programming, 9.5 Fine-Grained Synchronization
+*/
+
+use std::{cell::UnsafeCell, ptr};
+
+use crate::lock::{RawMutex, RawSimpleLock};
+use crate::map::ConcurrentMap;
+
+/// external binary search tree synchronized by lock coupling(hand-over-hand locking)
+///
+/// The (key, value)s are only on the leaf nodes, and the internal nodes have the key only for routing.
+/// On descending, the lock of the child is acquired before releasing the lock of the parent.
+/// So, the thread that holds the lock of a node is the only one that can reach its subtree from the node.
+pub struct LockCouplingBST<K, V> {
+    root: Box<Node<K, V>>, // root node is dummy, and the tree is on its left child
+}
+
+unsafe impl<K: Send, V: Send> Send for LockCouplingBST<K, V> {}
+unsafe impl<K: Send + Sync, V: Send + Sync> Sync for LockCouplingBST<K, V> {}
+
+struct Node<K, V> {
+    key: K,
+    lock: RawMutex,
+    inner: UnsafeCell<NodeInner<K, V>>,
+}
+
+/// The leaf node has the value and no childs, and the internal node has two childs and no value.
+struct NodeInner<K, V> {
+    value: Option<V>,
+    left: *mut Node<K, V>,
+    right: *mut Node<K, V>,
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: Option<V>, left: *mut Node<K, V>, right: *mut Node<K, V>) -> Self {
+        Self {
+            key,
+            lock: RawMutex::new(),
+            inner: UnsafeCell::new(NodeInner { value, left, right }),
+        }
+    }
+
+    fn new_leaf(key: K, value: V) -> *mut Self {
+        Box::into_raw(Box::new(Self::new(
+            key,
+            Some(value),
+            ptr::null_mut(),
+            ptr::null_mut(),
+        )))
+    }
+
+    fn new_internal(key: K, left: *mut Node<K, V>, right: *mut Node<K, V>) -> *mut Self {
+        Box::into_raw(Box::new(Self::new(key, None, left, right)))
+    }
+
+    /// get the inner of the node
+    ///
+    /// The caller should hold the lock of the node.
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn inner(&self) -> &mut NodeInner<K, V> {
+        &mut *self.inner.get()
+    }
+
+    /// check if the node is leaf
+    ///
+    /// The caller should hold the lock of the node.
+    unsafe fn is_leaf(&self) -> bool {
+        self.inner().left.is_null()
+    }
+
+    /// replace the child pointer `old` with `new`
+    ///
+    /// The caller should hold the lock of the node.
+    unsafe fn replace_child(&self, old: *mut Node<K, V>, new: *mut Node<K, V>) {
+        let inner = self.inner();
+
+        if inner.left == old {
+            inner.left = new;
+        } else {
+            debug_assert!(inner.right == old);
+            inner.right = new;
+        }
+    }
+}
+
+impl<K: Ord, V> Node<K, V> {
+    /// get the child to go for the key
+    ///
+    /// The caller should hold the lock of the node, and the node should be internal.
+    unsafe fn child(&self, key: &K) -> *mut Node<K, V> {
+        let inner = self.inner();
+
+        if *key < self.key {
+            inner.left
+        } else {
+            inner.right
+        }
+    }
+}
+
+impl<K: Ord, V> LockCouplingBST<K, V> {
+    /// descend from the root to the leaf for the key by lock coupling
+    ///
+    /// Return (parent, leaf) that are both locked. The parent may be the dummy root.
+    /// If the tree is empty, return (root, null) with locking the root only.
+    unsafe fn find_leaf(&self, key: &K) -> (*mut Node<K, V>, *mut Node<K, V>) {
+        let mut parent = &*self.root as *const _ as *mut Node<K, V>;
+        (*parent).lock.lock();
+
+        let mut current = (*parent).inner().left;
+
+        if current.is_null() {
+            return (parent, current);
+        }
+
+        (*current).lock.lock();
+
+        while !(*current).is_leaf() {
+            let next = (*current).child(key);
+            (*next).lock.lock();
+            (*parent).lock.unlock();
+
+            parent = current;
+            current = next;
+        }
+
+        (parent, current)
+    }
+}
+
+impl<K, V> ConcurrentMap<K, V> for LockCouplingBST<K, V>
+where
+    K: Ord + Clone + Default,
+{
+    fn new() -> Self {
+        Self {
+            root: Box::new(Node::new(
+                K::default(),
+                None,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )),
+        }
+    }
+
+    fn insert(&self, key: &K, value: V) -> Result<(), V> {
+        unsafe {
+            let (parent, leaf) = self.find_leaf(key);
+
+            if leaf.is_null() {
+                (*parent).inner().left = Node::new_leaf(key.clone(), value);
+                (*parent).lock.unlock();
+                return Ok(());
+            }
+
+            let leaf_ref = &*leaf;
+
+            if leaf_ref.key == *key {
+                leaf_ref.lock.unlock();
+                (*parent).lock.unlock();
+                return Err(value);
+            }
+
+            // replace the leaf with the internal node that has the leaf and the new leaf as childs
+            let new = Node::new_leaf(key.clone(), value);
+            let internal = if *key < leaf_ref.key {
+                Node::new_internal(leaf_ref.key.clone(), new, leaf)
+            } else {
+                Node::new_internal(key.clone(), leaf, new)
+            };
+
+            (*parent).replace_child(leaf, internal);
+
+            leaf_ref.lock.unlock();
+            (*parent).lock.unlock();
+            Ok(())
+        }
+    }
+
+    fn lookup<F, R>(&self, key: &K, f: F) -> R
+    where
+        F: FnOnce(Option<&V>) -> R,
+    {
+        unsafe {
+            let (parent, leaf) = self.find_leaf(key);
+            (*parent).lock.unlock();
+
+            if leaf.is_null() {
+                return f(None);
+            }
+
+            let leaf_ref = &*leaf;
+            let result = if leaf_ref.key == *key {
+                f(leaf_ref.inner().value.as_ref())
+            } else {
+                f(None)
+            };
+
+            leaf_ref.lock.unlock();
+            result
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.lookup(key, |value| value.cloned())
+    }
+
+    fn remove(&self, key: &K) -> Result<V, ()> {
+        unsafe {
+            let root = &*self.root as *const _ as *mut Node<K, V>;
+            (*root).lock.lock();
+
+            let mut current = (*root).inner().left;
+
+            if current.is_null() {
+                (*root).lock.unlock();
+                return Err(());
+            }
+
+            (*current).lock.lock();
+            let mut grand_parent: *mut Node<K, V> = ptr::null_mut();
+            let mut parent = root;
+
+            // hold three locks of (grand parent, parent, current) to detach the parent
+            while !(*current).is_leaf() {
+                let next = (*current).child(key);
+                (*next).lock.lock();
+
+                if !grand_parent.is_null() {
+                    (*grand_parent).lock.unlock();
+                }
+
+                grand_parent = parent;
+                parent = current;
+                current = next;
+            }
+
+            if (*current).key != *key {
+                (*current).lock.unlock();
+                (*parent).lock.unlock();
+
+                if !grand_parent.is_null() {
+                    (*grand_parent).lock.unlock();
+                }
+
+                return Err(());
+            }
+
+            let value = (*current).inner().value.take().unwrap();
+
+            if grand_parent.is_null() {
+                // the leaf is the only node on the tree
+                (*parent).replace_child(current, ptr::null_mut());
+
+                (*current).lock.unlock();
+                (*parent).lock.unlock();
+            } else {
+                // replace the parent with the sibling of the leaf
+                let parent_inner = (*parent).inner();
+                let sibling = if parent_inner.left == current {
+                    parent_inner.right
+                } else {
+                    parent_inner.left
+                };
+
+                (*grand_parent).replace_child(parent, sibling);
+
+                (*current).lock.unlock();
+                (*parent).lock.unlock();
+                (*grand_parent).lock.unlock();
+
+                // No one can reach the parent since the grand parent was locked on detaching.
+                drop(Box::from_raw(parent));
+            }
+
+            drop(Box::from_raw(current));
+
+            Ok(value)
+        }
+    }
+}
+
+impl<K, V> Drop for LockCouplingBST<K, V> {
+    fn drop(&mut self) {
+        let mut nodes = vec![self.root.inner.get_mut().left];
+
+        while let Some(node) = nodes.pop() {
+            if node.is_null() {
+                continue;
+            }
+
+            let mut node = unsafe { Box::from_raw(node) };
+            let inner = node.inner.get_mut();
+            nodes.push(inner.left);
+            nodes.push(inner.right);
+        }
+    }
+}
@@ -0,0 +1,3 @@
+mod lockcoupling;
+
+pub use lockcoupling::LockCouplingBST;
@@ -1,13 +1,12 @@
 use std::cell::RefCell;
 use std::fmt::Debug;
-use std::ptr;
-use std::{cmp::Ordering, mem, ptr::NonNull};
+use std::iter::FromIterator;
+use std::ops::{Index, IndexMut};
+use std::{cmp::Ordering, mem, mem::MaybeUninit, ptr::NonNull};
+use std::{ptr, slice};
 
 use crate::map::SequentialMap;
 
-const B_MAX_NODES: usize = 11;
-const B_MID_INDEX: usize = B_MAX_NODES / 2;
-
 /// insert value into [T], which has one empty area on last.
 /// ex) insert C at 1 into [A, B, uninit] => [A, C, B]
 unsafe fn slice_insert<T>(ptr: &mut [T], index: usize, value: T) {
@@ -39,15 +38,54 @@ unsafe fn slice_remove<T>(ptr: &mut [T], index: usize) -> T {
     value
 }
 
-struct Node<K, V> {
+/// the array of B + 1 values, since `[T; B + 1]` cannot be written with the generic B
+#[repr(C)]
+struct Edges<T, const B: usize> {
+    init: [T; B],
+    last: MaybeUninit<T>,
+}
+
+impl<T, const B: usize> Edges<T, B> {
+    fn as_ptr(&self) -> *const T {
+        self as *const Self as *const T
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        self as *mut Self as *mut T
+    }
+
+    fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.as_ptr(), B + 1) }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), B + 1) }
+    }
+}
+
+impl<T, const B: usize> Index<usize> for Edges<T, B> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.as_slice()[index]
+    }
+}
+
+impl<T, const B: usize> IndexMut<usize> for Edges<T, B> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.as_mut_slice()[index]
+    }
+}
+
+struct Node<K, V, const B: usize> {
     size: usize,
     depth: usize,
-    keys: [K; B_MAX_NODES],
-    edges: [Box<Node<K, V>>; B_MAX_NODES + 1],
-    values: [V; B_MAX_NODES],
+    keys: [K; B],
+    edges: Edges<Box<Node<K, V, B>>, B>,
+    values: [V; B],
 }
 
-impl<K, V> Drop for Node<K, V> {
+impl<K, V, const B: usize> Drop for Node<K, V, B> {
     fn drop(&mut self) {
         if self.size > 0 {
             panic!("The node should be emptied before dropping!")
@@ -57,7 +95,7 @@ impl<K, V> Drop for Node<K, V> {
     }
 }
 
-impl<K: Debug, V: Debug> Debug for Node<K, V> {
+impl<K: Debug, V: Debug, const B: usize> Debug for Node<K, V, B> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Node")
             .field("size", &self.size)
@@ -69,10 +107,17 @@ impl<K: Debug, V: Debug> Debug for Node<K, V> {
     }
 }
 
-impl<K, V> Node<K, V> {
+impl<K, V, const B: usize> Node<K, V, B> {
+    const MID: usize = B / 2;
+
     // since most of MaybeUnit APIs are experimental, I use very dangerous `mem::uninitialized until they become stable
     #[allow(deprecated, invalid_value)]
     fn new() -> Self {
+        assert!(
+            B >= 2,
+            "The node should be able to have at least 2 (key, value)s."
+        );
+
         Self {
             size: 0,
             depth: 0,
@@ -83,15 +128,15 @@ impl<K, V> Node<K, V> {
     }
 }
 
-enum InsertResult<K, V> {
+enum InsertResult<K, V, const B: usize> {
     Fitted,
     Splitted {
         parent: (K, V),
-        right: Box<Node<K, V>>,
+        right: Box<Node<K, V, B>>,
     },
 }
 
-impl<K, V> Node<K, V> {
+impl<K, V, const B: usize> Node<K, V, B> {
     fn keys(&self) -> &[K] {
         unsafe { self.keys.get_unchecked(..self.size) }
     }
@@ -108,17 +153,21 @@ impl<K, V> Node<K, V> {
         unsafe { self.values.get_unchecked_mut(..self.size) }
     }
 
-    fn edges(&self) -> &[Box<Node<K, V>>] {
+    fn edges(&self) -> &[Box<Node<K, V, B>>] {
         if self.depth > 0 {
-            unsafe { self.edges.get_unchecked(..(self.size + 1)) }
+            unsafe { self.edges.as_slice().get_unchecked(..(self.size + 1)) }
         } else {
             &[]
         }
     }
 
-    fn mut_edges(&mut self) -> &mut [Box<Node<K, V>>] {
+    fn mut_edges(&mut self) -> &mut [Box<Node<K, V, B>>] {
         if self.depth > 0 {
-            unsafe { self.edges.get_unchecked_mut(..(self.size + 1)) }
+            unsafe {
+                self.edges
+                    .as_mut_slice()
+                    .get_unchecked_mut(..(self.size + 1))
+            }
         } else {
             &mut []
         }
@@ -141,11 +190,70 @@ impl<K, V> Node<K, V> {
 
         mem::forget(self);
     }
+
+    /// get the maximum number of the (key, value)s on the tree of the depth
+    fn max_size(depth: usize) -> usize {
+        (B + 1)
+            .checked_pow(depth as u32 + 1)
+            .map_or(usize::MAX, |edges| edges - 1)
+    }
+
+    /// build the tree of the depth from the first size (key, value)s of the iterator
+    ///
+    /// Each node takes as few (key, value)s as possible, and spreads the rest over its edges evenly.
+    fn build<I: Iterator<Item = (K, V)>>(size: usize, depth: usize, iter: &mut I) -> Box<Self> {
+        let mut node = Box::new(Self::new());
+        node.depth = depth;
+
+        if depth == 0 {
+            debug_assert!(size <= B);
+
+            for index in 0..size {
+                let (key, value) = iter.next().unwrap();
+
+                unsafe {
+                    ptr::write(node.keys.as_mut_ptr().add(index), key);
+                    ptr::write(node.values.as_mut_ptr().add(index), value);
+                }
+            }
+
+            node.size = size;
+            return node;
+        }
+
+        let edge_max_size = Self::max_size(depth - 1);
+        let node_size = ((size + 1 + edge_max_size) / (edge_max_size + 1) - 1).max(1);
+        debug_assert!(node_size <= B);
+
+        let rest = size - node_size;
+        let (edge_size, remainder) = (rest / (node_size + 1), rest % (node_size + 1));
+
+        for index in 0..=node_size {
+            let edge_size = edge_size + if index < remainder { 1 } else { 0 };
+            let edge = Node::build(edge_size, depth - 1, iter);
+
+            unsafe {
+                ptr::write(node.edges.as_mut_ptr().add(index), edge);
+            }
+
+            if index < node_size {
+                let (key, value) = iter.next().unwrap();
+
+                unsafe {
+                    ptr::write(node.keys.as_mut_ptr().add(index), key);
+                    ptr::write(node.values.as_mut_ptr().add(index), value);
+                }
+            }
+        }
+
+        node.size = node_size;
+        node
+    }
 }
 
-impl<K: Ord, V> Node<K, V> {
-    fn insert_leaf(&mut self, edge_index: usize, key: K, value: V) -> InsertResult<K, V> {
-        if self.size < B_MAX_NODES {
+impl<K: Ord, V, const B: usize> Node<K, V, B> {
+    fn insert_leaf(&mut self, edge_index: usize, key: K, value: V) -> InsertResult<K, V, B> {
+        if self.size < B {
             self.size += 1;
 
             unsafe {
@@ -161,36 +269,36 @@ impl<K: Ord, V> Node<K, V> {
             // Make parent-[(1, 1)] and return InsertResult::Splitted { parent: (2, 2), right: Node { data: [(3, 3)] }}.
 
             let mut node = Box::new(Node::new());
-            node.size = B_MAX_NODES - B_MID_INDEX;
+            node.size = B - Self::MID;
 
-            match edge_index.cmp(&B_MID_INDEX) {
+            match edge_index.cmp(&Self::MID) {
                 Ordering::Less => {
                     // on [(1, _), (2, _)], insert (0, _) with edge_index = 0
                     unsafe {
                         // TODO: can be optimized by partial copy between remove and insert
                         let mid = (
-                            slice_remove(self.mut_keys(), B_MID_INDEX - 1),
-                            slice_remove(self.mut_values(), B_MID_INDEX - 1),
+                            slice_remove(self.mut_keys(), Self::MID - 1),
+                            slice_remove(self.mut_values(), Self::MID - 1),
                         );
 
                         slice_insert(self.mut_keys(), edge_index, key);
                         slice_insert(self.mut_values(), edge_index, value);
 
                         ptr::copy_nonoverlapping(
-                            self.keys.as_mut_ptr().add(B_MID_INDEX),
+                            self.keys.as_mut_ptr().add(Self::MID),
                             node.keys.as_mut_ptr(),
-                            B_MAX_NODES - B_MID_INDEX,
+                            B - Self::MID,
                         );
                         ptr::copy_nonoverlapping(
-                            self.values.as_mut_ptr().add(B_MID_INDEX),
+                            self.values.as_mut_ptr().add(Self::MID),
                             node.values.as_mut_ptr(),
-                            B_MAX_NODES - B_MID_INDEX,
+                            B - Self::MID,
                         );
 
-                        self.size = B_MID_INDEX;
+                        self.size = Self::MID;
 
-                        // debug_assert!(self.data.len() == B_MID_INDEX);
-                        // debug_assert!(node.data.len() == B_MID_INDEX);
+                        // debug_assert!(self.data.len() == Self::MID);
+                        // debug_assert!(node.data.len() == Self::MID);
 
                         InsertResult::Splitted {
                             parent: mid,
@@ -204,18 +312,18 @@ impl<K: Ord, V> Node<K, V> {
 
                     unsafe {
                         ptr::copy_nonoverlapping(
-                            self.keys.as_mut_ptr().add(B_MID_INDEX),
+                            self.keys.as_mut_ptr().add(Self::MID),
                             node.keys.as_mut_ptr(),
-                            B_MAX_NODES - B_MID_INDEX,
+                            B - Self::MID,
                         );
                         ptr::copy_nonoverlapping(
-                            self.values.as_mut_ptr().add(B_MID_INDEX),
+                            self.values.as_mut_ptr().add(Self::MID),
                             node.values.as_mut_ptr(),
-                            B_MAX_NODES - B_MID_INDEX,
+                            B - Self::MID,
                         );
                     }
 
-                    self.size = B_MID_INDEX;
+                    self.size = Self::MID;
 
                     InsertResult::Splitted {
                         parent: mid,
@@ -226,25 +334,25 @@ impl<K: Ord, V> Node<K, V> {
                     // on [(0, _), (1, _)], insert (2, _) with edge_index = 2
                     unsafe {
                         let mid = (
-                            slice_remove(self.mut_keys(), B_MID_INDEX),
-                            slice_remove(self.mut_values(), B_MID_INDEX),
+                            slice_remove(self.mut_keys(), Self::MID),
+                            slice_remove(self.mut_values(), Self::MID),
                         );
 
                         slice_insert(self.mut_keys(), edge_index - 1, key);
                         slice_insert(self.mut_values(), edge_index - 1, value);
 
                         ptr::copy_nonoverlapping(
-                            self.keys.as_mut_ptr().add(B_MID_INDEX),
+                            self.keys.as_mut_ptr().add(Self::MID),
                             node.keys.as_mut_ptr(),
-                            B_MAX_NODES - B_MID_INDEX,
+                            B - Self::MID,
                         );
                         ptr::copy_nonoverlapping(
-                            self.values.as_mut_ptr().add(B_MID_INDEX),
+                            self.values.as_mut_ptr().add(Self::MID),
                             node.values.as_mut_ptr(),
-                            B_MAX_NODES - B_MID_INDEX,
+                            B - Self::MID,
                         );
 
-                        self.size = B_MID_INDEX;
+                        self.size = Self::MID;
 
                         InsertResult::Splitted {
                             parent: mid,
@@ -261,9 +369,9 @@ impl<K: Ord, V> Node<K, V> {
         edge_index: usize,
         key: K,
         value: V,
-        edge: Box<Node<K, V>>,
-    ) -> InsertResult<K, V> {
-        if self.size < B_MAX_NODES {
+        edge: Box<Node<K, V, B>>,
+    ) -> InsertResult<K, V, B> {
+        if self.size < B {
             self.size += 1;
 
             unsafe {
@@ -282,42 +390,42 @@ impl<K: Ord, V> Node<K, V> {
             // and return InsertResult::Splitted { parent: (3, 3), right: Node { data: [(5, 5)], edges: [Node_4, Node_6]} }
 
             let mut node = Box::new(Node::new());
-            node.size = B_MAX_NODES - B_MID_INDEX;
+            node.size = B - Self::MID;
             node.depth = self.depth;
 
-            match edge_index.cmp(&B_MID_INDEX) {
+            match edge_index.cmp(&Self::MID) {
                 Ordering::Less => {
                     // on Node { data: [(3, _), (5, _)], edges: [Node_0, Node_4, Node_6] }, insert (1, _) and Node_2 with edge_index = 0
 
                     unsafe {
                         // TODO: can be optimized by partial copy between remove and insert
                         let mid = (
-                            slice_remove(self.mut_keys(), B_MID_INDEX - 1),
-                            slice_remove(self.mut_values(), B_MID_INDEX - 1),
+                            slice_remove(self.mut_keys(), Self::MID - 1),
+                            slice_remove(self.mut_values(), Self::MID - 1),
                         );
 
                         slice_insert(self.mut_keys(), edge_index, key);
                         slice_insert(self.mut_values(), edge_index, value);
 
                         ptr::copy_nonoverlapping(
-                            self.keys.as_mut_ptr().add(B_MID_INDEX),
+                            self.keys.as_mut_ptr().add(Self::MID),
                             node.keys.as_mut_ptr(),
-                            B_MAX_NODES - B_MID_INDEX,
+                            B - Self::MID,
                         );
                         ptr::copy_nonoverlapping(
-                            self.values.as_mut_ptr().add(B_MID_INDEX),
+                            self.values.as_mut_ptr().add(Self::MID),
                             node.values.as_mut_ptr(),
-                            B_MAX_NODES - B_MID_INDEX,
+                            B - Self::MID,
                         );
 
                         ptr::copy_nonoverlapping(
-                            self.edges.as_mut_ptr().add(B_MID_INDEX),
+                            self.edges.as_mut_ptr().add(Self::MID),
                             node.edges.as_mut_ptr(),
-                            (B_MAX_NODES + 1) - B_MID_INDEX,
+                            (B + 1) - Self::MID,
                         );
                         slice_insert(self.mut_edges(), edge_index + 1, edge);
 
-                        self.size = B_MID_INDEX;
+                        self.size = Self::MID;
 
                         InsertResult::Splitted {
                             parent: mid,
@@ -331,24 +439,24 @@ impl<K: Ord, V> Node<K, V> {
                         let mid = (key, value);
 
                         ptr::copy_nonoverlapping(
-                            self.keys.as_mut_ptr().add(B_MID_INDEX),
+                            self.keys.as_mut_ptr().add(Self::MID),
                             node.keys.as_mut_ptr(),
-                            B_MAX_NODES - B_MID_INDEX,
+                            B - Self::MID,
                         );
                         ptr::copy_nonoverlapping(
-                            self.values.as_mut_ptr().add(B_MID_INDEX),
+                            self.values.as_mut_ptr().add(Self::MID),
                             node.values.as_mut_ptr(),
-                            B_MAX_NODES - B_MID_INDEX,
+                            B - Self::MID,
                         );
 
                         ptr::write(node.edges.as_mut_ptr(), edge);
                         ptr::copy_nonoverlapping(
-                            self.edges.as_mut_ptr().add(B_MID_INDEX + 1),
+                            self.edges.as_mut_ptr().add(Self::MID + 1),
                             node.edges.as_mut_ptr().add(1),
-                            (B_MAX_NODES + 1) - (B_MID_INDEX + 1),
+                            (B + 1) - (Self::MID + 1),
                         );
 
-                        self.size = B_MID_INDEX;
+                        self.size = Self::MID;
 
                         InsertResult::Splitted {
                             parent: mid,
@@ -360,32 +468,32 @@ impl<K: Ord, V> Node<K, V> {
                     // on Node { data: [(1, _), (3, _)], edges: [Node_0, Node_2, Node_4] }, insert (5, _) and Node_6 with edge_index = 2
                     unsafe {
                         let mid = (
-                            slice_remove(self.mut_keys(), B_MID_INDEX),
-                            slice_remove(self.mut_values(), B_MID_INDEX),
+                            slice_remove(self.mut_keys(), Self::MID),
+                            slice_remove(self.mut_values(), Self::MID),
                         );
 
                         slice_insert(self.mut_keys(), edge_index - 1, key);
                         slice_insert(self.mut_values(), edge_index - 1, value);
 
                         ptr::copy_nonoverlapping(
-                            self.keys.as_mut_ptr().add(B_MID_INDEX),
+                            self.keys.as_mut_ptr().add(Self::MID),
                             node.keys.as_mut_ptr(),
-                            B_MAX_NODES - B_MID_INDEX,
+                            B - Self::MID,
                         );
                         ptr::copy_nonoverlapping(
-                            self.values.as_mut_ptr().add(B_MID_INDEX),
+                            self.values.as_mut_ptr().add(Self::MID),
                             node.values.as_mut_ptr(),
-                            B_MAX_NODES - B_MID_INDEX,
+                            B - Self::MID,
                         );
 
                         ptr::copy_nonoverlapping(
-                            self.edges.as_mut_ptr().add(B_MID_INDEX + 1),
+                            self.edges.as_mut_ptr().add(Self::MID + 1),
                             node.edges.as_mut_ptr(),
-                            (B_MAX_NODES + 1) - (B_MID_INDEX + 1),
+                            (B + 1) - (Self::MID + 1),
                         );
-                        slice_insert(node.mut_edges(), edge_index - B_MID_INDEX, edge);
+                        slice_insert(node.mut_edges(), edge_index - Self::MID, edge);
 
-                        self.size = B_MID_INDEX;
+                        self.size = Self::MID;
 
                         InsertResult::Splitted {
                             parent: mid,
@@ -398,7 +506,7 @@ impl<K: Ord, V> Node<K, V> {
     }
 
     /// find the leftmost node from the tree whose root is self
-    fn find_begin(&mut self) -> (Vec<(NonNull<Node<K, V>>, usize)>, &mut Node<K, V>) {
+    fn find_begin(&mut self) -> (Vec<(NonNull<Node<K, V, B>>, usize)>, &mut Node<K, V, B>) {
         let mut parents = Vec::new();
         let mut target = NonNull::from(self);
 
@@ -415,7 +523,7 @@ impl<K: Ord, V> Node<K, V> {
     }
 
     /// find the rightmost node from the tree whose root is self
-    fn find_end(&mut self) -> (Vec<(NonNull<Node<K, V>>, usize)>, &mut Node<K, V>) {
+    fn find_end(&mut self) -> (Vec<(NonNull<Node<K, V, B>>, usize)>, &mut Node<K, V, B>) {
         let mut parents = Vec::new();
         let mut target = NonNull::from(self);
 
@@ -448,13 +556,13 @@ enum DescentSearchResult {
 }
 
 #[derive(Debug)]
-struct Cursor<K, V> {
-    ancestors: Vec<(NonNull<Node<K, V>>, usize)>, // (parent, index from parent.edges[index])
-    current: NonNull<Node<K, V>>,
+struct Cursor<K, V, const B: usize> {
+    ancestors: Vec<(NonNull<Node<K, V, B>>, usize)>, // (parent, index from parent.edges[index])
+    current: NonNull<Node<K, V, B>>,
 }
 
-impl<K: Ord, V> Cursor<K, V> {
-    fn new(root: NonNull<Node<K, V>>) -> Self {
+impl<K: Ord, V, const B: usize> Cursor<K, V, B> {
+    fn new(root: NonNull<Node<K, V, B>>) -> Self {
         Self {
             ancestors: Vec::with_capacity(4),
             current: root,
@@ -464,16 +572,9 @@ impl<K: Ord, V> Cursor<K, V> {
     fn search_in_node(&self, key: &K) -> InnerSearchResult {
         let node = unsafe { self.current.as_ref() };
 
-        for (index, k) in node.keys().iter().enumerate() {
-            match key.cmp(k) {
-                Ordering::Less => return InnerSearchResult::Descent { edge_index: index },
-                Ordering::Equal => return InnerSearchResult::Some { value_index: index },
-                Ordering::Greater => {}
-            }
-        }
-
-        InnerSearchResult::Descent {
-            edge_index: node.size,
+        match node.keys().binary_search(key) {
+            Ok(index) => InnerSearchResult::Some { value_index: index },
+            Err(index) => InnerSearchResult::Descent { edge_index: index },
         }
     }
 
@@ -516,13 +617,17 @@ impl<K: Ord, V> Cursor<K, V> {
     }
 }
 
-pub struct BTree<K, V> {
-    root: NonNull<Node<K, V>>,
+/// B-tree whose node has at most B (key, value)s, and B + 1 edges
+pub struct BTree<K, V, const B: usize = 11> {
+    root: NonNull<Node<K, V, B>>,
     size: usize,
-    cursor: RefCell<Cursor<K, V>>,
+    cursor: RefCell<Cursor<K, V, B>>,
 }
 
-impl<K: Debug, V: Debug> Debug for BTree<K, V> {
+// The cursor points only to the nodes of the tree, so it is not Sync because of the RefCell, but Send.
+unsafe impl<K: Send, V: Send, const B: usize> Send for BTree<K, V, B> {}
+
+impl<K: Debug, V: Debug, const B: usize> Debug for BTree<K, V, B> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         unsafe {
             f.debug_struct("BTree")
@@ -533,14 +638,14 @@ impl<K: Debug, V: Debug> Debug for BTree<K, V> {
     }
 }
 
-impl<K, V> Drop for BTree<K, V> {
+impl<K, V, const B: usize> Drop for BTree<K, V, B> {
     fn drop(&mut self) {
         let root = unsafe { ptr::read(self.root.as_ptr()) };
         root.forget();
     }
 }
 
-impl<K: Ord, V> BTree<K, V> {
+impl<K: Ord, V, const B: usize> BTree<K, V, B> {
     fn clear(&self) {
         let mut cursor = self.cursor.borrow_mut();
         cursor.ancestors.clear();
@@ -648,7 +753,8 @@ impl<K: Ord, V> BTree<K, V> {
             // try replace with predecessor or successor
             // if the leaf node has at least two pairs of (key, value), just return after replacing since it does not need to rebalance
             let predecessor_edge = unsafe {
-                &mut **(current.edges.get_unchecked_mut(value_index) as *mut Box<Node<K, V>>)
+                &mut **(current.edges.as_mut_slice().get_unchecked_mut(value_index)
+                    as *mut Box<Node<K, V, B>>)
             };
             let (_, predecessor) = predecessor_edge.find_end();
 
@@ -668,8 +774,11 @@ impl<K: Ord, V> BTree<K, V> {
                 };
             } else {
                 let successor_edge = unsafe {
-                    &mut **(current.edges.get_unchecked_mut(value_index + 1)
-                        as *mut Box<Node<K, V>>)
+                    &mut **(current
+                        .edges
+                        .as_mut_slice()
+                        .get_unchecked_mut(value_index + 1)
+                        as *mut Box<Node<K, V, B>>)
                 };
                 let (parents, successor) = successor_edge.find_begin();
 
@@ -704,7 +813,11 @@ impl<K: Ord, V> BTree<K, V> {
             // the only one that uses right-hand rule since this is the rightmost node
             if edge_index == 0 {
                 let right_sibling = unsafe {
-                    &mut **(parent.edges.get_unchecked_mut(edge_index + 1) as *mut Box<Node<K, V>>)
+                    &mut **(parent
+                        .edges
+                        .as_mut_slice()
+                        .get_unchecked_mut(edge_index + 1)
+                        as *mut Box<Node<K, V, B>>)
                 };
 
                 // parent has one (key, value), therefore it is to be empty node.
@@ -809,8 +922,8 @@ impl<K: Ord, V> BTree<K, V> {
                     } else {
                         // println!("CASE 4");
                         let current = unsafe {
-                            &mut **(parent.edges.get_unchecked_mut(edge_index)
-                                as *mut Box<Node<K, V>>)
+                            &mut **(parent.edges.as_mut_slice().get_unchecked_mut(edge_index)
+                                as *mut Box<Node<K, V, B>>)
                         };
                         current.size += 1;
                         unsafe {
@@ -854,7 +967,11 @@ impl<K: Ord, V> BTree<K, V> {
                 }
             } else {
                 let left_sibling = unsafe {
-                    &mut **(parent.edges.get_unchecked_mut(edge_index - 1) as *mut Box<Node<K, V>>)
+                    &mut **(parent
+                        .edges
+                        .as_mut_slice()
+                        .get_unchecked_mut(edge_index - 1)
+                        as *mut Box<Node<K, V, B>>)
                 };
 
                 if parent.size == 1 {
@@ -995,7 +1112,7 @@ impl<K: Ord, V> BTree<K, V> {
 
         // root is now empty. Swap with unique edge
         if root.size == 0 {
-            let old_root: Box<Node<K, V>> = unsafe { Box::from_raw(root as *mut _) };
+            let old_root: Box<Node<K, V, B>> = unsafe { Box::from_raw(root as *mut _) };
             self.root = unsafe { Box::leak(ptr::read(old_root.edges.as_ptr().add(0))).into() };
             mem::forget(old_root);
         }
@@ -1003,18 +1120,44 @@ impl<K: Ord, V> BTree<K, V> {
         value
     }
 
+    /// build the tree from the iterator sorted by the key in O(n)
+    ///
+    /// The keys should be strictly increasing, that is, sorted and not duplicated.
+    pub fn from_sorted_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let items: Vec<(K, V)> = iter.into_iter().collect();
+        debug_assert!(
+            items.windows(2).all(|w| w[0].0 < w[1].0),
+            "The keys should be strictly increasing."
+        );
+
+        let size = items.len();
+        let mut depth = 0;
+
+        while Node::<K, V, B>::max_size(depth) < size {
+            depth += 1;
+        }
+
+        let root = Box::leak(Node::build(size, depth, &mut items.into_iter())).into();
+
+        Self {
+            root,
+            size,
+            cursor: RefCell::new(Cursor::new(root)),
+        }
+    }
+
     pub fn assert(&self) {
         let root = unsafe { self.root.as_ref() };
 
-        fn count_nodes<K: Ord, V>(
-            node: &Node<K, V>,
+        fn count_nodes<K: Ord, V, const B: usize>(
+            node: &Node<K, V, B>,
             depth: usize,
             root_depth: usize,
             from: Option<&K>,
             to: Option<&K>,
         ) -> usize {
             if node.depth != root_depth {
-                assert!(node.size > 0 && node.size <= B_MAX_NODES);
+                assert!(node.size > 0 && node.size <= B);
             }
 
             assert_eq!(node.depth, depth);
@@ -1062,7 +1205,7 @@ impl<K: Ord, V> BTree<K, V> {
     }
 }
 
-impl<K: Ord + Clone, V> SequentialMap<K, V> for BTree<K, V> {
+impl<K: Ord + Clone, V, const B: usize> SequentialMap<K, V> for BTree<K, V, B> {
     fn new() -> Self {
         let root = Box::leak(Box::new(Node::new())).into();
 
@@ -1100,6 +1243,18 @@ impl<K: Ord + Clone, V> SequentialMap<K, V> for BTree<K, V> {
         result
     }
 
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V> {
+        let result = match self.find(key) {
+            SearchResult::Some { value_index } => unsafe {
+                Some(&mut self.cursor.borrow_mut().current.as_mut().values[value_index])
+            },
+            SearchResult::None { .. } => None,
+        };
+
+        self.clear();
+        result
+    }
+
     fn remove(&mut self, key: &K) -> Result<V, ()> {
         let result = match self.find_mut(key) {
             SearchResult::Some { value_index } => {
@@ -1114,3 +1269,28 @@ impl<K: Ord + Clone, V> SequentialMap<K, V> for BTree<K, V> {
         result
     }
 }
+
+impl<K, V, const B: usize> Extend<(K, V)> for BTree<K, V, B>
+where
+    K: Ord + Clone,
+{
+    /// Insert all (key, value) pairs. If the key already exists, the value is overwritten.
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            if let Err(value) = self.insert(&key, value) {
+                *self.lookup_mut(&key).unwrap() = value;
+            }
+        }
+    }
+}
+
+impl<K, V, const B: usize> FromIterator<(K, V)> for BTree<K, V, B>
+where
+    K: Ord + Clone,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
@@ -0,0 +1,238 @@
+/*
+ Refer to
+ Nimrod Megiddo and Dharmendra S. Modha, "ARC: A Self-Tuning, Low Overhead Replacement Cache", FAST 2003
+*/
+
+use std::hash::Hash;
+
+use super::list::List;
+use crate::{hashmap::ChainedHashMap, map::SequentialMap};
+
+/// adaptive replacement cache, which balances the recency and the frequency by the history of the evictions
+///
+/// T1 has the entries used once recently, and T2 has the entries used at least twice. B1 and B2 have the keys
+/// evicted from T1 and T2. A hit on B1 means T1 was too small, so the target size of T1 grows, and vice versa.
+/// A scan of the new keys passes through T1 without evicting the frequent entries in T2.
+pub struct ARCCache<K, V> {
+    capacity: usize,
+    target: usize, // the target size of T1
+    t1: List<(K, V)>,
+    t2: List<(K, V)>,
+    b1: List<K>,
+    b2: List<K>,
+    entries: ChainedHashMap<K, Entry>,
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    T1,
+    T2,
+    B1,
+    B2,
+}
+
+/// the list of the key and the index of its node on the list
+#[derive(Clone, Copy)]
+struct Entry {
+    kind: Kind,
+    index: usize,
+}
+
+impl<K: Hash + Eq + Clone, V> ARCCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "the capacity should be positive");
+
+        Self {
+            capacity,
+            target: 0,
+            t1: List::new(),
+            t2: List::new(),
+            b1: List::new(),
+            b2: List::new(),
+            entries: ChainedHashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// get the number of the cached values
+    pub fn len(&self) -> usize {
+        self.t1.len() + self.t2.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// get the number of the hits by `get`
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// get the number of the misses by `get`
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// check that the value of the key is cached, without updating the cache and the statistics
+    pub fn contains(&self, key: &K) -> bool {
+        matches!(self.entries.lookup(key), Some(entry) if entry.kind == Kind::T1 || entry.kind == Kind::T2)
+    }
+
+    /// get the value of the key, promoting it to the frequent entries
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let entry = match self.entries.lookup(key) {
+            Some(entry) if entry.kind == Kind::T1 || entry.kind == Kind::T2 => *entry,
+            _ => {
+                self.misses += 1;
+                return None;
+            }
+        };
+
+        self.hits += 1;
+        let index = self.promote(key, entry);
+
+        Some(&self.t2.get(index).1)
+    }
+
+    /// insert the value of the key, then return the old value if it was cached
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let entry = match self.entries.lookup(&key) {
+            Some(entry) => *entry,
+            None => {
+                self.insert_new(key, value);
+                return None;
+            }
+        };
+
+        match entry.kind {
+            Kind::T1 | Kind::T2 => {
+                let index = self.promote(&key, entry);
+                return Some(std::mem::replace(&mut self.t2.get_mut(index).1, value));
+            }
+            Kind::B1 => {
+                // T1 was too small
+                let delta = (self.b2.len() / self.b1.len()).max(1);
+                self.target = (self.target + delta).min(self.capacity);
+                self.b1.remove(entry.index);
+            }
+            Kind::B2 => {
+                // T2 was too small
+                let delta = (self.b1.len() / self.b2.len()).max(1);
+                self.target = self.target.saturating_sub(delta);
+                self.b2.remove(entry.index);
+            }
+        }
+
+        if self.len() >= self.capacity {
+            self.replace(entry.kind == Kind::B2);
+        }
+
+        let index = self.t2.push_front((key.clone(), value));
+        *self.entries.lookup_mut(&key).unwrap() = Entry {
+            kind: Kind::T2,
+            index,
+        };
+
+        None
+    }
+
+    /// remove the value of the key and its history
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let entry = self.entries.remove(key).ok()?;
+
+        match entry.kind {
+            Kind::T1 => Some(self.t1.remove(entry.index).1),
+            Kind::T2 => Some(self.t2.remove(entry.index).1),
+            Kind::B1 => {
+                self.b1.remove(entry.index);
+                None
+            }
+            Kind::B2 => {
+                self.b2.remove(entry.index);
+                None
+            }
+        }
+    }
+
+    /// move the cached entry to the front of T2, then return its index
+    fn promote(&mut self, key: &K, entry: Entry) -> usize {
+        if entry.kind == Kind::T2 {
+            self.t2.move_to_front(entry.index);
+            return entry.index;
+        }
+
+        let item = self.t1.remove(entry.index);
+        let index = self.t2.push_front(item);
+        *self.entries.lookup_mut(key).unwrap() = Entry {
+            kind: Kind::T2,
+            index,
+        };
+
+        index
+    }
+
+    /// insert the key which is not in the cache and the history
+    fn insert_new(&mut self, key: K, value: V) {
+        if self.t1.len() + self.b1.len() >= self.capacity {
+            if self.t1.len() < self.capacity {
+                let ghost = self.b1.pop_back().unwrap();
+                self.entries.remove(&ghost).unwrap();
+
+                if self.len() >= self.capacity {
+                    self.replace(false);
+                }
+            } else {
+                let (evicted, _) = self.t1.pop_back().unwrap();
+                self.entries.remove(&evicted).unwrap();
+            }
+        } else if self.len() + self.b1.len() + self.b2.len() >= self.capacity {
+            if self.len() + self.b1.len() + self.b2.len() >= 2 * self.capacity {
+                if let Some(ghost) = self.b2.pop_back() {
+                    self.entries.remove(&ghost).unwrap();
+                }
+            }
+
+            if self.len() >= self.capacity {
+                self.replace(false);
+            }
+        }
+
+        let index = self.t1.push_front((key.clone(), value));
+        let _ = self.entries.insert(
+            &key,
+            Entry {
+                kind: Kind::T1,
+                index,
+            },
+        );
+    }
+
+    /// evict the least recently used entry of T1 or T2 into its history, by the target size of T1
+    fn replace(&mut self, hit_b2: bool) {
+        let from_t1 = !self.t1.is_empty()
+            && (self.t1.len() > self.target || (hit_b2 && self.t1.len() == self.target));
+
+        if from_t1 || self.t2.is_empty() {
+            let (key, _) = self.t1.pop_back().unwrap();
+            let index = self.b1.push_front(key.clone());
+            *self.entries.lookup_mut(&key).unwrap() = Entry {
+                kind: Kind::B1,
+                index,
+            };
+        } else {
+            let (key, _) = self.t2.pop_back().unwrap();
+            let index = self.b2.push_front(key.clone());
+            *self.entries.lookup_mut(&key).unwrap() = Entry {
+                kind: Kind::B2,
+                index,
+            };
+        }
+    }
+}
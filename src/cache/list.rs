@@ -0,0 +1,98 @@
+use crate::slab::Slab;
+
+const NIL: usize = usize::MAX;
+
+/// doubly linked list on the array, whose node is pointed by its index while it is in the list
+///
+/// The front is the most recently used one. The indices of the removed nodes are reused.
+pub struct List<T> {
+    nodes: Slab<Node<T>>,
+    head: usize,
+    tail: usize,
+}
+
+struct Node<T> {
+    item: T,
+    prev: usize,
+    next: usize,
+}
+
+impl<T> List<T> {
+    pub fn new() -> Self {
+        Self {
+            nodes: Slab::new(),
+            head: NIL,
+            tail: NIL,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> &T {
+        &self.nodes[index].item
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> &mut T {
+        &mut self.nodes[index].item
+    }
+
+    /// push the item at the front, then return the index of its node
+    pub fn push_front(&mut self, item: T) -> usize {
+        let index = self.nodes.insert(Node {
+            item,
+            prev: NIL,
+            next: self.head,
+        });
+
+        match self.head {
+            NIL => self.tail = index,
+            head => self.nodes[head].prev = index,
+        }
+
+        self.head = index;
+        index
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        let Node { item, prev, next } = self.nodes.remove(index).unwrap();
+
+        match prev {
+            NIL => self.head = next,
+            prev => self.nodes[prev].next = next,
+        }
+
+        match next {
+            NIL => self.tail = prev,
+            next => self.nodes[next].prev = prev,
+        }
+
+        item
+    }
+
+    /// move the node to the front, keeping its index since the slab reuses the last removed key first
+    pub fn move_to_front(&mut self, index: usize) {
+        if self.head == index {
+            return;
+        }
+
+        let item = self.remove(index);
+        let moved = self.push_front(item);
+        debug_assert_eq!(moved, index);
+    }
+
+    /// get the index of the least recently used node
+    pub fn back(&self) -> Option<usize> {
+        (self.tail != NIL).then_some(self.tail)
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let tail = self.back()?;
+        Some(self.remove(tail))
+    }
+}
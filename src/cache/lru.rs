@@ -0,0 +1,110 @@
+use std::hash::Hash;
+
+use super::list::List;
+use crate::{hashmap::ChainedHashMap, map::SequentialMap};
+
+/// cache which evicts the least recently used entries when the sum of the weights exceeds the capacity
+///
+/// An entry is weighted 1 by `insert`. The entry heavier than the capacity is evicted right after inserted.
+pub struct LruCache<K, V> {
+    capacity: usize,
+    size: usize, // the sum of the weights
+    list: List<(K, V, usize)>,
+    entries: ChainedHashMap<K, usize>, // the index of the node of the key
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            size: 0,
+            list: List::new(),
+            entries: ChainedHashMap::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// get the sum of the weights of the entries
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.entries.lookup(key).is_some()
+    }
+
+    /// get the value of the key, making it the most recently used
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let index = *self.entries.lookup(key)?;
+        self.list.move_to_front(index);
+
+        Some(&self.list.get(index).1)
+    }
+
+    /// get the value of the key without changing the order of the usage
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let index = *self.entries.lookup(key)?;
+        Some(&self.list.get(index).1)
+    }
+
+    /// insert the value of the key weighted 1, then return the old value
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.insert_with_weight(key, value, 1)
+    }
+
+    /// insert the value of the key with the weight, then return the old value
+    pub fn insert_with_weight(&mut self, key: K, value: V, weight: usize) -> Option<V> {
+        let old = match self.entries.lookup(&key) {
+            Some(&index) => {
+                let (_, old, old_weight) = self.list.remove(index);
+                self.size -= old_weight;
+                Some(old)
+            }
+            None => None,
+        };
+
+        let index = self.list.push_front((key.clone(), value, weight));
+        self.size += weight;
+
+        match self.entries.lookup_mut(&key) {
+            Some(entry) => *entry = index,
+            None => {
+                let _ = self.entries.insert(&key, index);
+            }
+        }
+
+        while self.size > self.capacity {
+            self.pop_lru();
+        }
+
+        old
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self.entries.remove(key).ok()?;
+        let (_, value, weight) = self.list.remove(index);
+        self.size -= weight;
+
+        Some(value)
+    }
+
+    /// remove the least recently used entry
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        let (key, value, weight) = self.list.pop_back()?;
+        self.entries.remove(&key).unwrap();
+        self.size -= weight;
+
+        Some((key, value))
+    }
+}
@@ -0,0 +1,8 @@
+mod arc;
+mod list;
+mod lru;
+mod sharded;
+
+pub use arc::ARCCache;
+pub use lru::LruCache;
+pub use sharded::ShardedLruCache;
@@ -0,0 +1,161 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hash, Hasher},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use crossbeam_utils::CachePadded;
+use parking_lot::Mutex;
+
+use super::LruCache;
+
+/// the number of the shards per thread of the machine on `new`
+const SHARDS_PER_THREAD: usize = 4;
+
+/// concurrent LRU cache whose keys are partitioned into the shards of LruCache protected by Mutex
+///
+/// The shards share the atomic size, the sum of the weights. The thread which makes the size exceed the capacity
+/// evicts the least recently used entries of its shard first, then of the next shards. So the order of the eviction
+/// is LRU in each shard, and the size exceeds the capacity only while the inserting threads are evicting.
+pub struct ShardedLruCache<K, V, S = RandomState> {
+    shards: Box<[Shard<K, V>]>,
+    size: CachePadded<AtomicUsize>,
+    capacity: usize,
+    hasher: S,
+}
+
+type Shard<K, V> = CachePadded<Mutex<LruCache<K, V>>>;
+
+impl<K: Hash + Eq + Clone, V> ShardedLruCache<K, V> {
+    /// make the cache with the shards as many as 4 times the threads of the machine
+    pub fn new(capacity: usize) -> Self {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        Self::with_shards(capacity, threads * SHARDS_PER_THREAD)
+    }
+
+    /// make the cache with the number of the shards, which is rounded up to the power of 2
+    pub fn with_shards(capacity: usize, shards: usize) -> Self {
+        Self::with_shards_and_hasher(capacity, shards, RandomState::new())
+    }
+}
+
+impl<K: Hash + Eq + Clone, V, S> ShardedLruCache<K, V, S> {
+    /// make the cache with the number of the shards, which is rounded up to the power of 2, and the hasher choosing the shard
+    pub fn with_shards_and_hasher(capacity: usize, shards: usize, hasher: S) -> Self {
+        assert!(shards > 0, "the number of the shards should be positive");
+
+        Self {
+            // the shards do not evict by themselves
+            shards: (0..shards.next_power_of_two())
+                .map(|_| CachePadded::new(Mutex::new(LruCache::new(usize::MAX))))
+                .collect(),
+            size: CachePadded::new(AtomicUsize::new(0)),
+            capacity,
+            hasher,
+        }
+    }
+
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// get the sum of the weights of the entries
+    pub fn size(&self) -> usize {
+        self.size.load(Ordering::Acquire)
+    }
+
+    /// get the number of the entries, locking the shards one by one
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.lock().len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K: Hash + Eq + Clone, V, S: BuildHasher> ShardedLruCache<K, V, S> {
+    fn shard_index(&self, key: &K) -> usize {
+        let mut hasher = self.hasher.build_hasher();
+        key.hash(&mut hasher);
+
+        hasher.finish() as usize & (self.shards.len() - 1)
+    }
+
+    /// run the function on the shard, then apply the change of its size to the shared size
+    fn with_shard<R>(&self, index: usize, f: impl FnOnce(&mut LruCache<K, V>) -> R) -> R {
+        let mut shard = self.shards[index].lock();
+        let before = shard.size();
+        let result = f(&mut shard);
+        let after = shard.size();
+
+        // while locking, so the removed weight is always added before
+        if after > before {
+            self.size.fetch_add(after - before, Ordering::AcqRel);
+        } else {
+            self.size.fetch_sub(before - after, Ordering::AcqRel);
+        }
+
+        result
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.shards[self.shard_index(key)].lock().contains(key)
+    }
+
+    /// apply the function to the value of the key, making it the most recently used in its shard
+    pub fn lookup<F, R>(&self, key: &K, f: F) -> R
+    where
+        F: FnOnce(Option<&V>) -> R,
+    {
+        f(self.shards[self.shard_index(key)].lock().get(key))
+    }
+
+    /// get the clone of the value of the key, making it the most recently used in its shard
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.lookup(key, |value| value.cloned())
+    }
+
+    /// insert the value of the key weighted 1, then return the old value
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.insert_with_weight(key, value, 1)
+    }
+
+    /// insert the value of the key with the weight, then return the old value
+    pub fn insert_with_weight(&self, key: K, value: V, weight: usize) -> Option<V> {
+        let index = self.shard_index(&key);
+        let old = self.with_shard(index, |shard| shard.insert_with_weight(key, value, weight));
+
+        self.evict(index);
+        old
+    }
+
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.with_shard(self.shard_index(key), |shard| shard.remove(key))
+    }
+
+    /// evict the entries from the shard and the next ones until the size is in the capacity
+    fn evict(&self, start: usize) {
+        for offset in 0..self.shards.len() {
+            let index = (start + offset) & (self.shards.len() - 1);
+
+            while self.size() > self.capacity {
+                if self.with_shard(index, |shard| shard.pop_lru()).is_none() {
+                    break;
+                }
+            }
+
+            if self.size() <= self.capacity {
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+mod rollback;
+
+pub use rollback::RollbackUnionFind;
+
+/// disjoint sets of 0..n with union by rank and path compression
+///
+/// Each operation takes amortized O(α(n)), the inverse Ackermann function.
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    count: usize,
+}
+
+impl UnionFind {
+    /// make n singleton sets
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            count: n,
+        }
+    }
+
+    /// get the number of the elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// get the number of the sets
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// add a new singleton set, then return its element
+    pub fn push(&mut self) -> usize {
+        let element = self.parent.len();
+
+        self.parent.push(element);
+        self.rank.push(0);
+        self.count += 1;
+
+        element
+    }
+
+    /// get the representative of the set of the element, pointing the path to it directly
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut current = element;
+
+        while current != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    /// merge the sets of a and b, or return false if they are already in the same set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+
+        if a == b {
+            return false;
+        }
+
+        // attach the lower tree under the higher one
+        if self.rank[a] < self.rank[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        self.parent[b] = a;
+
+        if self.rank[a] == self.rank[b] {
+            self.rank[a] += 1;
+        }
+
+        self.count -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+}
@@ -0,0 +1,113 @@
+/// disjoint sets of 0..n with union by size and undo of the unions in the reverse order
+///
+/// It does not compress the paths, so that a union changes only one parent and can be undone in O(1).
+/// find takes O(log n). This is for offline algorithms like dynamic connectivity on a segment tree of time.
+pub struct RollbackUnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+    history: Vec<Option<usize>>, // the attached root of each union, or None if it did not merge
+}
+
+impl RollbackUnionFind {
+    /// make n singleton sets
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            count: n,
+            history: Vec::new(),
+        }
+    }
+
+    /// get the number of the elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// get the number of the sets
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// get the representative of the set of the element
+    pub fn find(&self, mut element: usize) -> usize {
+        while self.parent[element] != element {
+            element = self.parent[element];
+        }
+
+        element
+    }
+
+    /// get the number of the elements in the set of the element
+    pub fn size(&self, element: usize) -> usize {
+        self.size[self.find(element)]
+    }
+
+    /// merge the sets of a and b, or return false if they are already in the same set
+    ///
+    /// Both cases are recorded, so each call is undone by one `undo`.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+
+        if a == b {
+            self.history.push(None);
+            return false;
+        }
+
+        // attach the smaller tree under the larger one
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.count -= 1;
+        self.history.push(Some(b));
+
+        true
+    }
+
+    pub fn connected(&self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// get the number of the recorded unions, to roll back to this state later
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// undo the last union, or return false if there is no union to undo
+    pub fn undo(&mut self) -> bool {
+        let attached = match self.history.pop() {
+            Some(attached) => attached,
+            None => return false,
+        };
+
+        if let Some(child) = attached {
+            let root = self.parent[child];
+
+            self.parent[child] = child;
+            self.size[root] -= self.size[child];
+            self.count += 1;
+        }
+
+        true
+    }
+
+    /// undo the unions until the number of the recorded unions becomes the snapshot
+    pub fn rollback(&mut self, snapshot: usize) {
+        assert!(
+            snapshot <= self.history.len(),
+            "the snapshot should not be after the current state"
+        );
+
+        while self.history.len() > snapshot {
+            self.undo();
+        }
+    }
+}
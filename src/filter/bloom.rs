@@ -0,0 +1,155 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
+
+use super::{indices, optimal_bits, optimal_hashes};
+
+/// probabilistic set which can answer that the item may be inserted, or is surely not inserted
+///
+/// The filters to be combined or serialized should have the same size and the same hasher. For serialization,
+/// use the deterministic hasher like `BuildHasherDefault<DefaultHasher>`, since `RandomState` differs on each run.
+pub struct BloomFilter<T: ?Sized, S = RandomState> {
+    bits: Vec<u64>,
+    num_bits: usize,
+    hashes: u32,
+    hasher: S,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<T: ?Sized + Hash> BloomFilter<T> {
+    /// make the filter for the false positive rate with the expected number of the items
+    pub fn new(items: usize, false_positive_rate: f64) -> Self {
+        Self::with_hasher(items, false_positive_rate, RandomState::new())
+    }
+}
+
+impl<T: ?Sized + Hash, S: BuildHasher> BloomFilter<T, S> {
+    pub fn with_hasher(items: usize, false_positive_rate: f64, hasher: S) -> Self {
+        let num_bits = optimal_bits(items, false_positive_rate);
+        Self::with_size_and_hasher(num_bits, optimal_hashes(num_bits, items), hasher)
+    }
+
+    /// make the filter with the number of the bits and the hashes
+    pub fn with_size_and_hasher(num_bits: usize, hashes: u32, hasher: S) -> Self {
+        assert!(num_bits > 0, "the number of the bits should be positive");
+        assert!(hashes > 0, "the number of the hashes should be positive");
+
+        Self {
+            bits: vec![0; (num_bits + 63) / 64],
+            num_bits,
+            hashes,
+            hasher,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn num_bits(&self) -> usize {
+        self.num_bits
+    }
+
+    pub fn num_hashes(&self) -> u32 {
+        self.hashes
+    }
+
+    /// check that no item is inserted
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|word| *word == 0)
+    }
+
+    /// insert the item, then return false if it may be inserted already
+    pub fn insert(&mut self, item: &T) -> bool {
+        let mut inserted = false;
+
+        for index in indices(&self.hasher, item, self.hashes, self.num_bits) {
+            let (word, mask) = (index / 64, 1 << (index % 64));
+            inserted |= self.bits[word] & mask == 0;
+            self.bits[word] |= mask;
+        }
+
+        inserted
+    }
+
+    /// check that the item may be inserted, with false positives but without false negatives
+    pub fn contains(&self, item: &T) -> bool {
+        indices(&self.hasher, item, self.hashes, self.num_bits)
+            .all(|index| self.bits[index / 64] & (1 << (index % 64)) != 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.iter_mut().for_each(|word| *word = 0);
+    }
+
+    /// get the false positive rate estimated from the ratio of the set bits
+    pub fn false_positive_rate(&self) -> f64 {
+        let ones: u32 = self.bits.iter().map(|word| word.count_ones()).sum();
+        (ones as f64 / self.num_bits as f64).powi(self.hashes as i32)
+    }
+
+    /// make this the filter of the items in either, or return Err(()) if the sizes differ
+    pub fn union(&mut self, other: &Self) -> Result<(), ()> {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// make this the filter of the items in both, or return Err(()) if the sizes differ
+    ///
+    /// The result may have more false positives than the filter built from the intersection directly.
+    pub fn intersect(&mut self, other: &Self) -> Result<(), ()> {
+        self.combine(other, |a, b| a & b)
+    }
+
+    fn combine(&mut self, other: &Self, op: impl Fn(u64, u64) -> u64) -> Result<(), ()> {
+        if self.num_bits != other.num_bits || self.hashes != other.hashes {
+            return Err(());
+        }
+
+        for (word, other) in self.bits.iter_mut().zip(&other.bits) {
+            *word = op(*word, *other);
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: ?Sized, S: Clone> Clone for BloomFilter<T, S> {
+    fn clone(&self) -> Self {
+        Self {
+            bits: self.bits.clone(),
+            num_bits: self.num_bits,
+            hashes: self.hashes,
+            hasher: self.hasher.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: ?Sized, S> serde::Serialize for BloomFilter<T, S> {
+    /// serialize as (the number of the bits, the number of the hashes, the words of the bits)
+    fn serialize<Se: serde::Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        (self.num_bits, self.hashes, &self.bits).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: ?Sized, S: Default> serde::Deserialize<'de> for BloomFilter<T, S> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let (num_bits, hashes, bits): (usize, u32, Vec<u64>) =
+            serde::Deserialize::deserialize(deserializer)?;
+
+        if num_bits == 0 || hashes == 0 || bits.len() != (num_bits + 63) / 64 {
+            return Err(D::Error::custom("the size of the bits does not match"));
+        }
+
+        Ok(Self {
+            bits,
+            num_bits,
+            hashes,
+            hasher: S::default(),
+            _marker: PhantomData,
+        })
+    }
+}
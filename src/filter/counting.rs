@@ -0,0 +1,118 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
+
+use super::{indices, optimal_bits, optimal_hashes};
+
+/// Bloom filter whose bit is the counter, so the inserted item can be removed
+///
+/// The counter sticks at u8::MAX once it overflows, so removing never makes a false negative.
+/// Removing the item that was not inserted may make false negatives of the other items.
+pub struct CountingBloomFilter<T: ?Sized, S = RandomState> {
+    counters: Vec<u8>,
+    hashes: u32,
+    hasher: S,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<T: ?Sized + Hash> CountingBloomFilter<T> {
+    /// make the filter for the false positive rate with the expected number of the items
+    pub fn new(items: usize, false_positive_rate: f64) -> Self {
+        Self::with_hasher(items, false_positive_rate, RandomState::new())
+    }
+}
+
+impl<T: ?Sized + Hash, S: BuildHasher> CountingBloomFilter<T, S> {
+    pub fn with_hasher(items: usize, false_positive_rate: f64, hasher: S) -> Self {
+        let counters = optimal_bits(items, false_positive_rate);
+        Self::with_size_and_hasher(counters, optimal_hashes(counters, items), hasher)
+    }
+
+    /// make the filter with the number of the counters and the hashes
+    pub fn with_size_and_hasher(counters: usize, hashes: u32, hasher: S) -> Self {
+        assert!(
+            counters > 0,
+            "the number of the counters should be positive"
+        );
+        assert!(hashes > 0, "the number of the hashes should be positive");
+
+        Self {
+            counters: vec![0; counters],
+            hashes,
+            hasher,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn num_counters(&self) -> usize {
+        self.counters.len()
+    }
+
+    pub fn num_hashes(&self) -> u32 {
+        self.hashes
+    }
+
+    /// check that no item is inserted
+    pub fn is_empty(&self) -> bool {
+        self.counters.iter().all(|counter| *counter == 0)
+    }
+
+    /// get the distinct indices of the counters for the item
+    fn counters_of(&self, item: &T) -> Vec<usize> {
+        let mut indices: Vec<_> =
+            indices(&self.hasher, item, self.hashes, self.counters.len()).collect();
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+    }
+
+    /// insert the item, which can be inserted several times
+    pub fn insert(&mut self, item: &T) {
+        for index in self.counters_of(item) {
+            let counter = &mut self.counters[index];
+            *counter = counter.saturating_add(1);
+        }
+    }
+
+    /// check that the item may be inserted, with false positives but without false negatives
+    pub fn contains(&self, item: &T) -> bool {
+        indices(&self.hasher, item, self.hashes, self.counters.len())
+            .all(|index| self.counters[index] != 0)
+    }
+
+    /// remove the item once, or return Err(()) if it is surely not inserted
+    pub fn remove(&mut self, item: &T) -> Result<(), ()> {
+        let indices = self.counters_of(item);
+
+        if indices.iter().any(|index| self.counters[*index] == 0) {
+            return Err(());
+        }
+
+        for index in indices {
+            let counter = &mut self.counters[index];
+
+            if *counter != u8::MAX {
+                *counter -= 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        self.counters.iter_mut().for_each(|counter| *counter = 0);
+    }
+}
+
+impl<T: ?Sized, S: Clone> Clone for CountingBloomFilter<T, S> {
+    fn clone(&self) -> Self {
+        Self {
+            counters: self.counters.clone(),
+            hashes: self.hashes,
+            hasher: self.hasher.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
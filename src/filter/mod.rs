@@ -0,0 +1,44 @@
+mod bloom;
+mod counting;
+
+pub use bloom::BloomFilter;
+pub use counting::CountingBloomFilter;
+
+use std::{
+    f64::consts::LN_2,
+    hash::{BuildHasher, Hash, Hasher},
+};
+
+/// get the number of the bits for the false positive rate with the expected number of the items
+fn optimal_bits(items: usize, false_positive_rate: f64) -> usize {
+    assert!(
+        false_positive_rate > 0.0 && false_positive_rate < 1.0,
+        "the false positive rate should be in (0, 1)"
+    );
+
+    let bits = -(items.max(1) as f64) * false_positive_rate.ln() / (LN_2 * LN_2);
+    (bits.ceil() as usize).max(1)
+}
+
+/// get the number of the hashes which minimizes the false positive rate
+fn optimal_hashes(bits: usize, items: usize) -> u32 {
+    (bits as f64 / items.max(1) as f64 * LN_2).round().max(1.0) as u32
+}
+
+/// get the indices of the item by double hashing, h1 + i * h2 for i in 0..hashes
+///
+/// It hashes the item only once, then continues the hasher for h2 which is odd.
+fn indices<T: Hash + ?Sized, S: BuildHasher>(
+    hasher: &S,
+    item: &T,
+    hashes: u32,
+    bits: usize,
+) -> impl Iterator<Item = usize> {
+    let mut state = hasher.build_hasher();
+    item.hash(&mut state);
+    let h1 = state.finish();
+    state.write_u8(0xff);
+    let h2 = state.finish() | 1;
+
+    (0..hashes as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % bits as u64) as usize)
+}
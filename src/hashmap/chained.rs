@@ -0,0 +1,200 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hash, Hasher},
+    mem,
+};
+
+use crate::map::SequentialMap;
+
+const INITIAL_CAPACITY: usize = 8;
+const DEFAULT_MAX_LOAD_FACTOR: f64 = 1.0;
+const MIGRATION_STEP: usize = 4; // the number of the old buckets moved on each operation
+
+/// hash map whose bucket has the chain of (key, value)s
+///
+/// When the load factor exceeds the max, it doubles the buckets and moves the old buckets incrementally,
+/// MIGRATION_STEP buckets on each operation that has `&mut self`, so no operation takes O(n) for rehashing.
+/// While rehashing, the old bucket of the key is used if it is not moved yet.
+pub struct ChainedHashMap<K, V, S = RandomState> {
+    buckets: Vec<Vec<(K, V)>>,
+    old: Vec<Vec<(K, V)>>, // the buckets being moved to `buckets`, empty if not rehashing
+    moved: usize,          // the number of the moved buckets of `old`
+    len: usize,
+    max_load_factor: f64,
+    hasher: S,
+}
+
+impl<K, V, S: Default> Default for ChainedHashMap<K, V, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, V, S> ChainedHashMap<K, V, S> {
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            buckets: (0..INITIAL_CAPACITY).map(|_| Vec::new()).collect(),
+            old: Vec::new(),
+            moved: 0,
+            len: 0,
+            max_load_factor: DEFAULT_MAX_LOAD_FACTOR,
+            hasher,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// get the number of the buckets
+    pub fn capacity(&self) -> usize {
+        self.buckets.len()
+    }
+
+    pub fn is_rehashing(&self) -> bool {
+        !self.old.is_empty()
+    }
+
+    pub fn max_load_factor(&self) -> f64 {
+        self.max_load_factor
+    }
+
+    /// set the max of len / capacity, which is checked on the next insertion
+    pub fn set_max_load_factor(&mut self, max_load_factor: f64) {
+        assert!(
+            max_load_factor > 0.0,
+            "the max load factor should be positive"
+        );
+
+        self.max_load_factor = max_load_factor;
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> ChainedHashMap<K, V, S> {
+    fn hash(&self, key: &K) -> u64 {
+        let mut hasher = self.hasher.build_hasher();
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn index(hash: u64, buckets: &[Vec<(K, V)>]) -> usize {
+        hash as usize & (buckets.len() - 1)
+    }
+
+    /// get the bucket of the key, which is the old one if it is not moved yet
+    fn bucket(&self, key: &K) -> &Vec<(K, V)> {
+        let hash = self.hash(key);
+
+        if self.is_rehashing() {
+            let index = Self::index(hash, &self.old);
+
+            if index >= self.moved {
+                return &self.old[index];
+            }
+        }
+
+        &self.buckets[Self::index(hash, &self.buckets)]
+    }
+
+    fn bucket_mut(&mut self, key: &K) -> &mut Vec<(K, V)> {
+        let hash = self.hash(key);
+
+        if self.is_rehashing() {
+            let index = Self::index(hash, &self.old);
+
+            if index >= self.moved {
+                return &mut self.old[index];
+            }
+        }
+
+        let index = Self::index(hash, &self.buckets);
+        &mut self.buckets[index]
+    }
+
+    /// move the old buckets at most the count
+    fn migrate(&mut self, count: usize) {
+        for _ in 0..count {
+            if !self.is_rehashing() {
+                return;
+            }
+
+            for (key, value) in mem::take(&mut self.old[self.moved]) {
+                let index = Self::index(self.hash(&key), &self.buckets);
+                self.buckets[index].push((key, value));
+            }
+
+            self.moved += 1;
+
+            if self.moved == self.old.len() {
+                self.old = Vec::new();
+                self.moved = 0;
+            }
+        }
+    }
+
+    /// double the buckets, finishing the last rehashing first
+    fn grow(&mut self) {
+        self.migrate(self.old.len());
+
+        let buckets = (0..self.buckets.len() * 2).map(|_| Vec::new()).collect();
+        self.old = mem::replace(&mut self.buckets, buckets);
+    }
+}
+
+impl<K, V, S> SequentialMap<K, V> for ChainedHashMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher + Default,
+{
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(&mut self, key: &K, value: V) -> Result<(), V> {
+        self.migrate(MIGRATION_STEP);
+
+        if self.lookup(key).is_some() {
+            return Err(value);
+        }
+
+        if (self.len + 1) as f64 > self.buckets.len() as f64 * self.max_load_factor {
+            self.grow();
+        }
+
+        self.bucket_mut(key).push((key.clone(), value));
+        self.len += 1;
+
+        Ok(())
+    }
+
+    fn lookup(&self, key: &K) -> Option<&V> {
+        self.bucket(key)
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.migrate(MIGRATION_STEP);
+
+        self.bucket_mut(key)
+            .iter_mut()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    fn remove(&mut self, key: &K) -> Result<V, ()> {
+        self.migrate(MIGRATION_STEP);
+
+        let bucket = self.bucket_mut(key);
+        let index = bucket.iter().position(|(k, _)| k == key).ok_or(())?;
+        let (_, value) = bucket.swap_remove(index);
+        self.len -= 1;
+
+        Ok(value)
+    }
+}
@@ -0,0 +1,192 @@
+/*
+ Refer to
+ Fagin, Nievergelt, Pippenger, Strong. Extendible Hashing - A Fast Access Method for Dynamic Files. TODS 1979.
+*/
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hash, Hasher},
+    mem,
+};
+
+use crate::map::SequentialMap;
+
+const DEFAULT_BUCKET_SIZE: usize = 16;
+
+/// hash map whose directory of 2^global_depth entries points to the buckets
+///
+/// The bucket of local depth d is pointed by the 2^(global_depth - d) entries which share the lower d bits.
+/// When the bucket overflows, only the bucket splits into two by its next bit, and the directory doubles
+/// only if the bucket is pointed by one entry, which copies the pointers but never moves the (key, value)s.
+pub struct ExtendibleHashMap<K, V, S = RandomState> {
+    directory: Vec<usize>, // the index of the bucket for each lower global_depth bits of the hash
+    buckets: Vec<Bucket<K, V>>,
+    global_depth: u32,
+    bucket_size: usize,
+    len: usize,
+    hasher: S,
+}
+
+struct Bucket<K, V> {
+    local_depth: u32,
+    entries: Vec<(u64, K, V)>, // (hash, key, value)
+}
+
+impl<K, V, S: Default> Default for ExtendibleHashMap<K, V, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, V, S> ExtendibleHashMap<K, V, S> {
+    pub fn with_hasher(hasher: S) -> Self {
+        Self::with_bucket_size_and_hasher(DEFAULT_BUCKET_SIZE, hasher)
+    }
+
+    /// make the map whose bucket splits when it has more than the bucket size (key, value)s
+    pub fn with_bucket_size_and_hasher(bucket_size: usize, hasher: S) -> Self {
+        assert!(bucket_size > 0, "the bucket size should be positive");
+
+        Self {
+            directory: vec![0],
+            buckets: vec![Bucket {
+                local_depth: 0,
+                entries: Vec::with_capacity(bucket_size),
+            }],
+            global_depth: 0,
+            bucket_size,
+            len: 0,
+            hasher,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn global_depth(&self) -> u32 {
+        self.global_depth
+    }
+
+    /// get the number of the buckets
+    pub fn buckets(&self) -> usize {
+        self.buckets.len()
+    }
+
+    fn bucket_index(&self, hash: u64) -> usize {
+        let mask = (1u64 << self.global_depth) - 1;
+        self.directory[(hash & mask) as usize]
+    }
+
+    /// split the bucket into two by the bit of its local depth, doubling the directory if needed
+    fn split(&mut self, index: usize) {
+        let local_depth = self.buckets[index].local_depth;
+
+        if local_depth == self.global_depth {
+            self.directory.extend_from_within(..);
+            self.global_depth += 1;
+        }
+
+        let bit = 1u64 << local_depth;
+        let (moved, kept) = mem::take(&mut self.buckets[index].entries)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(hash, _, _)| hash & bit != 0);
+
+        let new = self.buckets.len();
+        self.buckets[index].local_depth += 1;
+        self.buckets[index].entries = kept;
+        self.buckets.push(Bucket {
+            local_depth: local_depth + 1,
+            entries: moved,
+        });
+
+        // the entries pointing to the bucket with the bit go to the new bucket
+        for (prefix, pointer) in self.directory.iter_mut().enumerate() {
+            if *pointer == index && prefix as u64 & bit != 0 {
+                *pointer = new;
+            }
+        }
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> ExtendibleHashMap<K, V, S> {
+    fn hash(&self, key: &K) -> u64 {
+        let mut hasher = self.hasher.build_hasher();
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl<K, V, S> SequentialMap<K, V> for ExtendibleHashMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher + Default,
+{
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(&mut self, key: &K, value: V) -> Result<(), V> {
+        if self.lookup(key).is_some() {
+            return Err(value);
+        }
+
+        let hash = self.hash(key);
+        let mut index = self.bucket_index(hash);
+
+        // the directory doubles only while it is smaller than the number of the (key, value)s,
+        // so the bucket of the colliding hashes exceeds the bucket size instead
+        while self.buckets[index].entries.len() >= self.bucket_size
+            && (self.buckets[index].local_depth < self.global_depth
+                || self.directory.len() < self.len)
+        {
+            self.split(index);
+            index = self.bucket_index(hash);
+        }
+
+        self.buckets[index].entries.push((hash, key.clone(), value));
+        self.len += 1;
+
+        Ok(())
+    }
+
+    fn lookup(&self, key: &K) -> Option<&V> {
+        let hash = self.hash(key);
+
+        self.buckets[self.bucket_index(hash)]
+            .entries
+            .iter()
+            .find(|(h, k, _)| *h == hash && k == key)
+            .map(|(_, _, v)| v)
+    }
+
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V> {
+        let hash = self.hash(key);
+        let index = self.bucket_index(hash);
+
+        self.buckets[index]
+            .entries
+            .iter_mut()
+            .find(|(h, k, _)| *h == hash && k == key)
+            .map(|(_, _, v)| v)
+    }
+
+    fn remove(&mut self, key: &K) -> Result<V, ()> {
+        let hash = self.hash(key);
+        let index = self.bucket_index(hash);
+        let entries = &mut self.buckets[index].entries;
+
+        let position = entries
+            .iter()
+            .position(|(h, k, _)| *h == hash && k == key)
+            .ok_or(())?;
+        let (_, _, value) = entries.swap_remove(position);
+        self.len -= 1;
+
+        Ok(value)
+    }
+}
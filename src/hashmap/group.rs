@@ -0,0 +1,152 @@
+//! the group of the control bytes, which is matched at once by SSE2 on x86_64, NEON on aarch64, or one by one on others
+
+pub const GROUP_WIDTH: usize = 16;
+
+/// the control byte of the empty slot
+pub const EMPTY: u8 = 0b1111_1111;
+/// the control byte of the removed slot, which keeps the probe going
+pub const DELETED: u8 = 0b1000_0000;
+
+/// the bits of the matched slots in the group
+#[derive(Clone, Copy)]
+pub struct BitMask(pub u16);
+
+impl BitMask {
+    pub fn any(self) -> bool {
+        self.0 != 0
+    }
+}
+
+impl Iterator for BitMask {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let bit = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+
+        Some(bit)
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+mod imp {
+    use std::arch::x86_64::*;
+
+    use super::{BitMask, EMPTY};
+
+    pub struct Group(__m128i);
+
+    impl Group {
+        /// # Safety
+        /// The pointer should be valid for reading GROUP_WIDTH bytes.
+        pub unsafe fn load(ptr: *const u8) -> Self {
+            Self(_mm_loadu_si128(ptr as *const __m128i))
+        }
+
+        pub fn match_byte(&self, byte: u8) -> BitMask {
+            unsafe {
+                let cmp = _mm_cmpeq_epi8(self.0, _mm_set1_epi8(byte as i8));
+                BitMask(_mm_movemask_epi8(cmp) as u16)
+            }
+        }
+
+        pub fn match_empty(&self) -> BitMask {
+            self.match_byte(EMPTY)
+        }
+
+        /// match the slots whose high bit is set
+        pub fn match_empty_or_deleted(&self) -> BitMask {
+            unsafe { BitMask(_mm_movemask_epi8(self.0) as u16) }
+        }
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod imp {
+    use std::arch::aarch64::*;
+
+    use super::{BitMask, DELETED, EMPTY};
+
+    pub struct Group(uint8x16_t);
+
+    /// the weight of each lane to sum the lanes into the bits
+    const LANE_BITS: [u8; 16] = [1, 2, 4, 8, 16, 32, 64, 128, 1, 2, 4, 8, 16, 32, 64, 128];
+
+    impl Group {
+        /// # Safety
+        /// The pointer should be valid for reading GROUP_WIDTH bytes.
+        pub unsafe fn load(ptr: *const u8) -> Self {
+            Self(vld1q_u8(ptr))
+        }
+
+        /// gather the lanes of 0xFF or 0x00 into the bits
+        unsafe fn to_mask(lanes: uint8x16_t) -> BitMask {
+            let masked = vandq_u8(lanes, vld1q_u8(LANE_BITS.as_ptr()));
+            let low = vaddv_u8(vget_low_u8(masked)) as u16;
+            let high = vaddv_u8(vget_high_u8(masked)) as u16;
+
+            BitMask(low | high << 8)
+        }
+
+        pub fn match_byte(&self, byte: u8) -> BitMask {
+            unsafe { Self::to_mask(vceqq_u8(self.0, vdupq_n_u8(byte))) }
+        }
+
+        pub fn match_empty(&self) -> BitMask {
+            self.match_byte(EMPTY)
+        }
+
+        /// match the slots whose high bit is set
+        pub fn match_empty_or_deleted(&self) -> BitMask {
+            unsafe { Self::to_mask(vtstq_u8(self.0, vdupq_n_u8(DELETED))) }
+        }
+    }
+}
+
+#[cfg(not(any(
+    all(target_arch = "x86_64", target_feature = "sse2"),
+    all(target_arch = "aarch64", target_feature = "neon")
+)))]
+mod imp {
+    use super::{BitMask, DELETED, EMPTY, GROUP_WIDTH};
+
+    pub struct Group([u8; GROUP_WIDTH]);
+
+    impl Group {
+        /// # Safety
+        /// The pointer should be valid for reading GROUP_WIDTH bytes.
+        pub unsafe fn load(ptr: *const u8) -> Self {
+            Self(std::ptr::read_unaligned(ptr as *const [u8; GROUP_WIDTH]))
+        }
+
+        fn match_by<F: Fn(u8) -> bool>(&self, f: F) -> BitMask {
+            let bits = self
+                .0
+                .iter()
+                .enumerate()
+                .filter(|(_, byte)| f(**byte))
+                .fold(0, |bits, (i, _)| bits | 1 << i);
+
+            BitMask(bits)
+        }
+
+        pub fn match_byte(&self, byte: u8) -> BitMask {
+            self.match_by(|b| b == byte)
+        }
+
+        pub fn match_empty(&self) -> BitMask {
+            self.match_byte(EMPTY)
+        }
+
+        /// match the slots whose high bit is set
+        pub fn match_empty_or_deleted(&self) -> BitMask {
+            self.match_by(|b| b & DELETED != 0)
+        }
+    }
+}
+
+pub use imp::Group;
@@ -0,0 +1,582 @@
+/*
+ Refer to
+ Cliff Click. A Lock-Free Wait-Free Hash Table. https://web.stanford.edu/class/ee380/Abstracts/070221_LockFreeHash.pdf
+ and https://github.com/boundary/high-scale-lib
+*/
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hash, Hasher},
+    ptr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crossbeam_epoch::{pin, unprotected, Atomic, Guard, Owned, Shared};
+
+use crate::map::ConcurrentMap;
+
+const MIN_CAPACITY: usize = 16;
+const COPY_CHUNK: usize = 64; // the number of the slots copied by each helper
+
+/// the tag of the key slot which is closed by the copy, so no key is inserted on it
+const TOMBKEY: usize = 1;
+/// the tag of the value slot which is being copied to the next table
+const PRIME: usize = 1;
+/// the tag of the value slot whose value is removed
+const TOMB: usize = 2;
+
+/// lock-free open addressing hash map whose slot changes only by CAS on its state(Cliff Click)
+///
+/// The key slot is claimed once, and never changes until the table is dropped. The value slot is
+/// empty, live, or TOMB for removed. On resizing, every slot of the table is primed, copied to the next
+/// table, then becomes TOMB | PRIME. The writers help to copy a chunk, and copy their slot before writing
+/// on the next table. The readers never write except copying their slot, so the read-mostly workload
+/// runs without any contention.
+pub struct LockFreeHashMap<K, V, S = RandomState> {
+    table: Atomic<Table<K, V>>,
+    len: AtomicUsize,
+    hasher: S,
+}
+
+struct Key<K> {
+    hash: u64,
+    key: K,
+}
+
+/// the value aligned for the tags
+#[repr(align(4))]
+struct Value<V>(V);
+
+struct Slot<K, V> {
+    key: Atomic<Key<K>>,
+    value: Atomic<Value<V>>,
+}
+
+struct Table<K, V> {
+    slots: Box<[Slot<K, V>]>,
+    used: AtomicUsize,         // the number of the claimed key slots
+    next: Atomic<Table<K, V>>, // the table being copied into, only set on the top table
+    copy_index: AtomicUsize,   // the start of the next chunk to copy
+    copied: AtomicUsize,       // the number of the slots finished copying
+}
+
+enum Probe {
+    Found(usize),
+    Empty(usize),
+    Full,
+}
+
+impl<K, V> Table<K, V> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            slots: (0..capacity)
+                .map(|_| Slot {
+                    key: Atomic::null(),
+                    value: Atomic::null(),
+                })
+                .collect(),
+            used: AtomicUsize::new(0),
+            next: Atomic::null(),
+            copy_index: AtomicUsize::new(0),
+            copied: AtomicUsize::new(0),
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// find the slot of the key, or the first empty slot on the probe
+    fn probe(&self, hash: u64, key: &K, guard: &Guard) -> Probe
+    where
+        K: Eq,
+    {
+        let mask = self.capacity() - 1;
+        let mut index = hash as usize & mask;
+
+        for _ in 0..self.capacity() {
+            let current = self.slots[index].key.load(Ordering::Acquire, guard);
+
+            match unsafe { current.as_ref() } {
+                Some(current) if current.hash == hash && current.key == *key => {
+                    return Probe::Found(index)
+                }
+                None if current.tag() != TOMBKEY => return Probe::Empty(index),
+                _ => index = (index + 1) & mask,
+            }
+        }
+
+        Probe::Full
+    }
+}
+
+impl<K, V> Drop for Table<K, V> {
+    fn drop(&mut self) {
+        unsafe {
+            let guard = unprotected();
+
+            for slot in self.slots.iter() {
+                let key = slot.key.load(Ordering::Relaxed, guard);
+
+                if !key.is_null() {
+                    drop(key.into_owned());
+                }
+
+                // the primed value is owned by the next table
+                let value = slot.value.load(Ordering::Relaxed, guard);
+
+                if !value.is_null() && value.tag() & PRIME == 0 {
+                    drop(value.into_owned());
+                }
+            }
+        }
+    }
+}
+
+impl<K, V, S> LockFreeHashMap<K, V, S> {
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            table: Atomic::new(Table::new(MIN_CAPACITY)),
+            len: AtomicUsize::new(0),
+            hasher,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// get the number of the slots of the current table
+    pub fn capacity(&self) -> usize {
+        let guard = pin();
+        unsafe { self.table.load(Ordering::Acquire, &guard).deref() }.capacity()
+    }
+}
+
+impl<K, V, S> LockFreeHashMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    fn hash(&self, key: &K) -> u64 {
+        let mut hasher = self.hasher.build_hasher();
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn next<'g>(table: &'g Table<K, V>, guard: &'g Guard) -> Option<&'g Table<K, V>> {
+        unsafe { table.next.load(Ordering::Acquire, guard).as_ref() }
+    }
+
+    /// copy the slot to the next table, and return true if this call finishes the slot
+    fn copy_slot(&self, table: &Table<K, V>, index: usize, guard: &Guard) -> bool {
+        let slot = &table.slots[index];
+
+        // close the empty key slot
+        let key = match slot.key.compare_exchange(
+            Shared::null(),
+            Shared::null().with_tag(TOMBKEY),
+            Ordering::AcqRel,
+            Ordering::Acquire,
+            guard,
+        ) {
+            Ok(_) => return true,
+            Err(e) => e.current,
+        };
+
+        let key = match unsafe { key.as_ref() } {
+            Some(key) => key,
+            None => return false,
+        };
+
+        // prime the value, so the writers cannot change it on this table
+        let mut value = slot.value.load(Ordering::Acquire, guard);
+
+        while value.tag() & PRIME == 0 {
+            let primed = if value.is_null() {
+                Shared::null().with_tag(TOMB | PRIME)
+            } else {
+                value.with_tag(PRIME)
+            };
+
+            match slot.value.compare_exchange(
+                value,
+                primed,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+                guard,
+            ) {
+                Ok(_) if value.is_null() => return true,
+                Ok(_) => value = primed,
+                Err(e) => value = e.current,
+            }
+        }
+
+        if value.is_null() {
+            return false;
+        }
+
+        // install the value on the next table unless it is installed already, then finish the slot
+        let next = Self::next(table, guard).unwrap();
+        self.install(next, key, value.with_tag(0), guard);
+
+        slot.value
+            .compare_exchange(
+                value,
+                Shared::null().with_tag(TOMB | PRIME),
+                Ordering::AcqRel,
+                Ordering::Acquire,
+                guard,
+            )
+            .is_ok()
+    }
+
+    /// put the value copied from the previous table if the slot of the key has never had the value
+    fn install(&self, table: &Table<K, V>, key: &Key<K>, value: Shared<Value<V>>, guard: &Guard) {
+        loop {
+            match table.probe(key.hash, &key.key, guard) {
+                Probe::Found(index) => {
+                    let _ = table.slots[index].value.compare_exchange(
+                        Shared::null(),
+                        value,
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                        guard,
+                    );
+                    return;
+                }
+                Probe::Empty(index) => {
+                    let new = Owned::new(Key {
+                        hash: key.hash,
+                        key: key.key.clone(),
+                    });
+
+                    if table.slots[index]
+                        .key
+                        .compare_exchange(
+                            Shared::null(),
+                            new,
+                            Ordering::AcqRel,
+                            Ordering::Acquire,
+                            guard,
+                        )
+                        .is_ok()
+                    {
+                        table.used.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+                Probe::Full => unreachable!("the next table is sized to have the copies"),
+            }
+        }
+    }
+
+    /// copy a chunk of the table, and promote the next table if every slot is copied
+    fn help_copy(&self, table: &Table<K, V>, guard: &Guard) {
+        let start = table.copy_index.fetch_add(COPY_CHUNK, Ordering::Relaxed);
+
+        if start >= table.capacity() {
+            return;
+        }
+
+        let end = (start + COPY_CHUNK).min(table.capacity());
+        let count = (start..end)
+            .filter(|index| self.copy_slot(table, *index, guard))
+            .count();
+
+        if table.copied.fetch_add(count, Ordering::AcqRel) + count == table.capacity() {
+            self.promote(table, guard);
+        }
+    }
+
+    /// copy the chunks left, then every slot of the table by itself, and promote the next table
+    fn finish_copy(&self, table: &Table<K, V>, guard: &Guard) {
+        while table.copy_index.load(Ordering::Relaxed) < table.capacity() {
+            self.help_copy(table, guard);
+        }
+
+        // the chunks of the others may be being copied
+        for index in 0..table.capacity() {
+            if self.copy_slot(table, index, guard) {
+                table.copied.fetch_add(1, Ordering::AcqRel);
+            }
+        }
+
+        self.promote(table, guard);
+    }
+
+    /// replace the top table with its next, and reclaim it
+    fn promote(&self, table: &Table<K, V>, guard: &Guard) {
+        let current = Shared::from(table as *const _);
+        let next = table.next.load(Ordering::Acquire, guard);
+
+        if self
+            .table
+            .compare_exchange(current, next, Ordering::AcqRel, Ordering::Acquire, guard)
+            .is_ok()
+        {
+            unsafe { guard.defer_destroy(current) };
+        }
+    }
+
+    /// finish copying the tables above the table, so it becomes the top
+    ///
+    /// Return false if the table is not the newest anymore, which is resized by the others.
+    fn promote_until(&self, table: &Table<K, V>, guard: &Guard) -> bool {
+        loop {
+            let top = self.table.load(Ordering::Acquire, guard);
+
+            if ptr::eq(top.as_raw(), table) {
+                return true;
+            }
+
+            if Self::next(table, guard).is_some() {
+                return false;
+            }
+
+            self.finish_copy(unsafe { top.deref() }, guard);
+        }
+    }
+
+    /// make the next table of the top table, doubling it if it has many live (key, value)s
+    fn resize<'g>(&self, table: &'g Table<K, V>, guard: &'g Guard) -> &'g Table<K, V> {
+        if let Some(next) = Self::next(table, guard) {
+            return next;
+        }
+
+        if !self.promote_until(table, guard) {
+            return Self::next(table, guard).unwrap();
+        }
+
+        let capacity = if self.len() >= table.capacity() / 4 {
+            table.capacity() * 2
+        } else {
+            table.capacity()
+        };
+
+        match table.next.compare_exchange(
+            Shared::null(),
+            Owned::new(Table::new(capacity)),
+            Ordering::AcqRel,
+            Ordering::Acquire,
+            guard,
+        ) {
+            Ok(next) => unsafe { next.deref() },
+            Err(e) => unsafe { e.current.deref() },
+        }
+    }
+
+    /// move the slot to the next table, and get the next table to retry on
+    fn move_next<'g>(
+        &self,
+        table: &'g Table<K, V>,
+        index: usize,
+        guard: &'g Guard,
+    ) -> &'g Table<K, V> {
+        self.help_copy(table, guard);
+        self.copy_slot(table, index, guard);
+        Self::next(table, guard).unwrap()
+    }
+}
+
+impl<K, V, S> ConcurrentMap<K, V> for LockFreeHashMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Default,
+{
+    fn new() -> Self {
+        Self::with_hasher(S::default())
+    }
+
+    fn insert(&self, key: &K, value: V) -> Result<(), V> {
+        let guard = pin();
+        let hash = self.hash(key);
+        let mut table = unsafe { self.table.load(Ordering::Acquire, &guard).deref() };
+        let mut value = Owned::new(Value(value));
+
+        loop {
+            match table.probe(hash, key, &guard) {
+                Probe::Found(index) => {
+                    let slot = &table.slots[index];
+                    let current = slot.value.load(Ordering::Acquire, &guard);
+
+                    if current.tag() & PRIME != 0 || Self::next(table, &guard).is_some() {
+                        table = self.move_next(table, index, &guard);
+                        continue;
+                    }
+
+                    if !current.is_null() {
+                        return Err(value.into_box().0);
+                    }
+
+                    match slot.value.compare_exchange(
+                        current,
+                        value,
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                        &guard,
+                    ) {
+                        Ok(_) => {
+                            self.len.fetch_add(1, Ordering::Relaxed);
+                            return Ok(());
+                        }
+                        Err(e) => value = e.new,
+                    }
+                }
+                Probe::Empty(index) => {
+                    if Self::next(table, &guard).is_some() {
+                        table = self.move_next(table, index, &guard);
+                        continue;
+                    }
+
+                    // the new key is claimed only on the top table, which has no copy to receive
+                    if !self.promote_until(table, &guard) {
+                        continue;
+                    }
+
+                    if table.used.load(Ordering::Relaxed) >= table.capacity() / 4 * 3 {
+                        table = self.resize(table, &guard);
+                        continue;
+                    }
+
+                    let new = Owned::new(Key {
+                        hash,
+                        key: key.clone(),
+                    });
+
+                    if table.slots[index]
+                        .key
+                        .compare_exchange(
+                            Shared::null(),
+                            new,
+                            Ordering::AcqRel,
+                            Ordering::Acquire,
+                            &guard,
+                        )
+                        .is_ok()
+                    {
+                        table.used.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+                Probe::Full => {
+                    table = match Self::next(table, &guard) {
+                        Some(next) => {
+                            self.help_copy(table, &guard);
+                            next
+                        }
+                        None => self.resize(table, &guard),
+                    }
+                }
+            }
+        }
+    }
+
+    fn lookup<F, R>(&self, key: &K, f: F) -> R
+    where
+        F: FnOnce(Option<&V>) -> R,
+    {
+        let guard = pin();
+        let hash = self.hash(key);
+        let mut table = unsafe { self.table.load(Ordering::Acquire, &guard).deref() };
+
+        loop {
+            match table.probe(hash, key, &guard) {
+                Probe::Found(index) => {
+                    let current = table.slots[index].value.load(Ordering::Acquire, &guard);
+
+                    if current.tag() & PRIME != 0 {
+                        self.copy_slot(table, index, &guard);
+                        table = Self::next(table, &guard).unwrap();
+                        continue;
+                    }
+
+                    return f(unsafe { current.as_ref() }.map(|value| &value.0));
+                }
+                Probe::Empty(_) => return f(None),
+                Probe::Full => match Self::next(table, &guard) {
+                    Some(next) => table = next,
+                    None => return f(None),
+                },
+            }
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.lookup(key, |value| value.cloned())
+    }
+
+    fn remove(&self, key: &K) -> Result<V, ()> {
+        let guard = pin();
+        let hash = self.hash(key);
+        let mut table = unsafe { self.table.load(Ordering::Acquire, &guard).deref() };
+
+        loop {
+            match table.probe(hash, key, &guard) {
+                Probe::Found(index) => {
+                    let slot = &table.slots[index];
+                    let current = slot.value.load(Ordering::Acquire, &guard);
+
+                    if current.tag() & PRIME != 0 || Self::next(table, &guard).is_some() {
+                        table = self.move_next(table, index, &guard);
+                        continue;
+                    }
+
+                    if current.is_null() {
+                        return Err(());
+                    }
+
+                    if slot
+                        .value
+                        .compare_exchange(
+                            current,
+                            Shared::null().with_tag(TOMB),
+                            Ordering::AcqRel,
+                            Ordering::Acquire,
+                            &guard,
+                        )
+                        .is_ok()
+                    {
+                        self.len.fetch_sub(1, Ordering::Relaxed);
+
+                        unsafe {
+                            let value = current.deref().0.clone();
+                            guard.defer_destroy(current);
+                            return Ok(value);
+                        }
+                    }
+                }
+                Probe::Empty(_) => return Err(()),
+                Probe::Full => match Self::next(table, &guard) {
+                    Some(next) => {
+                        self.help_copy(table, &guard);
+                        table = next;
+                    }
+                    None => return Err(()),
+                },
+            }
+        }
+    }
+}
+
+impl<K, V, S> Drop for LockFreeHashMap<K, V, S> {
+    fn drop(&mut self) {
+        unsafe {
+            let guard = unprotected();
+            let mut table = self.table.load(Ordering::Relaxed, guard);
+
+            // the next table is not owned by the table, since it becomes the top later
+            while !table.is_null() {
+                let next = table.deref().next.load(Ordering::Relaxed, guard);
+                drop(table.into_owned());
+                table = next;
+            }
+        }
+    }
+}
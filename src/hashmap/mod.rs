@@ -0,0 +1,12 @@
+mod chained;
+mod extendible;
+mod group;
+mod lockfree;
+mod sharded;
+mod swiss;
+
+pub use chained::ChainedHashMap;
+pub use extendible::ExtendibleHashMap;
+pub use lockfree::LockFreeHashMap;
+pub use sharded::{ShardedHashMap, ValueReadGuard, ValueWriteGuard};
+pub use swiss::SwissHashMap;
@@ -0,0 +1,118 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hash, Hasher},
+    thread,
+};
+
+use crossbeam_utils::CachePadded;
+use parking_lot::{
+    MappedRwLockReadGuard, MappedRwLockWriteGuard, RwLock, RwLockReadGuard, RwLockWriteGuard,
+};
+
+use super::ChainedHashMap;
+use crate::map::{ConcurrentMap, SequentialMap};
+
+/// the number of the shards per thread of the machine on `new`
+const SHARDS_PER_THREAD: usize = 4;
+
+/// concurrent hash map whose keys are partitioned into the shards of ChainedHashMap protected by RwLock
+///
+/// The operations on the keys of different shards never contend, and the lookups on the same shard share the lock.
+pub struct ShardedHashMap<K, V, S = RandomState> {
+    shards: Box<[Shard<K, V>]>,
+    hasher: S,
+}
+
+type Shard<K, V> = CachePadded<RwLock<ChainedHashMap<K, V>>>;
+
+/// the guard of the value, which holds the read lock of its shard
+pub type ValueReadGuard<'a, V> = MappedRwLockReadGuard<'a, V>;
+
+/// the guard of the value, which holds the write lock of its shard
+pub type ValueWriteGuard<'a, V> = MappedRwLockWriteGuard<'a, V>;
+
+impl<K, V> ShardedHashMap<K, V> {
+    /// make the map with the number of the shards, which is rounded up to the power of 2
+    pub fn with_shards(shards: usize) -> Self {
+        Self::with_shards_and_hasher(shards, RandomState::new())
+    }
+}
+
+impl<K, V, S> ShardedHashMap<K, V, S> {
+    /// make the map with the number of the shards, which is rounded up to the power of 2, and the hasher choosing the shard
+    pub fn with_shards_and_hasher(shards: usize, hasher: S) -> Self {
+        assert!(shards > 0, "the number of the shards should be positive");
+
+        Self {
+            shards: (0..shards.next_power_of_two())
+                .map(|_| CachePadded::new(RwLock::new(ChainedHashMap::default())))
+                .collect(),
+            hasher,
+        }
+    }
+
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// get the number of the (key, value)s, locking the shards one by one
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.read().len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K: Hash + Eq + Clone, V, S: BuildHasher> ShardedHashMap<K, V, S> {
+    fn shard(&self, key: &K) -> &RwLock<ChainedHashMap<K, V>> {
+        let mut hasher = self.hasher.build_hasher();
+        key.hash(&mut hasher);
+
+        &self.shards[hasher.finish() as usize & (self.shards.len() - 1)]
+    }
+
+    /// get the guard of the value, which blocks the writers of its shard while it lives
+    pub fn read(&self, key: &K) -> Option<ValueReadGuard<'_, V>> {
+        RwLockReadGuard::try_map(self.shard(key).read(), |map| map.lookup(key)).ok()
+    }
+
+    /// get the guard of the mutable value, which blocks the others of its shard while it lives
+    pub fn write(&self, key: &K) -> Option<ValueWriteGuard<'_, V>> {
+        RwLockWriteGuard::try_map(self.shard(key).write(), |map| map.lookup_mut(key)).ok()
+    }
+}
+
+impl<K, V, S> ConcurrentMap<K, V> for ShardedHashMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher + Default,
+{
+    fn new() -> Self {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        Self::with_shards_and_hasher(threads * SHARDS_PER_THREAD, S::default())
+    }
+
+    fn insert(&self, key: &K, value: V) -> Result<(), V> {
+        self.shard(key).write().insert(key, value)
+    }
+
+    fn lookup<F, R>(&self, key: &K, f: F) -> R
+    where
+        F: FnOnce(Option<&V>) -> R,
+    {
+        f(self.shard(key).read().lookup(key))
+    }
+
+    fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.read(key).map(|value| value.clone())
+    }
+
+    fn remove(&self, key: &K) -> Result<V, ()> {
+        self.shard(key).write().remove(key)
+    }
+}
@@ -0,0 +1,264 @@
+/*
+ Refer to
+ https://abseil.io/about/design/swisstables and
+ https://github.com/rust-lang/hashbrown
+*/
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hash, Hasher},
+    mem::{self, MaybeUninit},
+};
+
+use super::group::{Group, DELETED, EMPTY, GROUP_WIDTH};
+use crate::map::SequentialMap;
+
+/// open addressing hash map which probes GROUP_WIDTH slots at once by their control bytes(Swiss table)
+///
+/// The control byte is EMPTY, DELETED, or the top 7 bits of the hash(h2) of the full slot, so most
+/// slots of the other keys are filtered out without comparing the keys. The groups are probed triangularly
+/// from the lower bits of the hash(h1), and the first GROUP_WIDTH control bytes are mirrored after the last
+/// one, so the group can be loaded from any slot. It grows when it is 7/8 full, counting the DELETED slots.
+pub struct SwissHashMap<K, V, S = RandomState> {
+    ctrl: Box<[u8]>, // the number of the buckets + GROUP_WIDTH
+    slots: Box<[MaybeUninit<(K, V)>]>,
+    len: usize,
+    growth_left: usize, // the number of the EMPTY slots that can be filled before growing
+    hasher: S,
+}
+
+/// get the max number of the full and DELETED slots of the buckets, which is 7/8 of them
+fn capacity_of(buckets: usize) -> usize {
+    buckets / 8 * 7
+}
+
+fn h1(hash: u64) -> usize {
+    hash as usize
+}
+
+fn h2(hash: u64) -> u8 {
+    (hash >> 57) as u8
+}
+
+impl<K, V, S: Default> Default for SwissHashMap<K, V, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, V, S> SwissHashMap<K, V, S> {
+    pub fn with_hasher(hasher: S) -> Self {
+        let buckets = GROUP_WIDTH;
+
+        Self {
+            ctrl: vec![EMPTY; buckets + GROUP_WIDTH].into_boxed_slice(),
+            slots: (0..buckets).map(|_| MaybeUninit::uninit()).collect(),
+            len: 0,
+            growth_left: capacity_of(buckets),
+            hasher,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// get the number of the (key, value)s that can be inserted without growing
+    pub fn capacity(&self) -> usize {
+        capacity_of(self.slots.len())
+    }
+
+    fn mask(&self) -> usize {
+        self.slots.len() - 1
+    }
+
+    fn is_full(&self, index: usize) -> bool {
+        self.ctrl[index] & DELETED == 0
+    }
+
+    fn group(&self, index: usize) -> Group {
+        unsafe { Group::load(self.ctrl.as_ptr().add(index)) }
+    }
+
+    /// set the control byte, and its mirror if it is in the first group
+    fn set_ctrl(&mut self, index: usize, byte: u8) {
+        let buckets = self.slots.len();
+
+        self.ctrl[index] = byte;
+
+        if index < GROUP_WIDTH {
+            self.ctrl[buckets + index] = byte;
+        }
+    }
+
+    /// get the sequence of the starts of the groups to probe
+    fn probe(&self, hash: u64) -> impl Iterator<Item = usize> {
+        let mask = self.mask();
+        let mut pos = h1(hash) & mask;
+        let mut stride = 0;
+
+        std::iter::from_fn(move || {
+            let current = pos;
+            stride += GROUP_WIDTH;
+            pos = (pos + stride) & mask;
+
+            Some(current)
+        })
+    }
+
+    /// find the first EMPTY or DELETED slot on the probe
+    fn find_insert_slot(&self, hash: u64) -> usize {
+        for pos in self.probe(hash) {
+            if let Some(bit) = self.group(pos).match_empty_or_deleted().next() {
+                return (pos + bit) & self.mask();
+            }
+        }
+
+        unreachable!()
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> SwissHashMap<K, V, S> {
+    fn hash(&self, key: &K) -> u64 {
+        let mut hasher = self.hasher.build_hasher();
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// find the slot of the key, stopping at the group which has the EMPTY slot
+    fn find(&self, key: &K) -> Option<usize> {
+        let hash = self.hash(key);
+
+        for pos in self.probe(hash) {
+            let group = self.group(pos);
+
+            for bit in group.match_byte(h2(hash)) {
+                let index = (pos + bit) & self.mask();
+
+                if unsafe { self.slots[index].assume_init_ref() }.0 == *key {
+                    return Some(index);
+                }
+            }
+
+            if group.match_empty().any() {
+                return None;
+            }
+        }
+
+        unreachable!()
+    }
+
+    /// move the (key, value)s to the new buckets, doubling them unless DELETED slots take more than half
+    fn resize(&mut self) {
+        let old_buckets = self.slots.len();
+        let buckets = if self.len >= capacity_of(old_buckets) / 2 {
+            old_buckets * 2
+        } else {
+            old_buckets
+        };
+
+        let ctrl = mem::replace(
+            &mut self.ctrl,
+            vec![EMPTY; buckets + GROUP_WIDTH].into_boxed_slice(),
+        );
+        let slots = mem::replace(
+            &mut self.slots,
+            (0..buckets).map(|_| MaybeUninit::uninit()).collect(),
+        );
+        self.growth_left = capacity_of(buckets) - self.len;
+
+        for (byte, slot) in ctrl[..old_buckets].iter().zip(slots.iter()) {
+            if byte & DELETED != 0 {
+                continue;
+            }
+
+            let (key, value) = unsafe { slot.assume_init_read() };
+            let hash = self.hash(&key);
+            let index = self.find_insert_slot(hash);
+
+            self.set_ctrl(index, h2(hash));
+            self.slots[index].write((key, value));
+        }
+    }
+}
+
+impl<K, V, S> SequentialMap<K, V> for SwissHashMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher + Default,
+{
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(&mut self, key: &K, value: V) -> Result<(), V> {
+        if self.find(key).is_some() {
+            return Err(value);
+        }
+
+        let hash = self.hash(key);
+        let mut index = self.find_insert_slot(hash);
+
+        if self.ctrl[index] == EMPTY && self.growth_left == 0 {
+            self.resize();
+            index = self.find_insert_slot(hash);
+        }
+
+        if self.ctrl[index] == EMPTY {
+            self.growth_left -= 1;
+        }
+
+        self.set_ctrl(index, h2(hash));
+        self.slots[index].write((key.clone(), value));
+        self.len += 1;
+
+        Ok(())
+    }
+
+    fn lookup(&self, key: &K) -> Option<&V> {
+        let index = self.find(key)?;
+        Some(unsafe { &self.slots[index].assume_init_ref().1 })
+    }
+
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.find(key)?;
+        Some(unsafe { &mut self.slots[index].assume_init_mut().1 })
+    }
+
+    fn remove(&mut self, key: &K) -> Result<V, ()> {
+        let index = self.find(key).ok_or(())?;
+        let (_, value) = unsafe { self.slots[index].assume_init_read() };
+
+        // if every group containing the slot has the EMPTY slot, no probe has passed the slot
+        let empty_before = self.group(index.wrapping_sub(GROUP_WIDTH) & self.mask());
+        let empty_after = self.group(index);
+        let byte = if empty_before.match_empty().0.leading_zeros()
+            + empty_after.match_empty().0.trailing_zeros()
+            < GROUP_WIDTH as u32
+        {
+            self.growth_left += 1;
+            EMPTY
+        } else {
+            DELETED
+        };
+
+        self.set_ctrl(index, byte);
+        self.len -= 1;
+
+        Ok(value)
+    }
+}
+
+impl<K, V, S> Drop for SwissHashMap<K, V, S> {
+    fn drop(&mut self) {
+        for index in 0..self.slots.len() {
+            if self.is_full(index) {
+                unsafe { self.slots[index].assume_init_drop() };
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use super::PriorityQueue;
+
+/// binary min heap on the array
+pub struct BinaryHeap<V> {
+    data: Vec<V>,
+}
+
+impl<V: Ord> BinaryHeap<V> {
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+
+            if self.data[parent] <= self.data[index] {
+                break;
+            }
+
+            self.data.swap(parent, index);
+            index = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let left = 2 * index + 1;
+            let right = left + 1;
+            let mut smallest = index;
+
+            if left < self.data.len() && self.data[left] < self.data[smallest] {
+                smallest = left;
+            }
+
+            if right < self.data.len() && self.data[right] < self.data[smallest] {
+                smallest = right;
+            }
+
+            if smallest == index {
+                break;
+            }
+
+            self.data.swap(index, smallest);
+            index = smallest;
+        }
+    }
+}
+
+impl<V: Ord> PriorityQueue<V> for BinaryHeap<V> {
+    fn new() -> Self {
+        Self { data: Vec::new() }
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn push(&mut self, value: V) {
+        self.data.push(value);
+        self.sift_up(self.data.len() - 1);
+    }
+
+    fn pop(&mut self) -> Option<V> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let value = self.data.swap_remove(0);
+
+        if !self.data.is_empty() {
+            self.sift_down(0);
+        }
+
+        Some(value)
+    }
+
+    fn peek(&self) -> Option<&V> {
+        self.data.first()
+    }
+}
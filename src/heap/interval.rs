@@ -0,0 +1,167 @@
+use super::PriorityQueue;
+
+/// double-ended priority queue, which pops both the smallest and the largest value in O(log n)
+///
+/// The node k has the interval [data[2k], data[2k + 1]], which contains the intervals of its children.
+/// So the mins make a min heap, and the maxes make a max heap. The last node may have only the min.
+pub struct IntervalHeap<V> {
+    data: Vec<V>,
+}
+
+impl<V: Ord> IntervalHeap<V> {
+    pub fn peek_min(&self) -> Option<&V> {
+        self.data.first()
+    }
+
+    pub fn peek_max(&self) -> Option<&V> {
+        self.data.get(1).or_else(|| self.data.first())
+    }
+
+    pub fn pop_min(&mut self) -> Option<V> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let value = self.data.swap_remove(0);
+        self.sift_down_min(0);
+
+        Some(value)
+    }
+
+    pub fn pop_max(&mut self) -> Option<V> {
+        if self.data.len() <= 2 {
+            return self.data.pop();
+        }
+
+        let value = self.data.swap_remove(1);
+        self.sift_down_max(1);
+
+        Some(value)
+    }
+
+    /// order the min and the max of the node of the index
+    fn fix_interval(&mut self, index: usize) {
+        let min = index & !1;
+
+        if min + 1 < self.data.len() && self.data[min] > self.data[min + 1] {
+            self.data.swap(min, min + 1);
+        }
+    }
+
+    fn sift_up_min(&mut self, mut index: usize) {
+        while index >= 2 {
+            let parent = (index / 2 - 1) / 2 * 2;
+
+            if self.data[parent] <= self.data[index] {
+                break;
+            }
+
+            self.data.swap(parent, index);
+            index = parent;
+        }
+    }
+
+    fn sift_up_max(&mut self, mut index: usize) {
+        while index >= 2 {
+            let parent = (index / 2 - 1) / 2 * 2 + 1;
+
+            if self.data[parent] >= self.data[index] {
+                break;
+            }
+
+            self.data.swap(parent, index);
+            index = parent;
+        }
+    }
+
+    fn sift_down_min(&mut self, mut index: usize) {
+        loop {
+            self.fix_interval(index);
+
+            let child = index / 2 * 4 + 2;
+            let smallest = [child, child + 2]
+                .iter()
+                .copied()
+                .filter(|child| *child < self.data.len())
+                .min_by(|a, b| self.data[*a].cmp(&self.data[*b]));
+
+            match smallest {
+                Some(child) if self.data[child] < self.data[index] => {
+                    self.data.swap(index, child);
+                    index = child;
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn sift_down_max(&mut self, mut index: usize) {
+        loop {
+            self.fix_interval(index);
+
+            // the max of the child, or its min if it has only the min
+            let child = index / 2 * 4 + 2;
+            let largest = [child, child + 2]
+                .iter()
+                .copied()
+                .filter_map(|child| {
+                    if child + 1 < self.data.len() {
+                        Some(child + 1)
+                    } else if child < self.data.len() {
+                        Some(child)
+                    } else {
+                        None
+                    }
+                })
+                .max_by(|a, b| self.data[*a].cmp(&self.data[*b]));
+
+            match largest {
+                Some(child) if self.data[child] > self.data[index] => {
+                    self.data.swap(index, child);
+                    index = child;
+                }
+                _ => break,
+            }
+        }
+    }
+}
+
+impl<V: Ord> PriorityQueue<V> for IntervalHeap<V> {
+    fn new() -> Self {
+        Self { data: Vec::new() }
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn push(&mut self, value: V) {
+        self.data.push(value);
+
+        let index = self.data.len() - 1;
+
+        if index % 2 == 1 {
+            // the node gets the max
+            self.fix_interval(index);
+            self.sift_up_min(index - 1);
+            self.sift_up_max(index);
+        } else if index >= 2 {
+            // the new node has only the min, so compare it with the interval of the parent
+            let parent = (index / 2 - 1) / 2 * 2;
+
+            if self.data[index] < self.data[parent] {
+                self.sift_up_min(index);
+            } else if self.data[index] > self.data[parent + 1] {
+                self.sift_up_max(index);
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<V> {
+        self.pop_min()
+    }
+
+    fn peek(&self) -> Option<&V> {
+        self.peek_min()
+    }
+}
@@ -0,0 +1,32 @@
+mod binary;
+mod interval;
+mod pairing;
+mod radix;
+mod skiplist;
+
+pub use binary::BinaryHeap;
+pub use interval::IntervalHeap;
+pub use pairing::{Handle, PairingHeap};
+pub use radix::{Radix, RadixHeap};
+pub use skiplist::SkipListPriorityQueue;
+
+/// min priority queue, which pops the smallest value first
+pub trait PriorityQueue<V: Ord> {
+    fn new() -> Self;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn push(&mut self, value: V);
+    fn pop(&mut self) -> Option<V>;
+    /// get the smallest value without popping it
+    fn peek(&self) -> Option<&V>;
+}
+
+/// concurrent min priority queue, which allows the same priorities
+pub trait ConcurrentPriorityQueue<P: Ord, V> {
+    fn new() -> Self;
+    fn insert(&self, priority: P, value: V);
+    /// pop the (priority, value) of the smallest priority, or return None if it is observed as empty
+    fn pop_min(&self) -> Option<(P, V)>;
+}
@@ -0,0 +1,235 @@
+/*
+ Refer to
+ Fredman, Sedgewick, Sleator, Tarjan. The pairing heap: A new form of self-adjusting heap. Algorithmica 1986.
+*/
+
+use std::{
+    collections::HashMap,
+    hash::{BuildHasherDefault, Hasher},
+    mem,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use super::PriorityQueue;
+use crate::slab::{Arena, Index};
+
+/// the source of the arena ids, which tell the arena of the handle
+static ARENA_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// the hasher of the arena ids, which are unique so used as the hashes as they are
+#[derive(Default)]
+struct ArenaIdHasher(u64);
+
+impl Hasher for ArenaIdHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, _: &[u8]) {
+        unreachable!("the arena id should be hashed by write_usize")
+    }
+
+    fn write_usize(&mut self, id: usize) {
+        self.0 = id as u64;
+    }
+}
+
+/// pairing min heap, whose nodes are in the arenas so the handles for `decrease_key` are checked
+///
+/// Each heap pushes into its own arena, and keeps the arenas of the heaps melded into it, so meld moves no nodes.
+/// The arenas are looked up by the id without hashing it, since every link between the nodes goes through them.
+/// The children of a node are in the list from its leftmost child by `sibling`.
+/// `prev` points to the left sibling, or to the parent if the node is the leftmost child.
+pub struct PairingHeap<V> {
+    arenas: HashMap<usize, Arena<Node<V>>, BuildHasherDefault<ArenaIdHasher>>,
+    root: Option<Handle>,
+    len: usize,
+    id: usize, // the arena to push
+}
+
+struct Node<V> {
+    value: V,
+    child: Option<Handle>,
+    sibling: Option<Handle>,
+    prev: Option<Handle>,
+}
+
+/// the handle of the value pushed by `push_with_handle`, used for `decrease_key`
+///
+/// It becomes stale after the value is popped. It is still valid after its heap is melded into another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Handle {
+    arena: usize,
+    node: Index,
+}
+
+impl<V: Ord> PairingHeap<V> {
+    fn node(&self, handle: Handle) -> &Node<V> {
+        &self.arenas[&handle.arena][handle.node]
+    }
+
+    fn node_mut(&mut self, handle: Handle) -> &mut Node<V> {
+        &mut self.arenas.get_mut(&handle.arena).unwrap()[handle.node]
+    }
+
+    /// link two roots, and return the new root which has the smaller value
+    fn link(&mut self, a: Option<Handle>, b: Option<Handle>) -> Option<Handle> {
+        let (a, b) = match (a, b) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return a.or(b),
+        };
+
+        let (parent, child) = if self.node(b).value < self.node(a).value {
+            (b, a)
+        } else {
+            (a, b)
+        };
+
+        let first = self.node(parent).child;
+
+        if let Some(first) = first {
+            self.node_mut(first).prev = Some(child);
+        }
+
+        let node = self.node_mut(child);
+        node.sibling = first;
+        node.prev = Some(parent);
+        self.node_mut(parent).child = Some(child);
+
+        Some(parent)
+    }
+
+    /// link the list of the roots in two passes: from the left by pairs, then from the right
+    fn merge_pairs(&mut self, mut first: Option<Handle>) -> Option<Handle> {
+        let mut pairs = Vec::new();
+
+        while let Some(a) = first {
+            let b = self.node(a).sibling;
+            first = b.and_then(|b| self.node(b).sibling);
+
+            for node in [Some(a), b].iter().flatten() {
+                let node = self.node_mut(*node);
+                node.sibling = None;
+                node.prev = None;
+            }
+
+            pairs.push(self.link(Some(a), b));
+        }
+
+        pairs
+            .into_iter()
+            .rev()
+            .fold(None, |root, node| self.link(node, root))
+    }
+
+    /// push the value, and return its handle for `decrease_key`
+    pub fn push_with_handle(&mut self, value: V) -> Handle {
+        let node = self.arenas.get_mut(&self.id).unwrap().insert(Node {
+            value,
+            child: None,
+            sibling: None,
+            prev: None,
+        });
+        let handle = Handle {
+            arena: self.id,
+            node,
+        };
+
+        self.root = self.link(self.root, Some(handle));
+        self.len += 1;
+        handle
+    }
+
+    /// move all values of the other heap into this by linking the roots, so the handles of the other are still valid
+    ///
+    /// It moves no nodes, but only the arenas of the heap which has fewer ones, like union by size.
+    pub fn meld(&mut self, mut other: Self) {
+        if other.arenas.len() > self.arenas.len() {
+            mem::swap(&mut self.arenas, &mut other.arenas);
+        }
+
+        self.arenas.extend(other.arenas.drain());
+        self.root = self.link(self.root, other.root);
+        self.len += other.len;
+    }
+
+    /// check whether the value of the handle is in this heap
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.arenas
+            .get(&handle.arena)
+            .map_or(false, |arena| arena.contains(handle.node))
+    }
+
+    /// decrease the value of the handle, or return Err(value) if it is greater than the current value or the handle
+    /// is stale
+    pub fn decrease_key(&mut self, handle: Handle, value: V) -> Result<(), V> {
+        if !self.contains(handle) || value > self.node(handle).value {
+            return Err(value);
+        }
+
+        self.node_mut(handle).value = value;
+
+        if self.root == Some(handle) {
+            return Ok(());
+        }
+
+        // cut the subtree of the node, then link it to the root
+        let node = self.node_mut(handle);
+        let prev = node.prev.take().unwrap();
+        let sibling = node.sibling.take();
+
+        if self.node(prev).child == Some(handle) {
+            self.node_mut(prev).child = sibling;
+        } else {
+            self.node_mut(prev).sibling = sibling;
+        }
+
+        if let Some(sibling) = sibling {
+            self.node_mut(sibling).prev = Some(prev);
+        }
+
+        self.root = self.link(self.root, Some(handle));
+        Ok(())
+    }
+}
+
+impl<V: Ord> PriorityQueue<V> for PairingHeap<V> {
+    fn new() -> Self {
+        let id = ARENA_ID.fetch_add(1, Ordering::Relaxed);
+
+        Self {
+            arenas: vec![(id, Arena::new())].into_iter().collect(),
+            root: None,
+            len: 0,
+            id,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn push(&mut self, value: V) {
+        self.push_with_handle(value);
+    }
+
+    fn pop(&mut self) -> Option<V> {
+        let root = self.root?;
+        let arena = self.arenas.get_mut(&root.arena).unwrap();
+        let node = arena.remove(root.node).unwrap();
+
+        // the emptied arena of a melded heap is not used anymore
+        if arena.is_empty() && root.arena != self.id {
+            self.arenas.remove(&root.arena);
+        }
+
+        self.len -= 1;
+        self.root = self.merge_pairs(node.child);
+
+        Some(node.value)
+    }
+
+    fn peek(&self) -> Option<&V> {
+        self.root.map(|root| &self.node(root).value)
+    }
+}
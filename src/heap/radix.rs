@@ -0,0 +1,100 @@
+/*
+ Refer to
+ Ahuja, Mehlhorn, Orlin, Tarjan. Faster Algorithms for the Shortest Path Problem. JACM 1990.
+*/
+
+use std::mem;
+
+/// the unsigned integer priority of RadixHeap
+pub trait Radix: Copy + Ord {
+    const BITS: u32;
+
+    /// get the number of the bits from the highest different bit, which is 0 if they are the same
+    fn distance(self, other: Self) -> u32;
+}
+
+macro_rules! impl_radix {
+    ($($type:ty),*) => {
+        $(
+            impl Radix for $type {
+                const BITS: u32 = <$type>::BITS;
+
+                fn distance(self, other: Self) -> u32 {
+                    Self::BITS - (self ^ other).leading_zeros()
+                }
+            }
+        )*
+    };
+}
+
+impl_radix!(u8, u16, u32, u64, usize);
+
+/// monotone min priority queue for the integer priorities
+///
+/// The pushed priority should not be smaller than the last popped one, which holds on Dijkstra's algorithm.
+/// The bucket i has the priorities whose highest different bit from the last popped one is the bit i - 1,
+/// so each (priority, value) moves to the lower bucket at most BITS times and the comparison is never needed.
+pub struct RadixHeap<P: Radix, V> {
+    buckets: Vec<Vec<(P, V)>>,
+    last: Option<P>, // the last popped priority
+    len: usize,
+}
+
+impl<P: Radix, V> Default for RadixHeap<P, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Radix, V> RadixHeap<P, V> {
+    pub fn new() -> Self {
+        Self {
+            buckets: (0..=P::BITS).map(|_| Vec::new()).collect(),
+            last: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn bucket(&self, priority: P) -> usize {
+        self.last.map_or(P::BITS, |last| priority.distance(last)) as usize
+    }
+
+    /// push the (priority, value), or return Err(value) if the priority is smaller than the last popped one
+    pub fn push(&mut self, priority: P, value: V) -> Result<(), V> {
+        if self.last.map_or(false, |last| priority < last) {
+            return Err(value);
+        }
+
+        let bucket = self.bucket(priority);
+        self.buckets[bucket].push((priority, value));
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// pop the (priority, value) of the smallest priority
+    pub fn pop(&mut self) -> Option<(P, V)> {
+        if self.buckets[0].is_empty() {
+            // move the first non-empty bucket to the lower buckets with its minimum as the last
+            let index = self.buckets.iter().position(|bucket| !bucket.is_empty())?;
+            let entries = mem::take(&mut self.buckets[index]);
+            self.last = entries.iter().map(|(priority, _)| *priority).min();
+
+            for (priority, value) in entries {
+                let bucket = self.bucket(priority);
+                self.buckets[bucket].push((priority, value));
+            }
+        }
+
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+}
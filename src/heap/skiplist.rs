@@ -0,0 +1,45 @@
+/*
+ Refer to
+ The Art of Multiprocessor Programming, 15.5 A Skiplist-Based Unbounded Priority Queue and
+ Lotan, Shavit. Skiplist-Based Concurrent Priority Queues. IPDPS 2000.
+*/
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::ConcurrentPriorityQueue;
+use crate::{map::ConcurrentMap, skiplist::LockFreeSkipList};
+
+/// lock-free priority queue on LockFreeSkipList
+///
+/// The key is (priority, sequence), so the same priorities are popped in the order of the insertion.
+/// `pop_min` marks the first node on the bottom level, and only the thread that marks it wins. The losers move on
+/// to the next node without searching from the head again.
+pub struct SkipListPriorityQueue<P, V> {
+    list: LockFreeSkipList<(P, u64), V>,
+    sequence: AtomicU64,
+}
+
+impl<P, V> ConcurrentPriorityQueue<P, V> for SkipListPriorityQueue<P, V>
+where
+    P: Ord + Clone,
+    V: Clone,
+{
+    fn new() -> Self {
+        Self {
+            list: LockFreeSkipList::new(),
+            sequence: AtomicU64::new(0),
+        }
+    }
+
+    fn insert(&self, priority: P, value: V) {
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed);
+        let result = self.list.insert(&(priority, sequence), value);
+
+        debug_assert!(result.is_ok(), "the sequence should be unique");
+    }
+
+    fn pop_min(&self) -> Option<(P, V)> {
+        let ((priority, _), value) = self.list.pop_first()?;
+        Some((priority, value))
+    }
+}
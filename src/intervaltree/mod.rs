@@ -0,0 +1,276 @@
+use std::{
+    cmp::{self, Ordering},
+    ops::{Bound, Range},
+};
+
+/// AVL tree of the half-open intervals ordered by (start, end), whose node has the max end on its subtree
+///
+/// The same interval can be inserted several times. The queries skip the subtree whose max end is not after
+/// the query, so they take O(log n + k) for k results.
+pub struct IntervalTree<K, V> {
+    root: Subtree<K, V>,
+    len: usize,
+}
+
+type Subtree<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    range: Range<K>,
+    value: V,
+    max: K, // the max end on the subtree
+    height: usize,
+    left: Subtree<K, V>,
+    right: Subtree<K, V>,
+}
+
+/// get the height of the subtree
+fn height<K, V>(tree: &Subtree<K, V>) -> usize {
+    tree.as_ref().map_or(0, |node| node.height)
+}
+
+/// compare the intervals by (start, end)
+fn compare<K: Ord>(a: &Range<K>, b: &Range<K>) -> Ordering {
+    a.start.cmp(&b.start).then_with(|| a.end.cmp(&b.end))
+}
+
+impl<K: Ord + Clone, V> Node<K, V> {
+    fn new(range: Range<K>, value: V) -> Box<Self> {
+        Box::new(Self {
+            max: range.end.clone(),
+            range,
+            value,
+            height: 1,
+            left: None,
+            right: None,
+        })
+    }
+
+    /// renew the height and the max end of the node from the childs
+    fn renew(&mut self) {
+        self.height = cmp::max(height(&self.left), height(&self.right)) + 1;
+        self.max = [&self.left, &self.right]
+            .iter()
+            .filter_map(|child| child.as_ref().map(|child| &child.max))
+            .fold(&self.range.end, cmp::max)
+            .clone();
+    }
+
+    /// rotate left the node, then return new parent(old right child)
+    fn rotate_left(mut node: Box<Self>) -> Box<Self> {
+        let mut right = node.right.take().unwrap();
+        node.right = right.left.take();
+        node.renew();
+        right.left = Some(node);
+        right.renew();
+        right
+    }
+
+    /// rotate right the node, then return new parent(old left child)
+    fn rotate_right(mut node: Box<Self>) -> Box<Self> {
+        let mut left = node.left.take().unwrap();
+        node.left = left.right.take();
+        node.renew();
+        left.right = Some(node);
+        left.renew();
+        left
+    }
+
+    /// rebalance the node whose childs differ in height by at most 2, then return new parent
+    fn balance(mut node: Box<Self>) -> Box<Self> {
+        let (left_height, right_height) = (height(&node.left), height(&node.right));
+
+        if left_height > right_height + 1 {
+            let left = node.left.as_ref().unwrap();
+
+            if height(&left.left) < height(&left.right) {
+                node.left = Some(Node::rotate_left(node.left.take().unwrap()));
+            }
+
+            Node::rotate_right(node)
+        } else if right_height > left_height + 1 {
+            let right = node.right.as_ref().unwrap();
+
+            if height(&right.right) < height(&right.left) {
+                node.right = Some(Node::rotate_right(node.right.take().unwrap()));
+            }
+
+            Node::rotate_left(node)
+        } else {
+            node.renew();
+            node
+        }
+    }
+
+    fn insert(tree: &mut Subtree<K, V>, node: Box<Self>) {
+        let current = match tree {
+            Some(current) => current,
+            None => {
+                *tree = Some(node);
+                return;
+            }
+        };
+
+        // the same interval goes to the right
+        match compare(&node.range, &current.range) {
+            Ordering::Less => Node::insert(&mut current.left, node),
+            _ => Node::insert(&mut current.right, node),
+        }
+
+        *tree = Some(Node::balance(tree.take().unwrap()));
+    }
+
+    /// remove the node that has the smallest interval on the tree, then return (new root, the node)
+    fn remove_first(mut node: Box<Self>) -> (Subtree<K, V>, Box<Self>) {
+        match node.left.take() {
+            Some(left) => {
+                let (left, first) = Node::remove_first(left);
+                node.left = left;
+                (Some(Node::balance(node)), first)
+            }
+            None => {
+                let right = node.right.take();
+                (right, node)
+            }
+        }
+    }
+
+    fn remove(tree: &mut Subtree<K, V>, range: &Range<K>) -> Result<Box<Self>, ()> {
+        let current = tree.as_mut().ok_or(())?;
+
+        let node = match compare(range, &current.range) {
+            Ordering::Less => Node::remove(&mut current.left, range)?,
+            Ordering::Greater => Node::remove(&mut current.right, range)?,
+            Ordering::Equal => {
+                let mut node = tree.take().unwrap();
+
+                // replace the node with its successor
+                *tree = match node.right.take() {
+                    Some(right) => {
+                        let (right, mut successor) = Node::remove_first(right);
+                        successor.left = node.left.take();
+                        successor.right = right;
+                        Some(successor)
+                    }
+                    None => node.left.take(),
+                };
+
+                if let Some(current) = tree.take() {
+                    *tree = Some(Node::balance(current));
+                }
+
+                return Ok(node);
+            }
+        };
+
+        *tree = Some(Node::balance(tree.take().unwrap()));
+        Ok(node)
+    }
+}
+
+impl<K: Ord + Clone, V> Default for IntervalTree<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Clone, V> IntervalTree<K, V> {
+    pub fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// insert the non-empty interval with the value
+    pub fn insert(&mut self, range: Range<K>, value: V) {
+        assert!(range.start < range.end, "the range should not be empty");
+
+        Node::insert(&mut self.root, Node::new(range, value));
+        self.len += 1;
+    }
+
+    /// remove one of the same intervals, then return its value
+    pub fn remove(&mut self, range: &Range<K>) -> Result<V, ()> {
+        let node = Node::remove(&mut self.root, range)?;
+        self.len -= 1;
+
+        Ok(node.value)
+    }
+
+    /// get the iterator of (interval, value)s which contain the point in the order of the interval
+    pub fn query_point(&self, point: &K) -> Overlap<'_, K, V> {
+        Overlap::new(&self.root, point.clone(), Bound::Included(point.clone()))
+    }
+
+    /// get the iterator of (interval, value)s which overlap the range in the order of the interval
+    pub fn query_overlap(&self, range: Range<K>) -> Overlap<'_, K, V> {
+        Overlap::new(&self.root, range.start, Bound::Excluded(range.end))
+    }
+}
+
+/// the in-order iterator of the intervals whose end > low and start <= or < high
+pub struct Overlap<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+    low: K,
+    high: Bound<K>,
+}
+
+impl<'a, K: Ord, V> Overlap<'a, K, V> {
+    fn new(root: &'a Subtree<K, V>, low: K, high: Bound<K>) -> Self {
+        let mut iter = Self {
+            stack: Vec::new(),
+            low,
+            high,
+        };
+
+        iter.push_left(root);
+        iter
+    }
+
+    /// push the left spine of the subtree, stopping at the subtree whose intervals all end before low
+    fn push_left(&mut self, mut tree: &'a Subtree<K, V>) {
+        while let Some(node) = tree {
+            if node.max <= self.low {
+                break;
+            }
+
+            self.stack.push(node);
+            tree = &node.left;
+        }
+    }
+
+    fn starts_before_high(&self, start: &K) -> bool {
+        match &self.high {
+            Bound::Included(high) => start <= high,
+            Bound::Excluded(high) => start < high,
+            Bound::Unbounded => true,
+        }
+    }
+}
+
+impl<'a, K: Ord, V> Iterator for Overlap<'a, K, V> {
+    type Item = (&'a Range<K>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = self.stack.pop()?;
+
+            // the nodes after this start at or after it
+            if !self.starts_before_high(&node.range.start) {
+                self.stack.clear();
+                return None;
+            }
+
+            self.push_left(&node.right);
+
+            if node.range.end > self.low {
+                return Some((&node.range, &node.value));
+            }
+        }
+    }
+}
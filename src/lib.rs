@@ -1,8 +1,30 @@
 pub mod avltree;
+pub mod bitset;
+pub mod bplustree;
+pub mod bst;
 pub mod btree;
+pub mod cache;
+pub mod dsu;
+pub mod filter;
+pub mod hashmap;
+pub mod heap;
+pub mod intervaltree;
 pub mod linkedlist;
 pub mod lock;
 pub mod map;
+pub mod merkle;
+pub mod persistent;
 pub mod queue;
+pub mod rope;
+pub mod scapegoattree;
+pub mod sketch;
+pub mod skiplist;
+pub mod slab;
+pub mod spatial;
+pub mod splaytree;
 pub mod stack;
+pub mod treap;
+pub mod trie;
 pub mod util;
+pub mod wavelet;
+pub mod wbtree;
@@ -0,0 +1,313 @@
+use std::{marker::PhantomData, mem, ptr};
+
+use crate::map::SequentialMap;
+
+// sequential doubly linked list which can be edited in the middle by the cursor
+pub struct DoublyLinkedList<K, V> {
+    head: *mut Node<K, V>,
+    tail: *mut Node<K, V>,
+    len: usize,
+    _marker: PhantomData<Box<Node<K, V>>>,
+}
+
+unsafe impl<K: Send, V: Send> Send for DoublyLinkedList<K, V> {}
+unsafe impl<K: Sync, V: Sync> Sync for DoublyLinkedList<K, V> {}
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    prev: *mut Node<K, V>,
+    next: *mut Node<K, V>,
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: V) -> *mut Node<K, V> {
+        Box::into_raw(Box::new(Node {
+            key,
+            value,
+            prev: ptr::null_mut(),
+            next: ptr::null_mut(),
+        }))
+    }
+}
+
+impl<K, V> DoublyLinkedList<K, V> {
+    fn empty() -> Self {
+        Self {
+            head: ptr::null_mut(),
+            tail: ptr::null_mut(),
+            len: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// get the cursor on the front node, or on the ghost if the list is empty
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, K, V> {
+        CursorMut {
+            current: self.head,
+            index: if self.head.is_null() { None } else { Some(0) },
+            list: self,
+        }
+    }
+
+    /// get the cursor on the back node, or on the ghost if the list is empty
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, K, V> {
+        CursorMut {
+            current: self.tail,
+            index: self.len.checked_sub(1),
+            list: self,
+        }
+    }
+
+    /// link the node between prev and next, where null means the end of the list
+    unsafe fn link(&mut self, prev: *mut Node<K, V>, next: *mut Node<K, V>, node: *mut Node<K, V>) {
+        (*node).prev = prev;
+        (*node).next = next;
+
+        match prev.as_mut() {
+            Some(prev) => prev.next = node,
+            None => self.head = node,
+        }
+
+        match next.as_mut() {
+            Some(next) => next.prev = node,
+            None => self.tail = node,
+        }
+
+        self.len += 1;
+    }
+
+    unsafe fn unlink(&mut self, node: *mut Node<K, V>) -> Box<Node<K, V>> {
+        let node = Box::from_raw(node);
+
+        match node.prev.as_mut() {
+            Some(prev) => prev.next = node.next,
+            None => self.head = node.next,
+        }
+
+        match node.next.as_mut() {
+            Some(next) => next.prev = node.prev,
+            None => self.tail = node.prev,
+        }
+
+        self.len -= 1;
+        node
+    }
+
+    fn find(&self, key: &K) -> *mut Node<K, V>
+    where
+        K: Eq,
+    {
+        let mut current = self.head;
+
+        while let Some(node) = unsafe { current.as_ref() } {
+            if node.key == *key {
+                break;
+            }
+
+            current = node.next;
+        }
+
+        current
+    }
+}
+
+impl<K, V> SequentialMap<K, V> for DoublyLinkedList<K, V>
+where
+    K: Eq + Clone,
+{
+    fn new() -> Self {
+        Self::empty()
+    }
+
+    fn insert(&mut self, key: &K, value: V) -> Result<(), V> {
+        if !self.find(key).is_null() {
+            return Err(value);
+        }
+
+        unsafe { self.link(self.tail, ptr::null_mut(), Node::new(key.clone(), value)) };
+        Ok(())
+    }
+
+    fn lookup(&self, key: &K) -> Option<&V> {
+        unsafe { self.find(key).as_ref().map(|node| &node.value) }
+    }
+
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V> {
+        unsafe { self.find(key).as_mut().map(|node| &mut node.value) }
+    }
+
+    fn remove(&mut self, key: &K) -> Result<V, ()> {
+        let node = self.find(key);
+
+        if node.is_null() {
+            return Err(());
+        }
+
+        Ok(unsafe { self.unlink(node) }.value)
+    }
+}
+
+impl<K, V> Drop for DoublyLinkedList<K, V> {
+    fn drop(&mut self) {
+        let mut current = self.head;
+
+        while !current.is_null() {
+            let node = unsafe { Box::from_raw(current) };
+            current = node.next;
+        }
+    }
+}
+
+/// mutable cursor on the list
+///
+/// The cursor is on a node or on the ghost between the back and the front of the list.
+/// Inserting by the cursor does not check the key, so the caller should keep the keys unique.
+pub struct CursorMut<'a, K, V> {
+    current: *mut Node<K, V>, // null if the cursor is on the ghost
+    index: Option<usize>,
+    list: &'a mut DoublyLinkedList<K, V>,
+}
+
+impl<'a, K, V> CursorMut<'a, K, V> {
+    /// get the index of the current node, or None on the ghost
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn current(&mut self) -> Option<(&K, &mut V)> {
+        unsafe {
+            self.current
+                .as_mut()
+                .map(|node| (&node.key, &mut node.value))
+        }
+    }
+
+    pub fn peek_next(&mut self) -> Option<(&K, &mut V)> {
+        let next = match unsafe { self.current.as_ref() } {
+            Some(node) => node.next,
+            None => self.list.head,
+        };
+
+        unsafe { next.as_mut().map(|node| (&node.key, &mut node.value)) }
+    }
+
+    pub fn peek_prev(&mut self) -> Option<(&K, &mut V)> {
+        let prev = match unsafe { self.current.as_ref() } {
+            Some(node) => node.prev,
+            None => self.list.tail,
+        };
+
+        unsafe { prev.as_mut().map(|node| (&node.key, &mut node.value)) }
+    }
+
+    /// move to the next node, where the next of the back is the ghost, and the next of the ghost is the front
+    pub fn move_next(&mut self) {
+        match unsafe { self.current.as_ref() } {
+            Some(node) => {
+                self.current = node.next;
+                self.index = if self.current.is_null() {
+                    None
+                } else {
+                    self.index.map(|index| index + 1)
+                };
+            }
+            None => {
+                self.current = self.list.head;
+                self.index = if self.current.is_null() {
+                    None
+                } else {
+                    Some(0)
+                };
+            }
+        }
+    }
+
+    /// move to the prev node, where the prev of the front is the ghost, and the prev of the ghost is the back
+    pub fn move_prev(&mut self) {
+        match unsafe { self.current.as_ref() } {
+            Some(node) => {
+                self.current = node.prev;
+                self.index = self.index.and_then(|index| index.checked_sub(1));
+            }
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.len.checked_sub(1);
+            }
+        }
+    }
+
+    /// insert (key, value) after the current node, or at the front on the ghost
+    pub fn insert_after(&mut self, key: K, value: V) {
+        let next = match unsafe { self.current.as_ref() } {
+            Some(node) => node.next,
+            None => self.list.head,
+        };
+
+        unsafe { self.list.link(self.current, next, Node::new(key, value)) };
+    }
+
+    /// insert (key, value) before the current node, or at the back on the ghost
+    pub fn insert_before(&mut self, key: K, value: V) {
+        let prev = match unsafe { self.current.as_ref() } {
+            Some(node) => node.prev,
+            None => self.list.tail,
+        };
+
+        unsafe { self.list.link(prev, self.current, Node::new(key, value)) };
+        self.index = self.index.map(|index| index + 1);
+    }
+
+    /// remove the current node and move to the next node
+    ///
+    /// If the cursor is on the ghost, return None without removing.
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        if self.current.is_null() {
+            return None;
+        }
+
+        let node = unsafe { self.list.unlink(self.current) };
+        self.current = node.next;
+
+        if self.current.is_null() {
+            self.index = None;
+        }
+
+        Some((node.key, node.value))
+    }
+
+    /// split the list into the nodes until the current node and the rest, and return the rest
+    ///
+    /// If the cursor is on the ghost, return the whole list.
+    pub fn split_after(&mut self) -> DoublyLinkedList<K, V> {
+        let current = match unsafe { self.current.as_mut() } {
+            Some(node) => node,
+            None => return mem::replace(self.list, DoublyLinkedList::empty()),
+        };
+
+        let mut rest = DoublyLinkedList::empty();
+
+        if !current.next.is_null() {
+            let split = self.index.unwrap() + 1;
+
+            rest.head = current.next;
+            rest.tail = self.list.tail;
+            rest.len = self.list.len - split;
+            unsafe { (*rest.head).prev = ptr::null_mut() };
+
+            current.next = ptr::null_mut();
+            self.list.tail = current;
+            self.list.len = split;
+        }
+
+        rest
+    }
+}
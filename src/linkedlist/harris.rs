@@ -0,0 +1,216 @@
+/*
+ Refer to
+ A Pragmatic Implementation of Non-Blocking Linked-Lists(Harris, 2001)
+*/
+
+use std::sync::atomic::Ordering;
+
+use crossbeam_epoch::{pin, unprotected, Atomic, Guard, Owned, Shared};
+
+use crate::map::ConcurrentMap;
+
+/// lock-free ordered linked list whose node is removed logically by marking its next(tag = 1)
+pub struct HarrisList<K, V> {
+    head: Atomic<Node<K, V>>,
+}
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    next: Atomic<Node<K, V>>,
+}
+
+/// (whether the key is found, the link to the node, the node)
+type Position<'g, K, V> = (bool, &'g Atomic<Node<K, V>>, Shared<'g, Node<K, V>>);
+
+impl<K: Ord, V> HarrisList<K, V> {
+    /// find the first unmarked node whose key >= key and the link that points it
+    ///
+    /// The marked nodes between them are unlinked by one CAS, and reclaimed by epoch.
+    fn find<'g>(&'g self, key: &K, guard: &'g Guard) -> Position<'g, K, V> {
+        'retry: loop {
+            let mut prev = &self.head;
+            let mut prev_next = prev.load(Ordering::Acquire, guard);
+
+            loop {
+                // the node of prev is removed
+                if prev_next.tag() == 1 {
+                    continue 'retry;
+                }
+
+                // skip the marked nodes
+                let mut curr = prev_next;
+
+                while let Some(curr_ref) = unsafe { curr.as_ref() } {
+                    let next = curr_ref.next.load(Ordering::Acquire, guard);
+
+                    if next.tag() == 0 {
+                        break;
+                    }
+
+                    curr = next.with_tag(0);
+                }
+
+                if prev_next != curr {
+                    if prev
+                        .compare_exchange(
+                            prev_next,
+                            curr,
+                            Ordering::AcqRel,
+                            Ordering::Acquire,
+                            guard,
+                        )
+                        .is_err()
+                    {
+                        continue 'retry;
+                    }
+
+                    // reclaim the unlinked nodes
+                    while prev_next != curr {
+                        unsafe {
+                            let next = prev_next.deref().next.load(Ordering::Relaxed, guard);
+                            guard.defer_destroy(prev_next);
+                            prev_next = next.with_tag(0);
+                        }
+                    }
+                }
+
+                match unsafe { curr.as_ref() } {
+                    Some(curr_ref) if curr_ref.key < *key => {
+                        prev = &curr_ref.next;
+                        prev_next = prev.load(Ordering::Acquire, guard);
+                    }
+                    Some(curr_ref) => return (curr_ref.key == *key, prev, curr),
+                    None => return (false, prev, curr),
+                }
+            }
+        }
+    }
+}
+
+impl<K, V> ConcurrentMap<K, V> for HarrisList<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    fn new() -> Self {
+        Self {
+            head: Atomic::null(),
+        }
+    }
+
+    fn insert(&self, key: &K, value: V) -> Result<(), V> {
+        let guard = pin();
+
+        let mut node = Owned::new(Node {
+            key: key.clone(),
+            value,
+            next: Atomic::null(),
+        });
+
+        loop {
+            let (found, prev, curr) = self.find(key, &guard);
+
+            if found {
+                return Err(node.into_box().value);
+            }
+
+            node.next.store(curr, Ordering::Relaxed);
+
+            match prev.compare_exchange(curr, node, Ordering::Release, Ordering::Relaxed, &guard) {
+                Ok(_) => return Ok(()),
+                Err(e) => node = e.new,
+            }
+        }
+    }
+
+    fn lookup<F, R>(&self, key: &K, f: F) -> R
+    where
+        F: FnOnce(Option<&V>) -> R,
+    {
+        let guard = pin();
+        let mut curr = self.head.load(Ordering::Acquire, &guard);
+
+        // traverse without unlinking the marked nodes
+        while let Some(curr_ref) = unsafe { curr.with_tag(0).as_ref() } {
+            let next = curr_ref.next.load(Ordering::Acquire, &guard);
+
+            if curr_ref.key >= *key {
+                if curr_ref.key == *key && next.tag() == 0 {
+                    return f(Some(&curr_ref.value));
+                }
+
+                break;
+            }
+
+            curr = next;
+        }
+
+        f(None)
+    }
+
+    fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.lookup(key, |value| value.cloned())
+    }
+
+    fn remove(&self, key: &K) -> Result<V, ()> {
+        let guard = pin();
+        let (found, prev, curr) = self.find(key, &guard);
+
+        if !found {
+            return Err(());
+        }
+
+        let curr_ref = unsafe { curr.deref() };
+        let mut next = curr_ref.next.load(Ordering::Acquire, &guard);
+
+        // the thread that marks the node removes it
+        loop {
+            if next.tag() == 1 {
+                return Err(());
+            }
+
+            match curr_ref.next.compare_exchange(
+                next,
+                next.with_tag(1),
+                Ordering::AcqRel,
+                Ordering::Acquire,
+                &guard,
+            ) {
+                Ok(_) => break,
+                Err(e) => next = e.current,
+            }
+        }
+
+        let value = curr_ref.value.clone();
+
+        if prev
+            .compare_exchange(curr, next, Ordering::AcqRel, Ordering::Relaxed, &guard)
+            .is_ok()
+        {
+            unsafe { guard.defer_destroy(curr) };
+        } else {
+            // let find unlink it
+            self.find(key, &guard);
+        }
+
+        Ok(value)
+    }
+}
+
+impl<K, V> Drop for HarrisList<K, V> {
+    fn drop(&mut self) {
+        unsafe {
+            let guard = unprotected();
+            let mut current = self.head.load(Ordering::Relaxed, guard);
+
+            while !current.is_null() {
+                let node = current.into_owned();
+                current = node.next.load(Ordering::Relaxed, guard).with_tag(0);
+            }
+        }
+    }
+}
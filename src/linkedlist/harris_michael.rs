@@ -0,0 +1,236 @@
+/*
+ Refer to
+ High Performance Dynamic Lock-Free Hash Tables and List-Based Sets(Michael, 2002)
+*/
+
+use std::{
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use crate::map::ConcurrentMap;
+use crate::util::hazard::{Hazard, HazardDomain};
+
+/// lock-free ordered linked list whose removed node is reclaimed by hazard pointers
+///
+/// Unlike HarrisList, the traversal unlinks the marked(tag = 1) nodes one by one,
+/// so every node in the traversal can be protected by hazard pointers.
+pub struct HarrisMichaelList<K, V> {
+    head: AtomicPtr<Node<K, V>>,
+    domain: HazardDomain<Node<K, V>>,
+}
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    next: AtomicPtr<Node<K, V>>,
+}
+
+fn tag<T>(pointer: *mut T) -> usize {
+    pointer as usize & 1
+}
+
+fn with_tag<T>(pointer: *mut T, tag: usize) -> *mut T {
+    ((pointer as usize & !1) | tag) as *mut T
+}
+
+/// the hazards protecting prev, curr and next on the traversal
+struct Hazards<'d, K, V> {
+    prev: Hazard<'d, Node<K, V>>,
+    curr: Hazard<'d, Node<K, V>>,
+    next: Hazard<'d, Node<K, V>>,
+}
+
+/// the position of the key, where prev is the link to curr
+struct Position<'d, K, V> {
+    found: bool,
+    prev: &'d AtomicPtr<Node<K, V>>,
+    curr: *mut Node<K, V>,
+}
+
+impl<K: Ord, V> HarrisMichaelList<K, V> {
+    fn hazards(&self) -> Hazards<'_, K, V> {
+        Hazards {
+            prev: self.domain.acquire(),
+            curr: self.domain.acquire(),
+            next: self.domain.acquire(),
+        }
+    }
+
+    /// find the first node whose key >= key, unlinking and retiring the marked nodes on the way
+    ///
+    /// The returned prev and curr are protected by the hazards.
+    fn find<'d>(&'d self, key: &K, hazards: &Hazards<'d, K, V>) -> Position<'d, K, V> {
+        'retry: loop {
+            let mut prev = &self.head;
+            let mut curr = prev.load(Ordering::Acquire);
+
+            loop {
+                // protect curr, and validate that prev is not marked and still points curr
+                hazards.curr.protect(curr);
+
+                if prev.load(Ordering::SeqCst) != curr {
+                    continue 'retry;
+                }
+
+                let curr_ref = match unsafe { curr.as_ref() } {
+                    Some(curr_ref) => curr_ref,
+                    None => {
+                        return Position {
+                            found: false,
+                            prev,
+                            curr,
+                        }
+                    }
+                };
+
+                let next = curr_ref.next.load(Ordering::Acquire);
+                hazards.next.protect(with_tag(next, 0));
+
+                if curr_ref.next.load(Ordering::SeqCst) != next {
+                    continue 'retry;
+                }
+
+                if tag(next) == 0 {
+                    if curr_ref.key >= *key {
+                        return Position {
+                            found: curr_ref.key == *key,
+                            prev,
+                            curr,
+                        };
+                    }
+
+                    hazards.prev.protect(curr);
+                    prev = &curr_ref.next;
+                } else {
+                    // curr is removed, so unlink it
+                    let next = with_tag(next, 0);
+
+                    if prev
+                        .compare_exchange(curr, next, Ordering::AcqRel, Ordering::Relaxed)
+                        .is_err()
+                    {
+                        continue 'retry;
+                    }
+
+                    unsafe { self.domain.retire(curr) };
+                }
+
+                curr = with_tag(next, 0);
+            }
+        }
+    }
+}
+
+impl<K, V> ConcurrentMap<K, V> for HarrisMichaelList<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    fn new() -> Self {
+        Self {
+            head: AtomicPtr::new(ptr::null_mut()),
+            domain: HazardDomain::new(),
+        }
+    }
+
+    fn insert(&self, key: &K, value: V) -> Result<(), V> {
+        let hazards = self.hazards();
+
+        let node = Box::into_raw(Box::new(Node {
+            key: key.clone(),
+            value,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }));
+
+        loop {
+            let position = self.find(key, &hazards);
+
+            if position.found {
+                return Err(unsafe { Box::from_raw(node) }.value);
+            }
+
+            unsafe { (*node).next.store(position.curr, Ordering::Relaxed) };
+
+            if position
+                .prev
+                .compare_exchange(position.curr, node, Ordering::Release, Ordering::Relaxed)
+                .is_ok()
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    fn lookup<F, R>(&self, key: &K, f: F) -> R
+    where
+        F: FnOnce(Option<&V>) -> R,
+    {
+        let hazards = self.hazards();
+        let position = self.find(key, &hazards);
+
+        if position.found {
+            f(Some(unsafe { &(*position.curr).value }))
+        } else {
+            f(None)
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.lookup(key, |value| value.cloned())
+    }
+
+    fn remove(&self, key: &K) -> Result<V, ()> {
+        let hazards = self.hazards();
+
+        loop {
+            let position = self.find(key, &hazards);
+
+            if !position.found {
+                return Err(());
+            }
+
+            let curr_ref = unsafe { &*position.curr };
+            let next = curr_ref.next.load(Ordering::Acquire);
+
+            // retry if curr is marked by other, or its next is changed
+            if tag(next) == 1
+                || curr_ref
+                    .next
+                    .compare_exchange(next, with_tag(next, 1), Ordering::AcqRel, Ordering::Relaxed)
+                    .is_err()
+            {
+                continue;
+            }
+
+            let value = curr_ref.value.clone();
+
+            if position
+                .prev
+                .compare_exchange(position.curr, next, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok()
+            {
+                unsafe { self.domain.retire(position.curr) };
+            } else {
+                // let find unlink it
+                self.find(key, &hazards);
+            }
+
+            return Ok(value);
+        }
+    }
+}
+
+impl<K, V> Drop for HarrisMichaelList<K, V> {
+    fn drop(&mut self) {
+        let mut current = with_tag(*self.head.get_mut(), 0);
+
+        while !current.is_null() {
+            let mut node = unsafe { Box::from_raw(current) };
+            current = with_tag(*node.next.get_mut(), 0);
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use std::{
+    cell::Cell,
+    marker::{PhantomData, PhantomPinned},
+    pin::Pin,
+    ptr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// the source of the ownership tokens, where 0 means no list owns the link
+static LIST_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// the link embedded in the element of IntrusiveList
+///
+/// It points to the neighbor elements, not their links, so the list reaches a link only through the pointer to its
+/// whole element. It records the token of the list that owns it, so the element can be in at most one list.
+pub struct ListLink {
+    prev: Cell<*const ()>, // the element, whose type is known by the list
+    next: Cell<*const ()>,
+    owner: Cell<usize>,
+    _pin: PhantomPinned,
+}
+
+impl Default for ListLink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ListLink {
+    pub const fn new() -> Self {
+        Self {
+            prev: Cell::new(ptr::null()),
+            next: Cell::new(ptr::null()),
+            owner: Cell::new(0),
+            _pin: PhantomPinned,
+        }
+    }
+
+    pub fn is_linked(&self) -> bool {
+        self.owner.get() != 0
+    }
+}
+
+/// the type which embeds ListLink
+///
+/// # Safety
+/// `link` should always return the same ListLink embedded in the element, which no other element shares. Use
+/// `impl_linked!` to implement it.
+pub unsafe trait Linked {
+    fn link(&self) -> &ListLink;
+}
+
+/// implement Linked for the struct with the field of ListLink
+#[macro_export]
+macro_rules! impl_linked {
+    ($type:ty, $field:ident) => {
+        unsafe impl $crate::linkedlist::Linked for $type {
+            fn link(&self) -> &$crate::linkedlist::ListLink {
+                &self.$field
+            }
+        }
+    };
+}
+
+/// doubly linked list of the pinned elements which embed ListLink, so it never allocates
+///
+/// The elements are borrowed for 'a, so they are not moved or dropped while they are in the list.
+pub struct IntrusiveList<'a, T: Linked> {
+    head: *const T,
+    tail: *const T,
+    len: usize,
+    id: usize, // the ownership token written on the links in the list
+    _marker: PhantomData<Pin<&'a T>>,
+}
+
+impl<'a, T: Linked> Default for IntrusiveList<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: Linked> IntrusiveList<'a, T> {
+    pub fn new() -> Self {
+        Self {
+            head: ptr::null(),
+            tail: ptr::null(),
+            len: 0,
+            id: LIST_ID.fetch_add(1, Ordering::Relaxed),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// check that the element is in this list in O(1) by its ownership token
+    pub fn contains(&self, item: Pin<&T>) -> bool {
+        item.link().owner.get() == self.id
+    }
+
+    pub fn front(&self) -> Option<Pin<&'a T>> {
+        unsafe { Self::element(self.head) }
+    }
+
+    pub fn back(&self) -> Option<Pin<&'a T>> {
+        unsafe { Self::element(self.tail) }
+    }
+
+    /// push the element at the front
+    ///
+    /// If it is already in a list, return Err(item).
+    pub fn push_front(&mut self, item: Pin<&'a T>) -> Result<(), Pin<&'a T>> {
+        let next = self.head;
+        self.link(item, ptr::null(), next)
+    }
+
+    /// push the element at the back
+    ///
+    /// If it is already in a list, return Err(item).
+    pub fn push_back(&mut self, item: Pin<&'a T>) -> Result<(), Pin<&'a T>> {
+        let prev = self.tail;
+        self.link(item, prev, ptr::null())
+    }
+
+    pub fn pop_front(&mut self) -> Option<Pin<&'a T>> {
+        let item = self.front()?;
+        self.unlink(item);
+        Some(item)
+    }
+
+    pub fn pop_back(&mut self) -> Option<Pin<&'a T>> {
+        let item = self.back()?;
+        self.unlink(item);
+        Some(item)
+    }
+
+    /// remove the element in O(1)
+    ///
+    /// If it is not in this list, return false.
+    pub fn remove(&mut self, item: Pin<&'a T>) -> bool {
+        if !self.contains(item) {
+            return false;
+        }
+
+        self.unlink(item);
+        true
+    }
+
+    /// get the iterator of the elements from the front to the back
+    pub fn iter(&self) -> Iter<'_, 'a, T> {
+        Iter {
+            current: self.head,
+            _marker: PhantomData,
+        }
+    }
+
+    unsafe fn element(item: *const T) -> Option<Pin<&'a T>> {
+        item.as_ref().map(|item| Pin::new_unchecked(item))
+    }
+
+    fn link(&mut self, item: Pin<&'a T>, prev: *const T, next: *const T) -> Result<(), Pin<&'a T>> {
+        let element: *const T = item.get_ref();
+        let link = item.link();
+
+        if link.is_linked() {
+            return Err(item);
+        }
+
+        link.owner.set(self.id);
+        link.prev.set(prev as *const ());
+        link.next.set(next as *const ());
+
+        match unsafe { prev.as_ref() } {
+            Some(prev) => prev.link().next.set(element as *const ()),
+            None => self.head = element,
+        }
+
+        match unsafe { next.as_ref() } {
+            Some(next) => next.link().prev.set(element as *const ()),
+            None => self.tail = element,
+        }
+
+        self.len += 1;
+        Ok(())
+    }
+
+    /// unlink the element owned by this list
+    fn unlink(&mut self, item: Pin<&'a T>) {
+        let link = item.link();
+        let prev = link.prev.replace(ptr::null()) as *const T;
+        let next = link.next.replace(ptr::null()) as *const T;
+        link.owner.set(0);
+
+        match unsafe { prev.as_ref() } {
+            Some(prev) => prev.link().next.set(next as *const ()),
+            None => self.head = next,
+        }
+
+        match unsafe { next.as_ref() } {
+            Some(next) => next.link().prev.set(prev as *const ()),
+            None => self.tail = prev,
+        }
+
+        self.len -= 1;
+    }
+}
+
+impl<'a, T: Linked> Drop for IntrusiveList<'a, T> {
+    /// unlink all elements, so they can be pushed into another list
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+pub struct Iter<'l, 'a, T: Linked> {
+    current: *const T,
+    _marker: PhantomData<&'l IntrusiveList<'a, T>>,
+}
+
+impl<'l, 'a, T: Linked> Iterator for Iter<'l, 'a, T> {
+    type Item = Pin<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = unsafe { IntrusiveList::<'a, T>::element(self.current)? };
+        self.current = item.link().next.get() as *const T;
+
+        Some(item)
+    }
+}
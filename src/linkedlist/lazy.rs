@@ -0,0 +1,212 @@
+/*
+ Refer to
+ The Art of Multiprocessor Programming, 9.7 Lazy Synchronization and
+ A Lazy Concurrent List-Based Set Algorithm(Heller et al., 2005)
+*/
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crossbeam_epoch::{pin, unprotected, Atomic, Guard, Owned, Shared};
+
+use crate::lock::{RawSimpleLock, RawSpinLock};
+use crate::map::ConcurrentMap;
+
+/// ordered linked list with the lock per node, and the lookup without locking
+///
+/// The node is removed logically by marking it, and then physically by unlinking it under the locks of pred and curr.
+/// So, the unmarked node is always reachable, and the lookup only checks the mark of the node.
+pub struct LazyList<K, V> {
+    head: Atomic<Node<K, V>>,
+    head_lock: RawSpinLock, // the lock of the head link, which acts as the lock of the dummy node
+}
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    lock: RawSpinLock,
+    marked: AtomicBool,
+    next: Atomic<Node<K, V>>,
+}
+
+/// (pred, curr), where the pred None means the head
+type Position<'g, K, V> = (Option<&'g Node<K, V>>, Shared<'g, Node<K, V>>);
+
+impl<K: Ord, V> LazyList<K, V> {
+    /// find the pred and curr where curr is the first node whose key >= key, without locking
+    fn find<'g>(&self, key: &K, guard: &'g Guard) -> Position<'g, K, V> {
+        let mut pred = None;
+        let mut curr = self.head.load(Ordering::Acquire, guard);
+
+        while let Some(curr_ref) = unsafe { curr.as_ref() } {
+            if curr_ref.key >= *key {
+                break;
+            }
+
+            pred = Some(curr_ref);
+            curr = curr_ref.next.load(Ordering::Acquire, guard);
+        }
+
+        (pred, curr)
+    }
+
+    fn link<'g>(&'g self, pred: Option<&'g Node<K, V>>) -> &'g Atomic<Node<K, V>> {
+        match pred {
+            Some(pred) => &pred.next,
+            None => &self.head,
+        }
+    }
+
+    fn lock_of<'g>(&'g self, pred: Option<&'g Node<K, V>>) -> &'g RawSpinLock {
+        match pred {
+            Some(pred) => &pred.lock,
+            None => &self.head_lock,
+        }
+    }
+
+    /// lock pred and curr, and validate that both are not removed and pred still points curr
+    ///
+    /// If validating fails, unlock them and return false.
+    fn lock_and_validate(
+        &self,
+        pred: Option<&Node<K, V>>,
+        curr: Shared<Node<K, V>>,
+        guard: &Guard,
+    ) -> bool {
+        let curr_ref = unsafe { curr.as_ref() };
+
+        self.lock_of(pred).lock();
+
+        if let Some(curr_ref) = curr_ref {
+            curr_ref.lock.lock();
+        }
+
+        let valid = pred.map_or(true, |pred| !pred.marked.load(Ordering::Acquire))
+            && curr_ref.map_or(true, |curr| !curr.marked.load(Ordering::Acquire))
+            && self.link(pred).load(Ordering::Acquire, guard) == curr;
+
+        if !valid {
+            self.unlock(pred, curr);
+        }
+
+        valid
+    }
+
+    fn unlock(&self, pred: Option<&Node<K, V>>, curr: Shared<Node<K, V>>) {
+        if let Some(curr) = unsafe { curr.as_ref() } {
+            curr.lock.unlock();
+        }
+
+        self.lock_of(pred).unlock();
+    }
+}
+
+impl<K, V> ConcurrentMap<K, V> for LazyList<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    fn new() -> Self {
+        Self {
+            head: Atomic::null(),
+            head_lock: RawSpinLock::new(),
+        }
+    }
+
+    fn insert(&self, key: &K, value: V) -> Result<(), V> {
+        let guard = pin();
+
+        loop {
+            let (pred, curr) = self.find(key, &guard);
+
+            if !self.lock_and_validate(pred, curr, &guard) {
+                continue;
+            }
+
+            let result = match unsafe { curr.as_ref() } {
+                Some(curr_ref) if curr_ref.key == *key => Err(value),
+                _ => {
+                    let node = Owned::new(Node {
+                        key: key.clone(),
+                        value,
+                        lock: RawSpinLock::new(),
+                        marked: AtomicBool::new(false),
+                        next: Atomic::from(curr),
+                    });
+
+                    self.link(pred).store(node, Ordering::Release);
+                    Ok(())
+                }
+            };
+
+            self.unlock(pred, curr);
+            return result;
+        }
+    }
+
+    fn lookup<F, R>(&self, key: &K, f: F) -> R
+    where
+        F: FnOnce(Option<&V>) -> R,
+    {
+        let guard = pin();
+        let (_, curr) = self.find(key, &guard);
+
+        match unsafe { curr.as_ref() } {
+            Some(curr_ref) if curr_ref.key == *key && !curr_ref.marked.load(Ordering::Acquire) => {
+                f(Some(&curr_ref.value))
+            }
+            _ => f(None),
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.lookup(key, |value| value.cloned())
+    }
+
+    fn remove(&self, key: &K) -> Result<V, ()> {
+        let guard = pin();
+
+        loop {
+            let (pred, curr) = self.find(key, &guard);
+
+            if !self.lock_and_validate(pred, curr, &guard) {
+                continue;
+            }
+
+            let result = match unsafe { curr.as_ref() } {
+                Some(curr_ref) if curr_ref.key == *key => {
+                    // remove logically, then physically
+                    curr_ref.marked.store(true, Ordering::Release);
+                    self.link(pred).store(
+                        curr_ref.next.load(Ordering::Acquire, &guard),
+                        Ordering::Release,
+                    );
+
+                    let value = curr_ref.value.clone();
+                    unsafe { guard.defer_destroy(curr) };
+                    Ok(value)
+                }
+                _ => Err(()),
+            };
+
+            self.unlock(pred, curr);
+            return result;
+        }
+    }
+}
+
+impl<K, V> Drop for LazyList<K, V> {
+    fn drop(&mut self) {
+        unsafe {
+            let guard = unprotected();
+            let mut current = self.head.load(Ordering::Relaxed, guard);
+
+            while !current.is_null() {
+                let node = current.into_owned();
+                current = node.next.load(Ordering::Relaxed, guard);
+            }
+        }
+    }
+}
@@ -1,29 +1,56 @@
+mod doubly;
+mod harris;
+mod harris_michael;
+mod intrusive;
+mod lazy;
+mod unrolled;
+
+pub use doubly::{CursorMut, DoublyLinkedList};
+pub use harris::HarrisList;
+pub use harris_michael::HarrisMichaelList;
+pub use intrusive::{IntrusiveList, Linked, ListLink};
+pub use lazy::LazyList;
+pub use unrolled::UnrolledLinkedList;
+
+use std::{iter::FromIterator, marker::PhantomData, ptr};
+
 use crate::map::SequentialMap;
 
 // simple sequential linked list
 pub struct LinkedList<K, V> {
     head: Node<K, V>, // dummy node with key = Default, but the key is not considered on algorithm
+    tail: *mut Node<K, V>, // the last node, or null if the list is empty
+    len: usize,
+    _marker: PhantomData<Box<Node<K, V>>>,
 }
 
+unsafe impl<K: Send, V: Send> Send for LinkedList<K, V> {}
+unsafe impl<K: Sync, V: Sync> Sync for LinkedList<K, V> {}
+
+// the nodes are owned by raw pointers, so that the tail pointer is not invalidated by the borrows of the nodes
 struct Node<K, V> {
     key: K,
     value: V,
-    next: Option<Box<Node<K, V>>>,
+    next: *mut Node<K, V>,
 }
 
 impl<K: Default, V: Default> Default for Node<K, V> {
     fn default() -> Self {
-        Self::new(K::default(), V::default())
+        Node {
+            key: K::default(),
+            value: V::default(),
+            next: ptr::null_mut(),
+        }
     }
 }
 
 impl<K, V> Node<K, V> {
-    fn new(key: K, value: V) -> Node<K, V> {
-        Node {
+    fn new(key: K, value: V) -> *mut Node<K, V> {
+        Box::into_raw(Box::new(Node {
             key,
             value,
-            next: None,
-        }
+            next: ptr::null_mut(),
+        }))
     }
 }
 
@@ -35,77 +62,335 @@ where
     fn new() -> LinkedList<K, V> {
         LinkedList {
             head: Node::default(),
+            tail: ptr::null_mut(),
+            len: 0,
+            _marker: PhantomData,
         }
     }
 
     fn insert(&mut self, key: &K, value: V) -> Result<(), V> {
-        let new = Box::new(Node::new(key.clone(), value));
+        if !self.find(key).is_null() {
+            return Err(value);
+        }
+
+        let new = Node::new(key.clone(), value);
+
+        match unsafe { self.tail.as_mut() } {
+            Some(tail) => tail.next = new,
+            None => self.head.next = new,
+        }
+
+        self.tail = new;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn lookup(&self, key: &K) -> Option<&V> {
+        unsafe { self.find(key).as_ref().map(|node| &node.value) }
+    }
+
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V> {
+        unsafe { self.find(key).as_mut().map(|node| &mut node.value) }
+    }
+
+    fn remove(&mut self, key: &K) -> Result<V, ()> {
+        let mut prev: *mut Node<K, V> = &mut self.head;
 
-        let mut current = &mut self.head.next;
+        unsafe {
+            while let Some(node) = (*prev).next.as_ref() {
+                if node.key == *key {
+                    let node = Box::from_raw((*prev).next);
+                    (*prev).next = node.next;
+                    self.len -= 1;
 
-        loop {
-            match current {
-                Some(node) => {
-                    if node.key == *key {
-                        return Err(new.value);
+                    if node.next.is_null() {
+                        self.tail = if self.len == 0 { ptr::null_mut() } else { prev };
                     }
 
-                    current = &mut node.next;
-                }
-                None => {
-                    *current = Some(new);
-                    return Ok(());
+                    return Ok(node.value);
                 }
+
+                prev = (*prev).next;
             }
         }
+
+        Err(())
     }
+}
 
-    fn lookup(&self, key: &K) -> Option<&V> {
-        let mut current = &self.head.next;
+impl<K, V> Extend<(K, V)> for LinkedList<K, V>
+where
+    K: Default + Eq + Clone,
+    V: Default,
+{
+    /// Insert all (key, value) pairs. If the key already exists, the value is overwritten.
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            if let Err(value) = self.insert(&key, value) {
+                *self.lookup_mut(&key).unwrap() = value;
+            }
+        }
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for LinkedList<K, V>
+where
+    K: Default + Eq + Clone,
+    V: Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
 
-        loop {
-            match current {
-                Some(node) => {
-                    let value = &node.value;
+impl<K, V> LinkedList<K, V> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
 
-                    if node.key == *key {
-                        return Some(value);
-                    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
 
-                    current = &node.next;
-                }
-                None => return None,
+    /// get the node of the key, or null if the key does not exist
+    fn find(&self, key: &K) -> *mut Node<K, V>
+    where
+        K: Eq,
+    {
+        let mut current = self.head.next;
+
+        while let Some(node) = unsafe { current.as_ref() } {
+            if node.key == *key {
+                break;
             }
+
+            current = node.next;
+        }
+
+        current
+    }
+
+    /// move all nodes of other to the back of the list in O(1) by splicing, so other becomes empty
+    ///
+    /// The keys of other should not exist in the list, which is not checked.
+    pub fn append(&mut self, other: &mut Self) {
+        if other.is_empty() {
+            return;
+        }
+
+        match unsafe { self.tail.as_mut() } {
+            Some(tail) => tail.next = other.head.next,
+            None => self.head.next = other.head.next,
         }
+
+        self.tail = other.tail;
+        self.len += other.len;
+
+        other.head.next = ptr::null_mut();
+        other.tail = ptr::null_mut();
+        other.len = 0;
     }
 
-    fn remove(&mut self, key: &K) -> Result<V, ()> {
-        let mut prev = &mut self.head;
+    /// get the iterator of (key, value)s in the order of the list
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            current: unsafe { self.head.next.as_ref() },
+        }
+    }
+
+    /// get the iterator of (key, mutable value)s in the order of the list
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            current: unsafe { self.head.next.as_mut() },
+        }
+    }
+}
 
-        loop {
-            match prev.next.is_some() {
-                true => {
-                    if prev.next.as_ref().unwrap().key == *key {
-                        let mut node = prev.next.take();
-                        prev.next = node.as_mut().unwrap().next.take();
+impl<K: Default, V: Default> LinkedList<K, V> {
+    /// split the list into [0, at) and [at, len), and return the latter
+    ///
+    /// Panics if at > len.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(
+            at <= self.len,
+            "cannot split off at {} from the list of length {}",
+            at,
+            self.len
+        );
 
-                        return Ok(node.unwrap().value);
-                    }
+        let mut rest = LinkedList {
+            head: Node::default(),
+            tail: ptr::null_mut(),
+            len: self.len - at,
+            _marker: PhantomData,
+        };
 
-                    prev = prev.next.as_mut().unwrap();
+        if at == self.len {
+            return rest;
+        }
+
+        let mut prev: *mut Node<K, V> = &mut self.head;
+
+        for _ in 0..at {
+            prev = unsafe { (*prev).next };
+        }
+
+        rest.head.next = unsafe { (*prev).next };
+        rest.tail = self.tail;
+
+        unsafe { (*prev).next = ptr::null_mut() };
+        self.tail = if at == 0 { ptr::null_mut() } else { prev };
+        self.len = at;
+
+        rest
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    current: Option<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.current?;
+        self.current = unsafe { node.next.as_ref() };
+
+        Some((&node.key, &node.value))
+    }
+}
+
+pub struct IterMut<'a, K, V> {
+    current: Option<&'a mut Node<K, V>>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.current.take()?;
+        self.current = unsafe { node.next.as_mut() };
+
+        Some((&node.key, &mut node.value))
+    }
+}
+
+pub struct IntoIter<K, V> {
+    list: LinkedList<K, V>,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.list.head.next.is_null() {
+            return None;
+        }
+
+        let node = unsafe { Box::from_raw(self.list.head.next) };
+        self.list.head.next = node.next;
+        self.list.len -= 1;
+
+        if self.list.is_empty() {
+            self.list.tail = ptr::null_mut();
+        }
+
+        Some((node.key, node.value))
+    }
+}
+
+impl<K, V> IntoIterator for LinkedList<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a LinkedList<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut LinkedList<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K: serde::Serialize, V: serde::Serialize> serde::Serialize for LinkedList<K, V> {
+    /// serialize as the map whose entries are in the order of the list
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+
+        for (key, value) in self {
+            map.serialize_entry(key, value)?;
+        }
+
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> serde::Deserialize<'de> for LinkedList<K, V>
+where
+    K: serde::Deserialize<'de> + Default + Eq + Clone,
+    V: serde::Deserialize<'de> + Default,
+{
+    /// deserialize from the map, keeping the order of the entries
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor<K, V>(std::marker::PhantomData<(K, V)>);
+
+        impl<'de, K, V> serde::de::Visitor<'de> for Visitor<K, V>
+        where
+            K: serde::Deserialize<'de> + Default + Eq + Clone,
+            V: serde::Deserialize<'de> + Default,
+        {
+            type Value = LinkedList<K, V>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                // the hint is from the input, so do not trust a huge one
+                let mut items = Vec::with_capacity(map.size_hint().unwrap_or(0).min(4096));
+
+                while let Some(item) = map.next_entry()? {
+                    items.push(item);
                 }
-                false => return Err(()),
+
+                Ok(items.into_iter().collect())
             }
         }
+
+        deserializer.deserialize_map(Visitor(std::marker::PhantomData))
     }
 }
 
 impl<K, V> Drop for LinkedList<K, V> {
     fn drop(&mut self) {
-        let mut node = self.head.next.take();
+        let mut node = self.head.next;
 
-        while let Some(mut inside) = node {
-            node = inside.next.take();
+        while !node.is_null() {
+            node = unsafe { Box::from_raw(node) }.next;
         }
     }
 }
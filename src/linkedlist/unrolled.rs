@@ -0,0 +1,258 @@
+use std::mem;
+
+use crate::map::SequentialMap;
+
+/// sequential linked list whose node has at most B (key, value)s in the order of the list
+///
+/// Every node except the last one has at least B / 2 (key, value)s, so the nodes are compact on the cache.
+pub struct UnrolledLinkedList<K, V, const B: usize = 16> {
+    head: Option<Box<Node<K, V, B>>>,
+    len: usize,
+}
+
+struct Node<K, V, const B: usize> {
+    entries: Vec<(K, V)>,
+    next: Option<Box<Node<K, V, B>>>,
+}
+
+impl<K, V, const B: usize> Node<K, V, B> {
+    const MIN: usize = B / 2;
+
+    fn new(key: K, value: V) -> Box<Self> {
+        assert!(B >= 2, "the capacity of the node should be at least 2");
+
+        let mut entries = Vec::with_capacity(B);
+        entries.push((key, value));
+
+        Box::new(Self {
+            entries,
+            next: None,
+        })
+    }
+
+    /// fill the node from the next node if it has less than B / 2 (key, value)s
+    fn rebalance(&mut self) {
+        if self.entries.len() >= Self::MIN {
+            return;
+        }
+
+        let next = match self.next.as_mut() {
+            Some(next) => next,
+            None => return,
+        };
+
+        if self.entries.len() + next.entries.len() <= B {
+            // merge the next into this
+            let mut next = self.next.take().unwrap();
+            self.entries.append(&mut next.entries);
+            self.next = next.next.take();
+        } else {
+            // borrow from the front of the next
+            let count = Self::MIN - self.entries.len();
+            self.entries.extend(next.entries.drain(..count));
+        }
+    }
+}
+
+impl<K, V, const B: usize> UnrolledLinkedList<K, V, B> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// get the iterator of (key, value)s in the order of the list
+    pub fn iter(&self) -> Iter<'_, K, V, B> {
+        Iter {
+            node: self.head.as_deref(),
+            index: 0,
+        }
+    }
+
+    /// get the iterator of (key, mutable value)s in the order of the list
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V, B> {
+        IterMut {
+            current: self.head.as_deref_mut().map(Self::split_node),
+        }
+    }
+
+    fn split_node(node: &mut Node<K, V, B>) -> NodeIterMut<'_, K, V, B> {
+        (node.entries.iter_mut(), node.next.as_deref_mut())
+    }
+}
+
+impl<K, V, const B: usize> SequentialMap<K, V> for UnrolledLinkedList<K, V, B>
+where
+    K: Eq + Clone,
+{
+    fn new() -> Self {
+        Self { head: None, len: 0 }
+    }
+
+    fn insert(&mut self, key: &K, value: V) -> Result<(), V> {
+        if self.lookup(key).is_some() {
+            return Err(value);
+        }
+
+        let mut current = &mut self.head;
+
+        while let Some(node) = current {
+            if node.next.is_none() && node.entries.len() < B {
+                node.entries.push((key.clone(), value));
+                self.len += 1;
+                return Ok(());
+            }
+
+            current = &mut node.next;
+        }
+
+        *current = Some(Node::new(key.clone(), value));
+        self.len += 1;
+        Ok(())
+    }
+
+    fn lookup(&self, key: &K) -> Option<&V> {
+        self.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.iter_mut().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    fn remove(&mut self, key: &K) -> Result<V, ()> {
+        let mut current = &mut self.head;
+
+        loop {
+            let node = current.as_mut().ok_or(())?;
+
+            if let Some(index) = node.entries.iter().position(|(k, _)| k == key) {
+                let (_, value) = node.entries.remove(index);
+                self.len -= 1;
+
+                if node.entries.is_empty() {
+                    // unlink the empty node
+                    let next = node.next.take();
+                    *current = next;
+                } else {
+                    node.rebalance();
+                }
+
+                return Ok(value);
+            }
+
+            current = &mut current.as_mut().unwrap().next;
+        }
+    }
+}
+
+impl<K, V, const B: usize> Drop for UnrolledLinkedList<K, V, B> {
+    fn drop(&mut self) {
+        let mut node = self.head.take();
+
+        while let Some(mut inside) = node {
+            node = inside.next.take();
+        }
+    }
+}
+
+pub struct Iter<'a, K, V, const B: usize> {
+    node: Option<&'a Node<K, V, B>>,
+    index: usize,
+}
+
+impl<'a, K, V, const B: usize> Iterator for Iter<'a, K, V, B> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = self.node?;
+
+            if let Some((key, value)) = node.entries.get(self.index) {
+                self.index += 1;
+                return Some((key, value));
+            }
+
+            self.node = node.next.as_deref();
+            self.index = 0;
+        }
+    }
+}
+
+type NodeIterMut<'a, K, V, const B: usize> = (
+    std::slice::IterMut<'a, (K, V)>,
+    Option<&'a mut Node<K, V, B>>,
+);
+
+pub struct IterMut<'a, K, V, const B: usize> {
+    current: Option<NodeIterMut<'a, K, V, B>>,
+}
+
+impl<'a, K, V, const B: usize> Iterator for IterMut<'a, K, V, B> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (entries, next) = self.current.as_mut()?;
+
+            if let Some((key, value)) = entries.next() {
+                return Some((key, value));
+            }
+
+            self.current = mem::take(next).map(UnrolledLinkedList::split_node);
+        }
+    }
+}
+
+pub struct IntoIter<K, V, const B: usize> {
+    list: UnrolledLinkedList<K, V, B>,
+    entries: std::vec::IntoIter<(K, V)>,
+}
+
+impl<K, V, const B: usize> Iterator for IntoIter<K, V, B> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.entries.next() {
+                self.list.len -= 1;
+                return Some(entry);
+            }
+
+            let mut node = self.list.head.take()?;
+            self.list.head = node.next.take();
+            self.entries = mem::take(&mut node.entries).into_iter();
+        }
+    }
+}
+
+impl<K, V, const B: usize> IntoIterator for UnrolledLinkedList<K, V, B> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, B>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            list: self,
+            entries: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl<'a, K, V, const B: usize> IntoIterator for &'a UnrolledLinkedList<K, V, B> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, B>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, const B: usize> IntoIterator for &'a mut UnrolledLinkedList<K, V, B> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V, B>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
@@ -1,3 +1,7 @@
+mod fclock;
+
+pub use fclock::FCMap;
+
 pub trait SequentialMap<K: Eq, V> {
     fn new() -> Self;
 
@@ -13,6 +17,12 @@ pub trait SequentialMap<K: Eq, V> {
     /// If fail, return None.
     fn lookup(&self, key: &K) -> Option<&V>;
 
+    /// Lookup (key, value) from the map with the key.
+    ///
+    /// If success, return the mutable reference of the value.
+    /// If fail, return None.
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V>;
+
     /// Remove (key, value) from the map with the key.
     ///
     /// If success, return Ok(value) which is inserted before.
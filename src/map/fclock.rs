@@ -0,0 +1,156 @@
+use std::{hint::unreachable_unchecked, marker::PhantomData};
+
+use crossbeam_epoch::pin;
+
+use crate::lock::{
+    fclock::{FCLock, FlatCombining},
+    RawSimpleLock,
+};
+
+use super::{ConcurrentMap, SequentialMap};
+
+#[derive(Debug, PartialEq)]
+enum MapOp<K, V> {
+    InsertRequest(K, V),
+    InsertResponse(Result<(), V>),
+    GetRequest(K),
+    GetResponse(Option<V>),
+    RemoveRequest(K),
+    RemoveResponse(Result<V, ()>),
+}
+
+// The operation is handed over between the publisher and the combiner, and never accessed by both at once.
+unsafe impl<K: Send, V: Send> Send for MapOp<K, V> {}
+unsafe impl<K: Send, V: Send> Sync for MapOp<K, V> {}
+
+impl<K: Eq, V: Clone, M: SequentialMap<K, V>> FlatCombining<MapOp<K, V>> for M {
+    fn apply(&mut self, operation: MapOp<K, V>) -> MapOp<K, V> {
+        match operation {
+            MapOp::InsertRequest(key, value) => MapOp::InsertResponse(self.insert(&key, value)),
+            MapOp::GetRequest(key) => MapOp::GetResponse(self.lookup(&key).cloned()),
+            MapOp::RemoveRequest(key) => MapOp::RemoveResponse(self.remove(&key)),
+            _ => unreachable!("The response cannot be applied."),
+        }
+    }
+}
+
+/// concurrent map from the sequential map by flat combining
+///
+/// The threads publish the operations, and the combiner that holds the lock applies them on the sequential map.
+/// The lookup gets the copy of the value from the combiner, so it needs V: Clone.
+///
+/// The keys and the values are sent to the combiner, so they should be Send.
+///
+/// ```compile_fail
+/// use std::rc::Rc;
+///
+/// use cds::{avltree::AVLTree, lock::RawSpinLock, map::FCMap};
+///
+/// fn assert_sync<T: Sync>() {}
+///
+/// assert_sync::<FCMap<Rc<i32>, Rc<i32>, RawSpinLock, AVLTree<Rc<i32>, Rc<i32>>>>();
+/// ```
+pub struct FCMap<K, V, L: RawSimpleLock, M: SequentialMap<K, V>>
+where
+    K: Eq + Send,
+    V: Send,
+{
+    map: FCLock<MapOp<K, V>, L>,
+    _marker: PhantomData<M>,
+}
+
+// The sequential map is accessed only by the combiner which holds the lock.
+unsafe impl<K, V, L, M> Send for FCMap<K, V, L, M>
+where
+    K: Eq + Send,
+    V: Send,
+    L: RawSimpleLock,
+    M: SequentialMap<K, V> + Send,
+{
+}
+
+unsafe impl<K, V, L, M> Sync for FCMap<K, V, L, M>
+where
+    K: Eq + Send,
+    V: Send,
+    L: RawSimpleLock,
+    M: SequentialMap<K, V> + Send,
+{
+}
+
+impl<K, V, L, M> FCMap<K, V, L, M>
+where
+    K: Eq + Send,
+    V: Send,
+    L: RawSimpleLock,
+    M: SequentialMap<K, V>,
+{
+    fn operate(&self, operation: MapOp<K, V>) -> MapOp<K, V> {
+        let guard = pin();
+
+        let record = self.map.acquire_record(&guard);
+        let record_ref = unsafe { record.deref() };
+
+        record_ref.set(operation);
+
+        self.map.try_combine(record, &guard);
+
+        record_ref.get_operation(&guard)
+    }
+
+    #[cfg(feature = "concurrent_stat")]
+    pub fn print_stat(&self) {
+        self.map.print_stat();
+    }
+}
+
+impl<K, V, L, M> ConcurrentMap<K, V> for FCMap<K, V, L, M>
+where
+    K: 'static + Eq + Clone + Send,
+    V: 'static + Clone + Send,
+    L: RawSimpleLock,
+    M: 'static + SequentialMap<K, V>,
+{
+    fn new() -> Self {
+        Self {
+            map: FCLock::new(M::new()),
+            _marker: PhantomData,
+        }
+    }
+
+    fn insert(&self, key: &K, value: V) -> Result<(), V> {
+        if let MapOp::InsertResponse(result) =
+            self.operate(MapOp::InsertRequest(key.clone(), value))
+        {
+            result
+        } else {
+            unsafe { unreachable_unchecked() }
+        }
+    }
+
+    fn lookup<F, R>(&self, key: &K, f: F) -> R
+    where
+        F: FnOnce(Option<&V>) -> R,
+    {
+        f(self.get(key).as_ref())
+    }
+
+    fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        if let MapOp::GetResponse(value) = self.operate(MapOp::GetRequest(key.clone())) {
+            value
+        } else {
+            unsafe { unreachable_unchecked() }
+        }
+    }
+
+    fn remove(&self, key: &K) -> Result<V, ()> {
+        if let MapOp::RemoveResponse(result) = self.operate(MapOp::RemoveRequest(key.clone())) {
+            result
+        } else {
+            unsafe { unreachable_unchecked() }
+        }
+    }
+}
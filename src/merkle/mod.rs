@@ -0,0 +1,202 @@
+/*
+ Refer to
+ Ralph C. Merkle, "A Digital Signature Based on a Conventional Encryption Function", 1987
+ RFC 6962, "Certificate Transparency", 2013
+ RFC 9162, "Certificate Transparency Version 2.0", 2021
+*/
+
+use std::marker::PhantomData;
+
+/// the prefix of the data of the leaf, which separates the hashes of the leaves from those of the nodes
+const LEAF_PREFIX: u8 = 0x00;
+
+/// the prefix of the hashes of the children
+const NODE_PREFIX: u8 = 0x01;
+
+/// the hash function of the Merkle tree
+pub trait Hasher {
+    type Hash: Clone + Eq + AsRef<[u8]>;
+
+    /// hash the concatenation of the parts
+    fn digest(parts: &[&[u8]]) -> Self::Hash;
+
+    /// hash the data of the leaf with the leaf prefix
+    fn leaf(data: &[u8]) -> Self::Hash {
+        Self::digest(&[&[LEAF_PREFIX], data])
+    }
+
+    /// hash the children with the node prefix
+    fn node(left: &Self::Hash, right: &Self::Hash) -> Self::Hash {
+        Self::digest(&[&[NODE_PREFIX], left.as_ref(), right.as_ref()])
+    }
+}
+
+/// Merkle tree over the leaves, which are appended one by one
+///
+/// Each level keeps the hashes of its nodes. The last node of the level without the sibling is promoted to the
+/// parent as it is, so the tree has the shape of RFC 6962 and append only renews the O(log n) nodes on the right edge.
+pub struct MerkleTree<H: Hasher> {
+    levels: Vec<Vec<H::Hash>>, // from the leaves to the root
+    _marker: PhantomData<H>,
+}
+
+/// inclusion proof of the index-th leaf in the tree of the size, which has the siblings from the leaf to the root
+pub struct Proof<H: Hasher> {
+    index: usize,
+    tree_size: usize,
+    path: Vec<H::Hash>,
+    _marker: PhantomData<H>,
+}
+
+impl<H: Hasher> Default for MerkleTree<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: Hasher> MerkleTree<H> {
+    pub fn new() -> Self {
+        Self {
+            levels: vec![Vec::new()],
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    /// append the leaf of the data, hashed with the leaf prefix
+    pub fn append(&mut self, data: &[u8]) {
+        self.append_leaf_hash(H::leaf(data));
+    }
+
+    /// append the leaf hash computed by the caller, e.g. by `Hasher::leaf`, then renew its ancestors
+    pub fn append_leaf_hash(&mut self, hash: H::Hash) {
+        self.levels[0].push(hash);
+
+        let mut index = self.len() - 1;
+        let mut level = 0;
+
+        while self.levels[level].len() > 1 {
+            let nodes = &self.levels[level];
+            let parent = if index % 2 == 1 {
+                H::node(&nodes[index - 1], &nodes[index])
+            } else {
+                nodes[index].clone()
+            };
+
+            if level + 1 == self.levels.len() {
+                self.levels.push(Vec::new());
+            }
+
+            let parents = &mut self.levels[level + 1];
+            index /= 2;
+
+            if index == parents.len() {
+                parents.push(parent);
+            } else {
+                parents[index] = parent;
+            }
+
+            level += 1;
+        }
+    }
+
+    /// get the hash of the root, or None if the tree is empty
+    pub fn root(&self) -> Option<&H::Hash> {
+        self.levels.last().unwrap().first()
+    }
+
+    /// get the hash of the index-th leaf
+    pub fn leaf(&self, index: usize) -> Option<&H::Hash> {
+        self.levels[0].get(index)
+    }
+
+    /// get the inclusion proof of the index-th leaf for the current root
+    pub fn proof(&self, index: usize) -> Option<Proof<H>> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let mut path = Vec::new();
+        let mut current = index;
+
+        for nodes in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = nodes.get(current ^ 1) {
+                path.push(sibling.clone());
+            }
+
+            current /= 2;
+        }
+
+        Some(Proof::new(index, self.len(), path))
+    }
+}
+
+impl<H: Hasher> Proof<H> {
+    pub fn new(index: usize, tree_size: usize, path: Vec<H::Hash>) -> Self {
+        Self {
+            index,
+            tree_size,
+            path,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn tree_size(&self) -> usize {
+        self.tree_size
+    }
+
+    pub fn path(&self) -> &[H::Hash] {
+        &self.path
+    }
+
+    /// check whether the data is the index-th leaf of the tree of the root
+    pub fn verify(&self, data: &[u8], root: &H::Hash) -> bool {
+        self.verify_leaf_hash(&H::leaf(data), root)
+    }
+
+    /// check whether the leaf hash is of the index-th leaf of the tree of the root(RFC 9162 2.1.3.2)
+    ///
+    /// The index and the tree size decide the side of each sibling and the length of the path.
+    pub fn verify_leaf_hash(&self, leaf: &H::Hash, root: &H::Hash) -> bool {
+        if self.index >= self.tree_size {
+            return false;
+        }
+
+        let (mut index, mut last) = (self.index, self.tree_size - 1);
+        let mut hash = leaf.clone();
+
+        for sibling in &self.path {
+            if last == 0 {
+                return false;
+            }
+
+            if index % 2 == 1 || index == last {
+                hash = H::node(sibling, &hash);
+
+                // skip the levels where the node is promoted without the sibling
+                while index % 2 == 0 && index != 0 {
+                    index >>= 1;
+                    last >>= 1;
+                }
+            } else {
+                hash = H::node(&hash, sibling);
+            }
+
+            index >>= 1;
+            last >>= 1;
+        }
+
+        last == 0 && hash == *root
+    }
+}
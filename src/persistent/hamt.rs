@@ -0,0 +1,391 @@
+/*
+ Refer to
+ Phil Bagwell, "Ideal Hash Trees", 2001
+*/
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hash, Hasher},
+    slice,
+    sync::Arc,
+};
+
+const BITS: u32 = 5; // the bits of the hash for each level
+const MASK: u64 = (1 << BITS) - 1;
+
+/// persistent hash map as the hash array mapped trie(HAMT) whose versions share the untouched nodes
+///
+/// Each branch has the bitmap of its children for the next 5 bits of the hash, and keeps only the existing children.
+/// Insert and remove copy only the nodes on the path from the root, and return a new version.
+/// The keys whose whole hashes are the same are kept in a collision node.
+pub struct HashMap<K, V, S = RandomState> {
+    root: Arc<Node<K, V>>,
+    len: usize,
+    hasher: S,
+}
+
+enum Node<K, V> {
+    Branch {
+        bitmap: u32,
+        children: Vec<Arc<Node<K, V>>>,
+    },
+    Leaf {
+        hash: u64,
+        key: K,
+        value: V,
+    },
+    Collision {
+        hash: u64,
+        entries: Vec<(K, V)>,
+    },
+}
+
+/// get the index of the child for the hash on the level of the shift, and the bit of it on the bitmap
+fn bit(hash: u64, shift: u32) -> u32 {
+    1 << (hash >> shift & MASK)
+}
+
+/// get the position of the child of the bit in the children
+fn position(bitmap: u32, bit: u32) -> usize {
+    (bitmap & (bit - 1)).count_ones() as usize
+}
+
+impl<K, V> Node<K, V> {
+    fn empty() -> Arc<Self> {
+        Arc::new(Node::Branch {
+            bitmap: 0,
+            children: Vec::new(),
+        })
+    }
+
+    /// get the hash of the leaf or the collision
+    fn hash(&self) -> u64 {
+        match self {
+            Node::Leaf { hash, .. } | Node::Collision { hash, .. } => *hash,
+            Node::Branch { .. } => unreachable!("the branch has no hash"),
+        }
+    }
+
+    /// make the branch of the two nodes(leaf or collision) of different hashes from the level of the shift
+    fn merge(a: Arc<Self>, b: Arc<Self>, shift: u32) -> Arc<Self> {
+        let (a_bit, b_bit) = (bit(a.hash(), shift), bit(b.hash(), shift));
+
+        let children = if a_bit == b_bit {
+            vec![Node::merge(a, b, shift + BITS)]
+        } else if a_bit < b_bit {
+            vec![a, b]
+        } else {
+            vec![b, a]
+        };
+
+        Arc::new(Node::Branch {
+            bitmap: a_bit | b_bit,
+            children,
+        })
+    }
+}
+
+impl<K: Eq + Clone, V: Clone> Node<K, V> {
+    fn lookup(&self, hash: u64, key: &K) -> Option<&V> {
+        let mut node = self;
+        let mut shift = 0;
+
+        loop {
+            match node {
+                Node::Branch { bitmap, children } => {
+                    let bit = bit(hash, shift);
+
+                    if bitmap & bit == 0 {
+                        return None;
+                    }
+
+                    node = &children[position(*bitmap, bit)];
+                    shift += BITS;
+                }
+                Node::Leaf {
+                    hash: h,
+                    key: k,
+                    value,
+                } => return (*h == hash && k == key).then_some(value),
+                Node::Collision { hash: h, entries } => {
+                    if *h != hash {
+                        return None;
+                    }
+
+                    return entries.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+                }
+            }
+        }
+    }
+
+    fn insert(node: &Arc<Self>, shift: u32, hash: u64, key: &K, value: V) -> Result<Arc<Self>, V> {
+        match node.as_ref() {
+            Node::Branch { bitmap, children } => {
+                let bit = bit(hash, shift);
+                let position = position(*bitmap, bit);
+                let mut children = children.clone();
+
+                if bitmap & bit == 0 {
+                    let leaf = Arc::new(Node::Leaf {
+                        hash,
+                        key: key.clone(),
+                        value,
+                    });
+                    children.insert(position, leaf);
+                } else {
+                    children[position] =
+                        Node::insert(&children[position], shift + BITS, hash, key, value)?;
+                }
+
+                Ok(Arc::new(Node::Branch {
+                    bitmap: bitmap | bit,
+                    children,
+                }))
+            }
+            Node::Leaf {
+                hash: h,
+                key: k,
+                value: v,
+            } => {
+                if *h != hash {
+                    let leaf = Arc::new(Node::Leaf {
+                        hash,
+                        key: key.clone(),
+                        value,
+                    });
+                    return Ok(Node::merge(node.clone(), leaf, shift));
+                }
+
+                if k == key {
+                    return Err(value);
+                }
+
+                Ok(Arc::new(Node::Collision {
+                    hash,
+                    entries: vec![(k.clone(), v.clone()), (key.clone(), value)],
+                }))
+            }
+            Node::Collision { hash: h, entries } => {
+                if *h != hash {
+                    let leaf = Arc::new(Node::Leaf {
+                        hash,
+                        key: key.clone(),
+                        value,
+                    });
+                    return Ok(Node::merge(node.clone(), leaf, shift));
+                }
+
+                if entries.iter().any(|(k, _)| k == key) {
+                    return Err(value);
+                }
+
+                let mut entries = entries.clone();
+                entries.push((key.clone(), value));
+
+                Ok(Arc::new(Node::Collision { hash, entries }))
+            }
+        }
+    }
+
+    /// remove the key, then return the new node, which is None if it becomes empty, and the value
+    fn remove(
+        node: &Arc<Self>,
+        shift: u32,
+        hash: u64,
+        key: &K,
+    ) -> Result<(Option<Arc<Self>>, V), ()> {
+        match node.as_ref() {
+            Node::Branch { bitmap, children } => {
+                let bit = bit(hash, shift);
+
+                if bitmap & bit == 0 {
+                    return Err(());
+                }
+
+                let position = position(*bitmap, bit);
+                let (child, value) = Node::remove(&children[position], shift + BITS, hash, key)?;
+                let mut children = children.clone();
+
+                let bitmap = match child {
+                    Some(child) => {
+                        children[position] = child;
+                        *bitmap
+                    }
+                    None => {
+                        children.remove(position);
+                        bitmap & !bit
+                    }
+                };
+
+                // pull up the only leaf or collision, except on the root
+                if shift > 0 && children.len() <= 1 {
+                    match children.pop() {
+                        None => return Ok((None, value)),
+                        Some(child) if !matches!(*child, Node::Branch { .. }) => {
+                            return Ok((Some(child), value))
+                        }
+                        Some(child) => children.push(child),
+                    }
+                }
+
+                Ok((Some(Arc::new(Node::Branch { bitmap, children })), value))
+            }
+            Node::Leaf {
+                hash: h,
+                key: k,
+                value,
+            } => {
+                if *h == hash && k == key {
+                    Ok((None, value.clone()))
+                } else {
+                    Err(())
+                }
+            }
+            Node::Collision { hash: h, entries } => {
+                let index = match entries.iter().position(|(k, _)| k == key) {
+                    Some(index) if *h == hash => index,
+                    _ => return Err(()),
+                };
+
+                let mut entries = entries.clone();
+                let (_, value) = entries.remove(index);
+
+                let node = if entries.len() == 1 {
+                    let (key, value) = entries.pop().unwrap();
+                    Node::Leaf { hash, key, value }
+                } else {
+                    Node::Collision { hash, entries }
+                };
+
+                Ok((Some(Arc::new(node)), value))
+            }
+        }
+    }
+}
+
+impl<K, V, S: Clone> Clone for HashMap<K, V, S> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            len: self.len,
+            hasher: self.hasher.clone(),
+        }
+    }
+}
+
+impl<K, V, S: Default> Default for HashMap<K, V, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, V> HashMap<K, V> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V, S> HashMap<K, V, S> {
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            root: Node::empty(),
+            len: 0,
+            hasher,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// get the iterator of (key, value)s in the order of the hash
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            stack: vec![slice::from_ref(&self.root).iter()],
+            entries: [].iter(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone, S: BuildHasher + Clone> HashMap<K, V, S> {
+    fn hash(&self, key: &K) -> u64 {
+        let mut hasher = self.hasher.build_hasher();
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Insert (key, value) into the new version of the map.
+    ///
+    /// If success, return Ok(new version).
+    /// If fail, return Err(value).
+    pub fn insert(&self, key: &K, value: V) -> Result<Self, V> {
+        let root = Node::insert(&self.root, 0, self.hash(key), key, value)?;
+
+        Ok(Self {
+            root,
+            len: self.len + 1,
+            hasher: self.hasher.clone(),
+        })
+    }
+
+    pub fn lookup(&self, key: &K) -> Option<&V> {
+        self.root.lookup(self.hash(key), key)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.lookup(key).is_some()
+    }
+
+    /// Remove (key, value) from the new version of the map.
+    ///
+    /// If success, return Ok((new version, value)).
+    /// If fail, return Err(()).
+    pub fn remove(&self, key: &K) -> Result<(Self, V), ()> {
+        let (root, value) = Node::remove(&self.root, 0, self.hash(key), key)?;
+
+        Ok((
+            Self {
+                root: root.unwrap_or_else(Node::empty),
+                len: self.len - 1,
+                hasher: self.hasher.clone(),
+            },
+            value,
+        ))
+    }
+}
+
+/// the iterator of (key, value)s by DFS
+pub struct Iter<'a, K, V> {
+    stack: Vec<slice::Iter<'a, Arc<Node<K, V>>>>,
+    entries: slice::Iter<'a, (K, V)>, // the rest of the current collision
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, value)) = self.entries.next() {
+                return Some((key, value));
+            }
+
+            let node = match self.stack.last_mut()?.next() {
+                Some(node) => node,
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+
+            match node.as_ref() {
+                Node::Branch { children, .. } => self.stack.push(children.iter()),
+                Node::Leaf { key, value, .. } => return Some((key, value)),
+                Node::Collision { entries, .. } => self.entries = entries.iter(),
+            }
+        }
+    }
+}
@@ -0,0 +1,3 @@
+mod hamt;
+
+pub use hamt::HashMap;
@@ -0,0 +1,156 @@
+use std::{
+    sync::{Condvar, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use super::{Queue, SequentialQueue};
+
+/// bounded queue whose push waits while it is full, and pop waits while it is empty
+///
+/// The producers wait on `not_full`, and the consumers wait on `not_empty`.
+/// Each side notifies one waiter of the other side after it changes the queue.
+pub struct BlockingQueue<V> {
+    inner: Mutex<Inner<V>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+}
+
+unsafe impl<V: Send> Send for BlockingQueue<V> {}
+unsafe impl<V: Send> Sync for BlockingQueue<V> {}
+
+struct Inner<V> {
+    queue: Queue<V>,
+    len: usize,
+}
+
+impl<V> Inner<V> {
+    fn push(&mut self, value: V) {
+        self.queue.push(value);
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> V {
+        self.len -= 1;
+        self.queue.pop().unwrap()
+    }
+}
+
+impl<V> BlockingQueue<V> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "the capacity should be positive");
+
+        Self {
+            inner: Mutex::new(Inner {
+                queue: Queue::new(),
+                len: 0,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner<V>> {
+        self.inner.lock().unwrap()
+    }
+
+    /// push the value into the locked queue, and wake up a waiting consumer
+    fn push_locked(&self, mut inner: MutexGuard<'_, Inner<V>>, value: V) {
+        inner.push(value);
+        drop(inner);
+        self.not_empty.notify_one();
+    }
+
+    /// pop the value from the locked queue, and wake up a waiting producer
+    fn pop_locked(&self, mut inner: MutexGuard<'_, Inner<V>>) -> V {
+        let value = inner.pop();
+        drop(inner);
+        self.not_full.notify_one();
+        value
+    }
+
+    /// blocking push that waits until the queue has the free slot
+    pub fn push(&self, value: V) {
+        let inner = self
+            .not_full
+            .wait_while(self.lock(), |inner| inner.len == self.capacity)
+            .unwrap();
+
+        self.push_locked(inner, value);
+    }
+
+    /// blocking pop that waits until the queue has the value
+    pub fn pop(&self) -> V {
+        let inner = self
+            .not_empty
+            .wait_while(self.lock(), |inner| inner.len == 0)
+            .unwrap();
+
+        self.pop_locked(inner)
+    }
+
+    /// non-blocking push that returns Err(value) when the queue is full
+    pub fn try_push(&self, value: V) -> Result<(), V> {
+        let inner = self.lock();
+
+        if inner.len == self.capacity {
+            return Err(value);
+        }
+
+        self.push_locked(inner, value);
+        Ok(())
+    }
+
+    /// non-blocking pop that returns None when the queue is empty
+    pub fn try_pop(&self) -> Option<V> {
+        let inner = self.lock();
+
+        if inner.len == 0 {
+            return None;
+        }
+
+        Some(self.pop_locked(inner))
+    }
+
+    /// push that waits for the free slot at most the timeout, and returns Err(value) on the timeout
+    pub fn push_timeout(&self, value: V, timeout: Duration) -> Result<(), V> {
+        let (inner, result) = self
+            .not_full
+            .wait_timeout_while(self.lock(), timeout, |inner| inner.len == self.capacity)
+            .unwrap();
+
+        if result.timed_out() {
+            return Err(value);
+        }
+
+        self.push_locked(inner, value);
+        Ok(())
+    }
+
+    /// pop that waits for the value at most the timeout, and returns None on the timeout
+    pub fn pop_timeout(&self, timeout: Duration) -> Option<V> {
+        let (inner, result) = self
+            .not_empty
+            .wait_timeout_while(self.lock(), timeout, |inner| inner.len == 0)
+            .unwrap();
+
+        if result.timed_out() {
+            return None;
+        }
+
+        Some(self.pop_locked(inner))
+    }
+}
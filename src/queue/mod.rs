@@ -1,14 +1,22 @@
+mod blocking;
 mod fclock;
 mod lockfree;
+mod mpmc;
+mod mpsc;
 mod mutex;
 mod spinlock;
+mod spsc;
 
+pub use blocking::BlockingQueue;
 pub use fclock::FCQueue;
 pub use lockfree::MSQueue;
+pub use mpmc::MPMCQueue;
+pub use mpsc::{MPSCConsumer, MPSCProducer, MPSCQueue};
 pub use mutex::MutexQueue;
 pub use mutex::TwoMutexQueue;
 pub use spinlock::SpinLockQueue;
 pub use spinlock::TwoSpinLockQueue;
+pub use spsc::{SPSCConsumer, SPSCProducer, SPSCQueue};
 
 use std::{fmt::Debug, mem, mem::MaybeUninit, ptr, ptr::NonNull};
 
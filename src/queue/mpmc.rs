@@ -0,0 +1,156 @@
+/*
+ Refer to
+ https://www.1024cores.net/home/lock-free-algorithms/queues/bounded-mpmc-queue
+*/
+
+use std::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crossbeam_utils::{Backoff, CachePadded};
+
+/// bounded queue for multiple producers and multiple consumers(Vyukov)
+///
+/// Each slot has the sequence number which tells whose turn it is.
+/// If sequence == pos, the slot is empty for the push at pos.
+/// If sequence == pos + 1, the slot is filled for the pop at pos.
+/// After popping, sequence becomes pos + capacity for the next round.
+pub struct MPMCQueue<V> {
+    buffer: Box<[Slot<V>]>,
+    enqueue_pos: CachePadded<AtomicUsize>,
+    dequeue_pos: CachePadded<AtomicUsize>,
+}
+
+unsafe impl<V: Send> Send for MPMCQueue<V> {}
+unsafe impl<V: Send> Sync for MPMCQueue<V> {}
+
+struct Slot<V> {
+    sequence: AtomicUsize,
+    value: UnsafeCell<MaybeUninit<V>>,
+}
+
+impl<V> MPMCQueue<V> {
+    /// make the queue with at least the given capacity
+    ///
+    /// The capacity is rounded up to 2 since a full slot's sequence (pos + 1) must not be equal to
+    /// the empty sequence of the next round (pos + capacity).
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "the capacity should be positive");
+        let capacity = capacity.max(2);
+
+        Self {
+            buffer: (0..capacity)
+                .map(|i| Slot {
+                    sequence: AtomicUsize::new(i),
+                    value: UnsafeCell::new(MaybeUninit::uninit()),
+                })
+                .collect(),
+            enqueue_pos: CachePadded::new(AtomicUsize::new(0)),
+            dequeue_pos: CachePadded::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// non-blocking push that returns Err(value) when the queue is observed as full
+    pub fn try_push(&self, value: V) -> Result<(), V> {
+        let mut pos = self.enqueue_pos.load(Ordering::Relaxed);
+
+        loop {
+            let slot = &self.buffer[pos % self.capacity()];
+            let sequence = slot.sequence.load(Ordering::Acquire);
+            let diff = sequence as isize - pos as isize;
+
+            if diff == 0 {
+                match self.enqueue_pos.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        unsafe { (*slot.value.get()).write(value) };
+                        slot.sequence.store(pos.wrapping_add(1), Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(current) => pos = current,
+                }
+            } else if diff < 0 {
+                // the slot is not popped yet from the last round
+                return Err(value);
+            } else {
+                pos = self.enqueue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// non-blocking pop that returns None when the queue is observed as empty
+    pub fn try_pop(&self) -> Option<V> {
+        let mut pos = self.dequeue_pos.load(Ordering::Relaxed);
+
+        loop {
+            let slot = &self.buffer[pos % self.capacity()];
+            let sequence = slot.sequence.load(Ordering::Acquire);
+            let diff = sequence as isize - pos.wrapping_add(1) as isize;
+
+            if diff == 0 {
+                match self.dequeue_pos.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        let value = unsafe { (*slot.value.get()).assume_init_read() };
+                        slot.sequence
+                            .store(pos.wrapping_add(self.capacity()), Ordering::Release);
+                        return Some(value);
+                    }
+                    Err(current) => pos = current,
+                }
+            } else if diff < 0 {
+                // the slot is not pushed yet on this round
+                return None;
+            } else {
+                pos = self.dequeue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// blocking push that waits until the queue has the free slot
+    pub fn push(&self, mut value: V) {
+        let backoff = Backoff::new();
+
+        loop {
+            match self.try_push(value) {
+                Ok(()) => return,
+                Err(v) => value = v,
+            }
+
+            backoff.snooze();
+        }
+    }
+
+    /// blocking pop that waits until the queue has the value
+    pub fn pop(&self) -> V {
+        let backoff = Backoff::new();
+
+        loop {
+            if let Some(value) = self.try_pop() {
+                return value;
+            }
+
+            backoff.snooze();
+        }
+    }
+}
+
+impl<V> Drop for MPMCQueue<V> {
+    fn drop(&mut self) {
+        while self.try_pop().is_some() {}
+    }
+}
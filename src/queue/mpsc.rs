@@ -0,0 +1,140 @@
+/*
+ Refer to
+ https://www.1024cores.net/home/lock-free-algorithms/queues/non-intrusive-mpsc-node-based-queue
+*/
+
+use std::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    ptr,
+    sync::{
+        atomic::{AtomicPtr, Ordering},
+        Arc,
+    },
+};
+
+use crossbeam_utils::{Backoff, CachePadded};
+
+/// unbounded queue for multiple producers and a single consumer(Vyukov)
+///
+/// The producers only swap the head, then link the previous head to the new node.
+/// The consumer owns the tail, so it reads the tail without atomics and frees the popped node by itself.
+pub struct MPSCQueue<V> {
+    head: CachePadded<AtomicPtr<Node<V>>>, // the last pushed node, swapped by the producers
+    tail: UnsafeCell<*mut Node<V>>, // the dummy node whose next is the first value, owned by the consumer
+}
+
+unsafe impl<V: Send> Send for MPSCQueue<V> {}
+unsafe impl<V: Send> Sync for MPSCQueue<V> {}
+
+struct Node<V> {
+    value: MaybeUninit<V>,
+    next: AtomicPtr<Node<V>>,
+}
+
+impl<V> Node<V> {
+    fn new(value: MaybeUninit<V>) -> *mut Self {
+        Box::into_raw(Box::new(Self {
+            value,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }))
+    }
+}
+
+/// the producer of MPSCQueue, which can be cloned for multiple producers
+pub struct MPSCProducer<V> {
+    queue: Arc<MPSCQueue<V>>,
+}
+
+/// the unique consumer of MPSCQueue
+pub struct MPSCConsumer<V> {
+    queue: Arc<MPSCQueue<V>>,
+}
+
+impl<V> MPSCQueue<V> {
+    /// make the queue, and return its producer and consumer
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> (MPSCProducer<V>, MPSCConsumer<V>) {
+        let dummy = Node::new(MaybeUninit::uninit());
+
+        let queue = Arc::new(Self {
+            head: CachePadded::new(AtomicPtr::new(dummy)),
+            tail: UnsafeCell::new(dummy),
+        });
+
+        (
+            MPSCProducer {
+                queue: queue.clone(),
+            },
+            MPSCConsumer { queue },
+        )
+    }
+}
+
+impl<V> Drop for MPSCQueue<V> {
+    fn drop(&mut self) {
+        unsafe {
+            let dummy = Box::from_raw(*self.tail.get_mut());
+            let mut current = dummy.next.load(Ordering::Relaxed);
+
+            while !current.is_null() {
+                let mut node = Box::from_raw(current);
+                node.value.assume_init_drop();
+                current = node.next.load(Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+impl<V> Clone for MPSCProducer<V> {
+    fn clone(&self) -> Self {
+        Self {
+            queue: self.queue.clone(),
+        }
+    }
+}
+
+impl<V> MPSCProducer<V> {
+    pub fn push(&self, value: V) {
+        let node = Node::new(MaybeUninit::new(value));
+        let prev = self.queue.head.swap(node, Ordering::AcqRel);
+
+        // until linking, the consumer cannot see the node and the nodes pushed after it
+        unsafe { (*prev).next.store(node, Ordering::Release) };
+    }
+}
+
+impl<V> MPSCConsumer<V> {
+    /// non-blocking pop that can return `None` when the queue is observed as empty
+    ///
+    /// It also returns `None` if the next producer has swapped the head, but not linked its node yet.
+    pub fn try_pop(&mut self) -> Option<V> {
+        unsafe {
+            let tail = *self.queue.tail.get();
+            let next = (*tail).next.load(Ordering::Acquire);
+
+            if next.is_null() {
+                return None;
+            }
+
+            // the next becomes the dummy node
+            *self.queue.tail.get() = next;
+            drop(Box::from_raw(tail));
+
+            Some((*next).value.assume_init_read())
+        }
+    }
+
+    /// blocking pop that waits for the value
+    pub fn pop(&mut self) -> V {
+        let backoff = Backoff::new();
+
+        loop {
+            if let Some(value) = self.try_pop() {
+                return value;
+            }
+
+            backoff.snooze();
+        }
+    }
+}
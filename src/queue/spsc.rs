@@ -0,0 +1,184 @@
+/*
+ Refer to
+ https://rigtorp.se/ringbuffer/
+*/
+
+use std::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use crossbeam_utils::CachePadded;
+
+/// bounded wait-free ring buffer for a single producer and a single consumer
+///
+/// The indices increase monotonically and wrap around the buffer. Each side caches the index of the other side,
+/// so it reads the shared index only when the cached one says the buffer is full or empty.
+pub struct SPSCQueue<V> {
+    buffer: Box<[UnsafeCell<MaybeUninit<V>>]>,
+    head: CachePadded<AtomicUsize>, // the index to pop next, written by the consumer
+    tail: CachePadded<AtomicUsize>, // the index to push next, written by the producer
+}
+
+unsafe impl<V: Send> Send for SPSCQueue<V> {}
+unsafe impl<V: Send> Sync for SPSCQueue<V> {}
+
+pub struct SPSCProducer<V> {
+    queue: Arc<SPSCQueue<V>>,
+    tail: usize,
+    cached_head: usize,
+}
+
+pub struct SPSCConsumer<V> {
+    queue: Arc<SPSCQueue<V>>,
+    head: usize,
+    cached_tail: usize,
+}
+
+impl<V> SPSCQueue<V> {
+    /// make the queue with the capacity, and return its producer and consumer
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(capacity: usize) -> (SPSCProducer<V>, SPSCConsumer<V>) {
+        assert!(capacity > 0, "the capacity should be positive");
+
+        let queue = Arc::new(Self {
+            buffer: (0..capacity)
+                .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+                .collect(),
+            head: CachePadded::new(AtomicUsize::new(0)),
+            tail: CachePadded::new(AtomicUsize::new(0)),
+        });
+
+        (
+            SPSCProducer {
+                queue: queue.clone(),
+                tail: 0,
+                cached_head: 0,
+            },
+            SPSCConsumer {
+                queue,
+                head: 0,
+                cached_tail: 0,
+            },
+        )
+    }
+
+    fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn slot(&self, index: usize) -> *mut MaybeUninit<V> {
+        self.buffer[index % self.capacity()].get()
+    }
+}
+
+impl<V> Drop for SPSCQueue<V> {
+    fn drop(&mut self) {
+        let head = *self.head.get_mut();
+        let tail = *self.tail.get_mut();
+
+        for offset in 0..tail.wrapping_sub(head) {
+            unsafe { (*self.slot(head.wrapping_add(offset))).assume_init_drop() };
+        }
+    }
+}
+
+impl<V> SPSCProducer<V> {
+    pub fn capacity(&self) -> usize {
+        self.queue.capacity()
+    }
+
+    /// get the number of the free slots, reading the head of the consumer only if the cached one is not enough
+    fn free(&mut self, required: usize) -> usize {
+        let mut free = self.capacity() - self.tail.wrapping_sub(self.cached_head);
+
+        if free < required {
+            self.cached_head = self.queue.head.load(Ordering::Acquire);
+            free = self.capacity() - self.tail.wrapping_sub(self.cached_head);
+        }
+
+        free
+    }
+
+    /// push the value, or return Err(value) if the queue is full
+    pub fn push(&mut self, value: V) -> Result<(), V> {
+        if self.free(1) == 0 {
+            return Err(value);
+        }
+
+        unsafe { (*self.queue.slot(self.tail)).write(value) };
+        self.tail = self.tail.wrapping_add(1);
+        self.queue.tail.store(self.tail, Ordering::Release);
+
+        Ok(())
+    }
+
+    /// push the copies of the values as many as possible at once, and return the number of the pushed
+    pub fn push_slice(&mut self, values: &[V]) -> usize
+    where
+        V: Clone,
+    {
+        let count = self.free(values.len()).min(values.len());
+
+        for (offset, value) in values[..count].iter().enumerate() {
+            unsafe {
+                (*self.queue.slot(self.tail.wrapping_add(offset))).write(value.clone());
+            }
+        }
+
+        self.tail = self.tail.wrapping_add(count);
+        self.queue.tail.store(self.tail, Ordering::Release);
+
+        count
+    }
+}
+
+impl<V> SPSCConsumer<V> {
+    pub fn capacity(&self) -> usize {
+        self.queue.capacity()
+    }
+
+    /// get the number of the filled slots, reading the tail of the producer only if the cached one is not enough
+    fn filled(&mut self, required: usize) -> usize {
+        let mut filled = self.cached_tail.wrapping_sub(self.head);
+
+        if filled < required {
+            self.cached_tail = self.queue.tail.load(Ordering::Acquire);
+            filled = self.cached_tail.wrapping_sub(self.head);
+        }
+
+        filled
+    }
+
+    /// pop the value, or return None if the queue is empty
+    pub fn pop(&mut self) -> Option<V> {
+        if self.filled(1) == 0 {
+            return None;
+        }
+
+        let value = unsafe { (*self.queue.slot(self.head)).assume_init_read() };
+        self.head = self.head.wrapping_add(1);
+        self.queue.head.store(self.head, Ordering::Release);
+
+        Some(value)
+    }
+
+    /// pop the values into the buffer as many as possible at once, and return the number of the popped
+    pub fn pop_slice(&mut self, buffer: &mut [V]) -> usize {
+        let count = self.filled(buffer.len()).min(buffer.len());
+
+        for (offset, slot) in buffer[..count].iter_mut().enumerate() {
+            *slot =
+                unsafe { (*self.queue.slot(self.head.wrapping_add(offset))).assume_init_read() };
+        }
+
+        self.head = self.head.wrapping_add(count);
+        self.queue.head.store(self.head, Ordering::Release);
+
+        count
+    }
+}
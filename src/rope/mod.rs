@@ -0,0 +1,493 @@
+use std::{fmt, ops::Range, str, sync::Arc};
+
+/// the maximum bytes of the text in a leaf
+const MAX_LEAF: usize = 1024;
+
+/// text as the height-balanced(AVL) binary tree of chunked strings, indexed by the char
+///
+/// The nodes are immutable and shared by Arc, so insert, remove, and slice only make the new nodes on
+/// O(log n) paths, and the clone of the rope is O(1).
+#[derive(Clone)]
+pub struct Rope {
+    root: Arc<Node>,
+}
+
+struct Node {
+    chars: usize,
+    bytes: usize,
+    newlines: usize,
+    height: usize,
+    kind: Kind,
+}
+
+enum Kind {
+    Leaf(String),
+    Branch(Arc<Node>, Arc<Node>),
+}
+
+/// get the byte offset of the char index in the string
+fn char_to_byte(text: &str, index: usize) -> usize {
+    text.char_indices()
+        .nth(index)
+        .map_or(text.len(), |(byte, _)| byte)
+}
+
+impl Node {
+    fn leaf(text: String) -> Arc<Self> {
+        Arc::new(Self {
+            chars: text.chars().count(),
+            bytes: text.len(),
+            newlines: text.bytes().filter(|&byte| byte == b'\n').count(),
+            height: 0,
+            kind: Kind::Leaf(text),
+        })
+    }
+
+    fn branch(left: Arc<Self>, right: Arc<Self>) -> Arc<Self> {
+        Arc::new(Self {
+            chars: left.chars + right.chars,
+            bytes: left.bytes + right.bytes,
+            newlines: left.newlines + right.newlines,
+            height: left.height.max(right.height) + 1,
+            kind: Kind::Branch(left, right),
+        })
+    }
+
+    /// build the balanced tree of the leaves for the text
+    fn build(text: &str) -> Arc<Self> {
+        if text.len() <= MAX_LEAF {
+            return Self::leaf(text.to_string());
+        }
+
+        // split near the middle on the char boundary
+        let mut mid = text.len() / 2;
+
+        while !text.is_char_boundary(mid) {
+            mid += 1;
+        }
+
+        Self::branch(Self::build(&text[..mid]), Self::build(&text[mid..]))
+    }
+
+    fn children(node: &Arc<Self>) -> (&Arc<Self>, &Arc<Self>) {
+        match &node.kind {
+            Kind::Branch(left, right) => (left, right),
+            Kind::Leaf(_) => unreachable!("the leaf has no children"),
+        }
+    }
+
+    /// make the branch of the two trees whose heights differ by at most 2 with the rotations
+    fn balance(left: Arc<Self>, right: Arc<Self>) -> Arc<Self> {
+        if left.height > right.height + 1 {
+            let (ll, lr) = Self::children(&left);
+
+            if ll.height >= lr.height {
+                Self::branch(ll.clone(), Self::branch(lr.clone(), right))
+            } else {
+                let (lrl, lrr) = Self::children(lr);
+                Self::branch(
+                    Self::branch(ll.clone(), lrl.clone()),
+                    Self::branch(lrr.clone(), right),
+                )
+            }
+        } else if right.height > left.height + 1 {
+            let (rl, rr) = Self::children(&right);
+
+            if rr.height >= rl.height {
+                Self::branch(Self::branch(left, rl.clone()), rr.clone())
+            } else {
+                let (rll, rlr) = Self::children(rl);
+                Self::branch(
+                    Self::branch(left, rll.clone()),
+                    Self::branch(rlr.clone(), rr.clone()),
+                )
+            }
+        } else {
+            Self::branch(left, right)
+        }
+    }
+
+    /// concatenate the two trees along the spine of the higher one
+    fn join(left: Arc<Self>, right: Arc<Self>) -> Arc<Self> {
+        if left.bytes == 0 {
+            return right;
+        }
+
+        if right.bytes == 0 {
+            return left;
+        }
+
+        if left.height > right.height + 1 {
+            let (ll, lr) = Self::children(&left);
+            Self::balance(ll.clone(), Self::join(lr.clone(), right))
+        } else if right.height > left.height + 1 {
+            let (rl, rr) = Self::children(&right);
+            Self::balance(Self::join(left, rl.clone()), rr.clone())
+        } else {
+            Self::meet(left, right)
+        }
+    }
+
+    /// concatenate the two trees of the similar heights, merging the leaves on the seam if they fit in a leaf
+    ///
+    /// Without merging, inserting a char at a time would leave the tiny leaves on the seams.
+    fn meet(left: Arc<Self>, right: Arc<Self>) -> Arc<Self> {
+        if Self::last_leaf(&left).len() + Self::first_leaf(&right).len() > MAX_LEAF {
+            return Self::branch(left, right);
+        }
+
+        let (left, last) = Self::pop_last(&left);
+        let (right, first) = Self::pop_first(&right);
+        let merged = Self::leaf(last.to_string() + first);
+
+        let joined = match left {
+            Some(left) => Self::join(left, merged),
+            None => merged,
+        };
+
+        match right {
+            Some(right) => Self::join(joined, right),
+            None => joined,
+        }
+    }
+
+    fn first_leaf(mut node: &Arc<Self>) -> &str {
+        loop {
+            match &node.kind {
+                Kind::Leaf(text) => return text,
+                Kind::Branch(left, _) => node = left,
+            }
+        }
+    }
+
+    fn last_leaf(mut node: &Arc<Self>) -> &str {
+        loop {
+            match &node.kind {
+                Kind::Leaf(text) => return text,
+                Kind::Branch(_, right) => node = right,
+            }
+        }
+    }
+
+    /// remove the first leaf, then return (the rest, the text of the leaf)
+    fn pop_first(node: &Arc<Self>) -> (Option<Arc<Self>>, &str) {
+        match &node.kind {
+            Kind::Leaf(text) => (None, text),
+            Kind::Branch(left, right) => {
+                let (rest, text) = Self::pop_first(left);
+
+                // the height decreases by at most 1, so the rotations keep the balance
+                let rest = match rest {
+                    Some(rest) => Self::balance(rest, right.clone()),
+                    None => right.clone(),
+                };
+
+                (Some(rest), text)
+            }
+        }
+    }
+
+    /// remove the last leaf, then return (the rest, the text of the leaf)
+    fn pop_last(node: &Arc<Self>) -> (Option<Arc<Self>>, &str) {
+        match &node.kind {
+            Kind::Leaf(text) => (None, text),
+            Kind::Branch(left, right) => {
+                let (rest, text) = Self::pop_last(right);
+
+                let rest = match rest {
+                    Some(rest) => Self::balance(left.clone(), rest),
+                    None => left.clone(),
+                };
+
+                (Some(rest), text)
+            }
+        }
+    }
+
+    /// split the tree into the first `index` chars and the rest
+    fn split(node: &Arc<Self>, index: usize) -> (Arc<Self>, Arc<Self>) {
+        if index == 0 {
+            return (Self::leaf(String::new()), node.clone());
+        }
+
+        if index == node.chars {
+            return (node.clone(), Self::leaf(String::new()));
+        }
+
+        match &node.kind {
+            Kind::Leaf(text) => {
+                let byte = char_to_byte(text, index);
+                (
+                    Self::leaf(text[..byte].to_string()),
+                    Self::leaf(text[byte..].to_string()),
+                )
+            }
+            Kind::Branch(left, right) => {
+                if index <= left.chars {
+                    let (first, second) = Self::split(left, index);
+                    (first, Self::join(second, right.clone()))
+                } else {
+                    let (first, second) = Self::split(right, index - left.chars);
+                    (Self::join(left.clone(), first), second)
+                }
+            }
+        }
+    }
+}
+
+impl Default for Rope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&str> for Rope {
+    fn from(text: &str) -> Self {
+        Self {
+            root: Node::build(text),
+        }
+    }
+}
+
+impl fmt::Display for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.chunks().try_for_each(|chunk| f.write_str(chunk))
+    }
+}
+
+impl fmt::Debug for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_string(), f)
+    }
+}
+
+impl PartialEq for Rope {
+    fn eq(&self, other: &Self) -> bool {
+        self.len_bytes() == other.len_bytes() && self.chars().eq(other.chars())
+    }
+}
+
+impl Eq for Rope {}
+
+impl Rope {
+    pub fn new() -> Self {
+        Self {
+            root: Node::leaf(String::new()),
+        }
+    }
+
+    pub fn len_chars(&self) -> usize {
+        self.root.chars
+    }
+
+    pub fn len_bytes(&self) -> usize {
+        self.root.bytes
+    }
+
+    /// get the number of the lines, which is the number of '\n' + 1
+    pub fn len_lines(&self) -> usize {
+        self.root.newlines + 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.bytes == 0
+    }
+
+    /// insert the text before the char at the index
+    pub fn insert(&mut self, index: usize, text: &str) {
+        assert!(index <= self.len_chars(), "the index is out of range");
+
+        let (first, second) = Node::split(&self.root, index);
+        self.root = Node::join(Node::join(first, Node::build(text)), second);
+    }
+
+    /// remove the chars in the range
+    pub fn remove(&mut self, range: Range<usize>) {
+        assert!(
+            range.start <= range.end && range.end <= self.len_chars(),
+            "the range is out of range"
+        );
+
+        let (first, rest) = Node::split(&self.root, range.start);
+        let (_, second) = Node::split(&rest, range.end - range.start);
+        self.root = Node::join(first, second);
+    }
+
+    /// get the rope of the chars in the range, which shares the nodes with this
+    pub fn slice(&self, range: Range<usize>) -> Rope {
+        assert!(
+            range.start <= range.end && range.end <= self.len_chars(),
+            "the range is out of range"
+        );
+
+        let (_, rest) = Node::split(&self.root, range.start);
+        let (slice, _) = Node::split(&rest, range.end - range.start);
+
+        Self { root: slice }
+    }
+
+    /// append the other rope at the end
+    pub fn append(&mut self, other: Rope) {
+        self.root = Node::join(self.root.clone(), other.root);
+    }
+
+    /// split the rope at the char index, and return the latter
+    pub fn split_off(&mut self, index: usize) -> Rope {
+        assert!(index <= self.len_chars(), "the index is out of range");
+
+        let (first, second) = Node::split(&self.root, index);
+        self.root = first;
+
+        Self { root: second }
+    }
+
+    /// get the char at the index
+    pub fn char(&self, mut index: usize) -> char {
+        assert!(index < self.len_chars(), "the index is out of range");
+
+        let mut node = &self.root;
+
+        loop {
+            match &node.kind {
+                Kind::Leaf(text) => return text.chars().nth(index).unwrap(),
+                Kind::Branch(left, right) => {
+                    if index < left.chars {
+                        node = left;
+                    } else {
+                        index -= left.chars;
+                        node = right;
+                    }
+                }
+            }
+        }
+    }
+
+    /// get the char index of the start of the line
+    pub fn line_to_char(&self, mut line: usize) -> usize {
+        assert!(line < self.len_lines(), "the line is out of range");
+
+        let mut node = &self.root;
+        let mut index = 0;
+
+        loop {
+            match &node.kind {
+                Kind::Leaf(text) => {
+                    if line == 0 {
+                        return index;
+                    }
+
+                    // the char after the `line`th '\n' in the leaf
+                    let (newline, _) = text
+                        .chars()
+                        .enumerate()
+                        .filter(|(_, c)| *c == '\n')
+                        .nth(line - 1)
+                        .unwrap();
+
+                    return index + newline + 1;
+                }
+                Kind::Branch(left, right) => {
+                    if line <= left.newlines {
+                        node = left;
+                    } else {
+                        line -= left.newlines;
+                        index += left.chars;
+                        node = right;
+                    }
+                }
+            }
+        }
+    }
+
+    /// get the iterator of the strings in the leaves
+    pub fn chunks(&self) -> Chunks<'_> {
+        Chunks {
+            stack: vec![&self.root],
+        }
+    }
+
+    pub fn chars(&self) -> Chars<'_> {
+        Chars {
+            chunks: self.chunks(),
+            current: "".chars(),
+        }
+    }
+
+    /// get the iterator of the lines without '\n'
+    ///
+    /// It yields `len_lines` lines, so the text ending with '\n' has the empty line at the end.
+    pub fn lines(&self) -> Lines<'_> {
+        Lines {
+            chars: self.chars(),
+            done: false,
+        }
+    }
+}
+
+pub struct Chunks<'a> {
+    stack: Vec<&'a Node>,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match &self.stack.pop()?.kind {
+                Kind::Leaf(text) if text.is_empty() => {}
+                Kind::Leaf(text) => return Some(text),
+                Kind::Branch(left, right) => {
+                    self.stack.push(right);
+                    self.stack.push(left);
+                }
+            }
+        }
+    }
+}
+
+pub struct Chars<'a> {
+    chunks: Chunks<'a>,
+    current: str::Chars<'a>,
+}
+
+impl<'a> Iterator for Chars<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(c) = self.current.next() {
+                return Some(c);
+            }
+
+            self.current = self.chunks.next()?.chars();
+        }
+    }
+}
+
+pub struct Lines<'a> {
+    chars: Chars<'a>,
+    done: bool,
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let mut line = String::new();
+
+        for c in &mut self.chars {
+            if c == '\n' {
+                return Some(line);
+            }
+
+            line.push(c);
+        }
+
+        self.done = true;
+        Some(line)
+    }
+}
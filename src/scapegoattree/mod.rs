@@ -0,0 +1,290 @@
+use std::cmp::Ordering;
+
+use crate::map::SequentialMap;
+
+/// scapegoat tree with α = 2/3, which has no balance metadata on the nodes
+///
+/// If an insertion makes a node deeper than log_{1/α}(len), the subtree of the ancestor that is
+/// unbalanced(scapegoat) is rebuilt. If the removals shrink the tree by α, the whole tree is rebuilt.
+pub struct ScapegoatTree<K, V> {
+    root: Subtree<K, V>,
+    len: usize,
+    max_len: usize, // the maximum len since the last rebuilding of the whole tree
+}
+
+type Subtree<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    left: Subtree<K, V>,
+    right: Subtree<K, V>,
+}
+
+enum InsertResult {
+    Fitted,
+    TooDeep { size: usize }, // the inserted node is too deep, so find the scapegoat with the size of the subtree
+}
+
+/// get the maximum depth of the balanced tree that has len nodes, that is, floor(log_{3/2}(len))
+fn max_depth(len: usize) -> usize {
+    ((len as f64).ln() / 1.5f64.ln()).floor() as usize
+}
+
+/// check that the subtree of the size is unbalanced by the child of the size, that is, child > α * size
+fn is_scapegoat(child: usize, size: usize) -> bool {
+    3 * child > 2 * size
+}
+
+/// get the size of the subtree by traversing
+fn size<K, V>(tree: &Subtree<K, V>) -> usize {
+    let mut size = 0;
+    let mut stack: Vec<&Node<K, V>> = tree.iter().map(|node| node.as_ref()).collect();
+
+    while let Some(node) = stack.pop() {
+        size += 1;
+        stack.extend(node.left.iter().map(|node| node.as_ref()));
+        stack.extend(node.right.iter().map(|node| node.as_ref()));
+    }
+
+    size
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: V) -> Self {
+        Self {
+            key,
+            value,
+            left: None,
+            right: None,
+        }
+    }
+
+    /// rebuild the subtree as perfectly balanced
+    fn rebuild(tree: &mut Subtree<K, V>) {
+        let mut nodes = Vec::new();
+        let mut stack = Vec::new();
+        let mut current = tree.take();
+
+        // flatten the subtree in-order
+        loop {
+            while let Some(mut node) = current {
+                current = node.left.take();
+                stack.push(node);
+            }
+
+            match stack.pop() {
+                Some(mut node) => {
+                    current = node.right.take();
+                    nodes.push(node);
+                }
+                None => break,
+            }
+        }
+
+        *tree = Node::build(nodes.len(), &mut nodes.into_iter());
+    }
+
+    /// build the perfectly balanced tree from the first len nodes of the iterator
+    fn build<I: Iterator<Item = Box<Self>>>(len: usize, iter: &mut I) -> Subtree<K, V> {
+        if len == 0 {
+            return None;
+        }
+
+        let left = Node::build(len / 2, iter);
+        let mut node = iter.next().unwrap();
+        node.left = left;
+        node.right = Node::build(len - len / 2 - 1, iter);
+
+        Some(node)
+    }
+}
+
+impl<K: Ord, V> Node<K, V> {
+    fn insert(
+        tree: &mut Subtree<K, V>,
+        node: Box<Self>,
+        depth: usize,
+        max_depth: usize,
+    ) -> Result<InsertResult, Box<Self>> {
+        let current = match tree {
+            Some(current) => current,
+            None => {
+                *tree = Some(node);
+
+                return Ok(if depth > max_depth {
+                    InsertResult::TooDeep { size: 1 }
+                } else {
+                    InsertResult::Fitted
+                });
+            }
+        };
+
+        let (child, sibling) = match node.key.cmp(&current.key) {
+            Ordering::Less => (&mut current.left, &current.right),
+            Ordering::Equal => return Err(node),
+            Ordering::Greater => (&mut current.right, &current.left),
+        };
+
+        let child_size = match Node::insert(child, node, depth + 1, max_depth)? {
+            InsertResult::Fitted => return Ok(InsertResult::Fitted),
+            InsertResult::TooDeep { size } => size,
+        };
+
+        let size = child_size + size(sibling) + 1;
+
+        if is_scapegoat(child_size, size) {
+            Node::rebuild(tree);
+            Ok(InsertResult::Fitted)
+        } else {
+            Ok(InsertResult::TooDeep { size })
+        }
+    }
+
+    fn remove(tree: &mut Subtree<K, V>, key: &K) -> Result<Box<Self>, ()> {
+        let current = tree.as_mut().ok_or(())?;
+
+        match key.cmp(&current.key) {
+            Ordering::Less => Node::remove(&mut current.left, key),
+            Ordering::Greater => Node::remove(&mut current.right, key),
+            Ordering::Equal => {
+                let mut node = tree.take().unwrap();
+
+                *tree = match (node.left.take(), node.right.take()) {
+                    (None, right) => right,
+                    (left, None) => left,
+                    (left, mut right) => {
+                        // replace the node with the smallest node of the right subtree
+                        let mut next = Node::remove_first(&mut right);
+                        next.left = left;
+                        next.right = right;
+                        Some(next)
+                    }
+                };
+
+                Ok(node)
+            }
+        }
+    }
+
+    /// remove the node that has the smallest key on the non-empty subtree
+    fn remove_first(tree: &mut Subtree<K, V>) -> Box<Self> {
+        let mut current = tree;
+
+        while current.as_ref().unwrap().left.is_some() {
+            current = &mut current.as_mut().unwrap().left;
+        }
+
+        let mut node = current.take().unwrap();
+        *current = node.right.take();
+        node
+    }
+}
+
+impl<K, V> Default for ScapegoatTree<K, V> {
+    fn default() -> Self {
+        Self {
+            root: None,
+            len: 0,
+            max_len: 0,
+        }
+    }
+}
+
+impl<K, V> ScapegoatTree<K, V> {
+    /// get the number of the (key, value)s on the tree
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// get the height of the tree
+    pub fn get_height(&self) -> usize {
+        let mut height = 0;
+        let mut stack: Vec<(&Node<K, V>, usize)> =
+            self.root.iter().map(|node| (node.as_ref(), 1)).collect();
+
+        while let Some((node, depth)) = stack.pop() {
+            height = height.max(depth);
+            stack.extend(node.left.iter().map(|node| (node.as_ref(), depth + 1)));
+            stack.extend(node.right.iter().map(|node| (node.as_ref(), depth + 1)));
+        }
+
+        height
+    }
+}
+
+impl<K, V> SequentialMap<K, V> for ScapegoatTree<K, V>
+where
+    K: Ord + Clone,
+{
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(&mut self, key: &K, value: V) -> Result<(), V> {
+        let node = Box::new(Node::new(key.clone(), value));
+        let max_depth = max_depth(self.len + 1);
+
+        Node::insert(&mut self.root, node, 0, max_depth).map_err(|node| node.value)?;
+
+        self.len += 1;
+        self.max_len = self.max_len.max(self.len);
+        Ok(())
+    }
+
+    fn lookup(&self, key: &K) -> Option<&V> {
+        let mut current = self.root.as_ref();
+
+        while let Some(node) = current {
+            match key.cmp(&node.key) {
+                Ordering::Less => current = node.left.as_ref(),
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Greater => current = node.right.as_ref(),
+            }
+        }
+
+        None
+    }
+
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut current = self.root.as_mut();
+
+        while let Some(node) = current {
+            match key.cmp(&node.key) {
+                Ordering::Less => current = node.left.as_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+                Ordering::Greater => current = node.right.as_mut(),
+            }
+        }
+
+        None
+    }
+
+    fn remove(&mut self, key: &K) -> Result<V, ()> {
+        let node = Node::remove(&mut self.root, key)?;
+        self.len -= 1;
+
+        // rebuild the whole tree if len < α * max_len
+        if 3 * self.len < 2 * self.max_len {
+            Node::rebuild(&mut self.root);
+            self.max_len = self.len;
+        }
+
+        Ok(node.value)
+    }
+}
+
+impl<K, V> Drop for ScapegoatTree<K, V> {
+    fn drop(&mut self) {
+        let mut stack: Vec<Box<Node<K, V>>> = self.root.take().into_iter().collect();
+
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod spacesaving;
+mod tdigest;
+
+pub use spacesaving::SpaceSaving;
+pub use tdigest::TDigest;
@@ -0,0 +1,139 @@
+/*
+ Refer to
+ Ahmed Metwally, Divyakant Agrawal, Amr El Abbadi, "Efficient Computation of Frequent and Top-k Elements in Data Streams", 2005
+*/
+
+use std::{cmp::Reverse, hash::Hash};
+
+use crate::{
+    hashmap::ChainedHashMap,
+    heap::{BinaryHeap, PriorityQueue},
+    map::SequentialMap,
+};
+
+/// Space-Saving summary which monitors at most `capacity` items of the stream with their counts
+///
+/// The new item replaces the item of the smallest count m, starting from m + 1 with the error m.
+/// The true count of each monitored item is in [count - error, count], and every error is at most n / capacity
+/// for the stream of n items. So every item more frequent than n / capacity is monitored.
+pub struct SpaceSaving<T> {
+    counters: Vec<Counter<T>>,
+    indices: ChainedHashMap<T, usize>, // the index of the counter of the item
+    heap: BinaryHeap<(u64, usize)>,    // (count, index), which may be outdated by the increments
+    capacity: usize,
+    total: u64,
+}
+
+struct Counter<T> {
+    item: T,
+    count: u64,
+    error: u64,
+}
+
+impl<T: Hash + Eq + Clone> SpaceSaving<T> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "the capacity should be positive");
+
+        Self {
+            counters: Vec::with_capacity(capacity),
+            indices: ChainedHashMap::new(),
+            heap: BinaryHeap::new(),
+            capacity,
+            total: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// get the number of the monitored items
+    pub fn len(&self) -> usize {
+        self.counters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counters.is_empty()
+    }
+
+    /// get the number of the items of the stream
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    pub fn add(&mut self, item: &T) {
+        self.total += 1;
+
+        if let Some(&index) = self.indices.lookup(item) {
+            self.increment(index);
+            return;
+        }
+
+        if self.counters.len() < self.capacity {
+            self.counters.push(Counter {
+                item: item.clone(),
+                count: 0,
+                error: 0,
+            });
+
+            let index = self.counters.len() - 1;
+            self.indices.insert(item, index).ok().unwrap();
+            self.increment(index);
+            return;
+        }
+
+        // replace the item of the smallest count
+        let index = self.pop_min();
+        let counter = &mut self.counters[index];
+
+        self.indices.remove(&counter.item).unwrap();
+        self.indices.insert(item, index).ok().unwrap();
+        counter.item = item.clone();
+        counter.error = counter.count;
+        self.increment(index);
+    }
+
+    /// get (count, error) of the item, or None if it is not monitored
+    pub fn estimate(&self, item: &T) -> Option<(u64, u64)> {
+        let counter = &self.counters[*self.indices.lookup(item)?];
+        Some((counter.count, counter.error))
+    }
+
+    /// get (item, count, error)s of the k most frequent monitored items in the descending order of the count
+    pub fn top(&self, k: usize) -> Vec<(&T, u64, u64)> {
+        let mut top: Vec<_> = self
+            .counters
+            .iter()
+            .map(|counter| (&counter.item, counter.count, counter.error))
+            .collect();
+
+        top.sort_by_key(|(_, count, _)| Reverse(*count));
+        top.truncate(k);
+        top
+    }
+
+    fn increment(&mut self, index: usize) {
+        self.counters[index].count += 1;
+        self.heap.push((self.counters[index].count, index));
+
+        // rebuild the heap from the counters if the outdated entries dominate
+        if self.heap.len() > 4 * self.capacity {
+            self.heap = BinaryHeap::new();
+
+            for (index, counter) in self.counters.iter().enumerate() {
+                self.heap.push((counter.count, index));
+            }
+        }
+    }
+
+    /// pop the index of the counter of the smallest count, skipping the outdated entries
+    fn pop_min(&mut self) -> usize {
+        loop {
+            let (count, index) = self.heap.pop().unwrap();
+
+            if self.counters[index].count == count {
+                return index;
+            }
+        }
+    }
+}
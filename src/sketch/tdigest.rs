@@ -0,0 +1,198 @@
+/*
+ Refer to
+ Ted Dunning and Otmar Ertl, "Computing Extremely Accurate Quantiles Using t-Digests", 2019
+*/
+
+use std::{borrow::Cow, f64::consts::PI};
+
+const DEFAULT_COMPRESSION: f64 = 100.0;
+
+/// mergeable sketch of the distribution which estimates the quantiles accurately near the tails
+///
+/// The values are buffered, then merged into the sorted centroids. A centroid can absorb its neighbor only if
+/// they span at most 1 on the scale k(q) = compression / 2π * asin(2q - 1), so the centroids near q = 0 or 1
+/// are small. The number of the centroids is O(compression).
+#[derive(Clone)]
+pub struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>, // sorted by the mean
+    buffer: Vec<Centroid>,    // not merged yet
+    count: u64,
+    min: f64,
+    max: f64,
+}
+
+#[derive(Clone, Copy)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+impl Default for TDigest {
+    fn default() -> Self {
+        Self::new(DEFAULT_COMPRESSION)
+    }
+}
+
+impl TDigest {
+    /// make the sketch whose accuracy and size grow with the compression(about 100 is typical)
+    pub fn new(compression: f64) -> Self {
+        assert!(compression >= 1.0, "the compression should be at least 1");
+
+        Self {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            count: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    /// get the number of the added values
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        (!self.is_empty()).then_some(self.min)
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        (!self.is_empty()).then_some(self.max)
+    }
+
+    pub fn add(&mut self, value: f64) {
+        assert!(!value.is_nan(), "the value should not be NaN");
+
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.count += 1;
+        self.push(Centroid {
+            mean: value,
+            weight: 1.0,
+        });
+    }
+
+    /// add all values of the other sketch
+    pub fn merge(&mut self, other: &TDigest) {
+        if other.is_empty() {
+            return;
+        }
+
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.count += other.count;
+
+        for centroid in other.centroids.iter().chain(&other.buffer) {
+            self.push(*centroid);
+        }
+    }
+
+    /// estimate the value at the quantile q in [0, 1], or return None if it is empty
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        assert!((0.0..=1.0).contains(&q), "the quantile should be in [0, 1]");
+
+        if self.is_empty() {
+            return None;
+        }
+
+        let centroids = self.centroids();
+        let total: f64 = centroids.iter().map(|c| c.weight).sum();
+        let target = q * total;
+
+        if target <= 0.0 {
+            return Some(self.min);
+        }
+
+        if target >= total {
+            return Some(self.max);
+        }
+
+        // interpolate between the centers of the centroids, and the min and the max at the ends
+        let (mut prev_mean, mut prev_position) = (self.min, 0.0);
+        let mut cumulative = 0.0;
+
+        for centroid in centroids.iter() {
+            let position = cumulative + centroid.weight / 2.0;
+
+            if target < position {
+                let ratio = (target - prev_position) / (position - prev_position);
+                return Some(prev_mean + ratio * (centroid.mean - prev_mean));
+            }
+
+            cumulative += centroid.weight;
+            prev_mean = centroid.mean;
+            prev_position = position;
+        }
+
+        let ratio = (target - prev_position) / (total - prev_position);
+        Some(prev_mean + ratio * (self.max - prev_mean))
+    }
+
+    fn push(&mut self, centroid: Centroid) {
+        self.buffer.push(centroid);
+
+        if self.buffer.len() as f64 >= self.compression * 5.0 {
+            self.centroids = self.compress();
+            self.buffer.clear();
+        }
+    }
+
+    /// get the centroids with the buffer merged
+    fn centroids(&self) -> Cow<'_, [Centroid]> {
+        if self.buffer.is_empty() {
+            Cow::Borrowed(&self.centroids)
+        } else {
+            Cow::Owned(self.compress())
+        }
+    }
+
+    /// the scale function which maps the quantile to the index of the centroid
+    fn scale(&self, q: f64) -> f64 {
+        self.compression / (2.0 * PI) * (2.0 * q - 1.0).asin()
+    }
+
+    /// the inverse of the scale function
+    fn inverse_scale(&self, k: f64) -> f64 {
+        let angle = k * 2.0 * PI / self.compression;
+
+        if angle >= PI / 2.0 {
+            1.0
+        } else {
+            (angle.sin() + 1.0) / 2.0
+        }
+    }
+
+    /// merge the buffer into the centroids, then return the new centroids
+    fn compress(&self) -> Vec<Centroid> {
+        let mut all: Vec<_> = self.centroids.iter().chain(&self.buffer).copied().collect();
+        all.sort_by(|a, b| a.mean.partial_cmp(&b.mean).unwrap());
+
+        let total: f64 = all.iter().map(|c| c.weight).sum();
+        let mut merged = Vec::new();
+        let mut current = all[0];
+        let mut before = 0.0; // the weight before the current
+        let mut limit = self.inverse_scale(self.scale(0.0) + 1.0) * total;
+
+        for next in &all[1..] {
+            if before + current.weight + next.weight <= limit {
+                let weight = current.weight + next.weight;
+                current.mean += (next.mean - current.mean) * next.weight / weight;
+                current.weight = weight;
+            } else {
+                before += current.weight;
+                merged.push(current);
+                current = *next;
+                limit = self.inverse_scale(self.scale(before / total) + 1.0) * total;
+            }
+        }
+
+        merged.push(current);
+        merged
+    }
+}
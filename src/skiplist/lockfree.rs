@@ -0,0 +1,390 @@
+/*
+ Refer to
+ The Art of Multiprocessor Programming, 14.4 A Lock-Free Concurrent Skiplist and
+ https://www.cl.cam.ac.uk/techreports/UCAM-CL-TR-579.pdf
+*/
+
+use std::{
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crossbeam_epoch::{pin, unprotected, Atomic, Guard, Owned, Shared};
+
+use super::{random_height, MAX_HEIGHT};
+use crate::map::ConcurrentMap;
+
+/// lock-free skip list whose node is removed by marking its links from the top level to the bottom
+///
+/// The marked link(tag = 1) means that its node is logically removed on that level. The node is
+/// reclaimed by epoch when it is unlinked from all levels, which is counted by `refs`.
+pub struct LockFreeSkipList<K, V> {
+    head: [Atomic<Node<K, V>>; MAX_HEIGHT],
+}
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    next: Box<[Atomic<Node<K, V>>]>,
+    refs: AtomicUsize, // the number of the levels linked, and 1 while the inserting thread links the node
+}
+
+/// the links on each level that should point to the node of the key, and their next nodes
+struct Position<'g, K, V> {
+    found: bool,
+    preds: [&'g [Atomic<Node<K, V>>]; MAX_HEIGHT],
+    succs: [Shared<'g, Node<K, V>>; MAX_HEIGHT],
+}
+
+impl<K, V> Node<K, V> {
+    /// decrease the refs, then reclaim the node if nothing refers it
+    unsafe fn release(node: Shared<Node<K, V>>, guard: &Guard) {
+        if node.deref().refs.fetch_sub(1, Ordering::AcqRel) == 1 {
+            guard.defer_destroy(node);
+        }
+    }
+}
+
+impl<K: Ord, V> LockFreeSkipList<K, V> {
+    /// find the position of the key, unlinking the marked nodes on the way
+    fn find<'g>(&'g self, key: &K, guard: &'g Guard) -> Position<'g, K, V> {
+        'retry: loop {
+            let mut position = Position {
+                found: false,
+                preds: [&self.head[..]; MAX_HEIGHT],
+                succs: [Shared::null(); MAX_HEIGHT],
+            };
+
+            let mut pred: &'g [Atomic<Node<K, V>>] = &self.head;
+
+            for level in (0..MAX_HEIGHT).rev() {
+                let mut curr = pred[level].load(Ordering::Acquire, guard);
+
+                // the pred is removed on this level
+                if curr.tag() == 1 {
+                    continue 'retry;
+                }
+
+                while let Some(curr_ref) = unsafe { curr.as_ref() } {
+                    let succ = curr_ref.next[level].load(Ordering::Acquire, guard);
+
+                    if succ.tag() == 1 {
+                        // the curr is removed on this level, so unlink it
+                        match pred[level].compare_exchange(
+                            curr,
+                            succ.with_tag(0),
+                            Ordering::AcqRel,
+                            Ordering::Acquire,
+                            guard,
+                        ) {
+                            Ok(_) => {
+                                unsafe { Node::release(curr, guard) };
+                                curr = succ.with_tag(0);
+                            }
+                            Err(_) => continue 'retry,
+                        }
+                    } else if curr_ref.key < *key {
+                        pred = &curr_ref.next;
+                        curr = succ;
+                    } else {
+                        break;
+                    }
+                }
+
+                position.preds[level] = pred;
+                position.succs[level] = curr;
+            }
+
+            position.found =
+                unsafe { position.succs[0].as_ref() }.map_or(false, |node| node.key == *key);
+            return position;
+        }
+    }
+
+    /// mark the links of the node from the top level, and return whether this thread marked the bottom level
+    fn mark(node_ref: &Node<K, V>, guard: &Guard) -> bool {
+        for next in node_ref.next[1..].iter().rev() {
+            let mut current = next.load(Ordering::Acquire, guard);
+
+            while current.tag() == 0 {
+                match next.compare_exchange(
+                    current,
+                    current.with_tag(1),
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                    guard,
+                ) {
+                    Ok(_) => break,
+                    Err(e) => current = e.current,
+                }
+            }
+        }
+
+        // the thread that marks the bottom level removes the node
+        let next = &node_ref.next[0];
+        let mut current = next.load(Ordering::Acquire, guard);
+
+        loop {
+            if current.tag() == 1 {
+                return false;
+            }
+
+            match next.compare_exchange(
+                current,
+                current.with_tag(1),
+                Ordering::AcqRel,
+                Ordering::Acquire,
+                guard,
+            ) {
+                Ok(_) => return true,
+                Err(e) => current = e.current,
+            }
+        }
+    }
+
+    /// remove the first (key, value) on the bottom level, and return the copies of them
+    ///
+    /// It tries to mark the nodes from the first one, skipping the nodes removed by the other threads.
+    pub fn pop_first(&self) -> Option<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        let guard = pin();
+        let mut current = self.head[0].load(Ordering::Acquire, &guard);
+
+        loop {
+            let node_ref = unsafe { current.with_tag(0).as_ref()? };
+            let next = node_ref.next[0].load(Ordering::Acquire, &guard);
+
+            if next.tag() == 0 && Self::mark(node_ref, &guard) {
+                let (key, value) = (node_ref.key.clone(), node_ref.value.clone());
+
+                // unlink the node from all levels
+                self.find(&key, &guard);
+
+                return Some((key, value));
+            }
+
+            current = next;
+        }
+    }
+
+    /// get the iterator of the copies of (key, value)s in the order of the key
+    ///
+    /// It is weakly consistent: the (key, value)s inserted or removed during the iteration may or may not be yielded.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let guard = pin();
+        let current = self.head[0].load(Ordering::Acquire, &guard).as_raw();
+
+        Iter {
+            guard,
+            current,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V> ConcurrentMap<K, V> for LockFreeSkipList<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    fn new() -> Self {
+        Self {
+            head: [(); MAX_HEIGHT].map(|_| Atomic::null()),
+        }
+    }
+
+    fn insert(&self, key: &K, value: V) -> Result<(), V> {
+        let guard = pin();
+        let height = random_height();
+
+        let mut node = Owned::new(Node {
+            key: key.clone(),
+            value,
+            next: (0..height).map(|_| Atomic::null()).collect(),
+            refs: AtomicUsize::new(2), // the bottom level, and the inserting thread
+        });
+
+        // link on the bottom level, which is the linearization point
+        let (node, mut position) = loop {
+            let position = self.find(key, &guard);
+
+            if position.found {
+                return Err(node.into_box().value);
+            }
+
+            for (level, next) in node.next.iter().enumerate() {
+                next.store(position.succs[level], Ordering::Relaxed);
+            }
+
+            match position.preds[0][0].compare_exchange(
+                position.succs[0],
+                node,
+                Ordering::Release,
+                Ordering::Relaxed,
+                &guard,
+            ) {
+                Ok(node) => break (node, position),
+                Err(e) => node = e.new,
+            }
+        };
+
+        let node_ref = unsafe { node.deref() };
+
+        // link on the upper levels, and stop if the node is removed
+        'link: for level in 1..height {
+            loop {
+                let next = node_ref.next[level].load(Ordering::Acquire, &guard);
+                let succ = position.succs[level];
+
+                // only the removing thread marks it
+                if next.tag() == 1 {
+                    break 'link;
+                }
+
+                if next != succ
+                    && node_ref.next[level]
+                        .compare_exchange(next, succ, Ordering::AcqRel, Ordering::Acquire, &guard)
+                        .is_err()
+                {
+                    break 'link;
+                }
+
+                node_ref.refs.fetch_add(1, Ordering::Relaxed);
+
+                if position.preds[level][level]
+                    .compare_exchange(succ, node, Ordering::Release, Ordering::Relaxed, &guard)
+                    .is_ok()
+                {
+                    break;
+                }
+
+                node_ref.refs.fetch_sub(1, Ordering::Relaxed);
+
+                position = self.find(key, &guard);
+
+                if position.succs[0] != node {
+                    break 'link;
+                }
+            }
+        }
+
+        // if the node is removed while linking, help to unlink it from the levels linked after removing
+        if node_ref.next[0].load(Ordering::Acquire, &guard).tag() == 1 {
+            self.find(key, &guard);
+        }
+
+        unsafe { Node::release(node, &guard) };
+        Ok(())
+    }
+
+    fn lookup<F, R>(&self, key: &K, f: F) -> R
+    where
+        F: FnOnce(Option<&V>) -> R,
+    {
+        let guard = pin();
+        let mut pred: &[Atomic<Node<K, V>>] = &self.head;
+        let mut curr = Shared::null();
+
+        // traverse without unlinking, skipping the removed nodes
+        for level in (0..MAX_HEIGHT).rev() {
+            curr = pred[level].load(Ordering::Acquire, &guard).with_tag(0);
+
+            while let Some(curr_ref) = unsafe { curr.as_ref() } {
+                let succ = curr_ref.next[level].load(Ordering::Acquire, &guard);
+
+                if succ.tag() == 1 {
+                    curr = succ.with_tag(0);
+                } else if curr_ref.key < *key {
+                    pred = &curr_ref.next;
+                    curr = succ;
+                } else {
+                    break;
+                }
+            }
+        }
+
+        match unsafe { curr.as_ref() } {
+            Some(node) if node.key == *key => f(Some(&node.value)),
+            _ => f(None),
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.lookup(key, |value| value.cloned())
+    }
+
+    fn remove(&self, key: &K) -> Result<V, ()> {
+        let guard = pin();
+        let position = self.find(key, &guard);
+
+        if !position.found {
+            return Err(());
+        }
+
+        let node_ref = unsafe { position.succs[0].deref() };
+
+        if !Self::mark(node_ref, &guard) {
+            return Err(());
+        }
+
+        let value = node_ref.value.clone();
+
+        // unlink the node from all levels
+        self.find(key, &guard);
+
+        Ok(value)
+    }
+}
+
+impl<K, V> Drop for LockFreeSkipList<K, V> {
+    fn drop(&mut self) {
+        unsafe {
+            let guard = unprotected();
+
+            // every node is reclaimed when it is unlinked from the last level that it is linked
+            for level in (0..MAX_HEIGHT).rev() {
+                let mut current = self.head[level].load(Ordering::Relaxed, guard);
+
+                while let Some(node) = current.with_tag(0).as_ref() {
+                    let next = node.next[level].load(Ordering::Relaxed, guard);
+
+                    if node.refs.fetch_sub(1, Ordering::Relaxed) == 1 {
+                        drop(current.with_tag(0).into_owned());
+                    }
+
+                    current = next;
+                }
+            }
+        }
+    }
+}
+
+/// the iterator of the copies of (key, value)s on the bottom level, which pins the epoch while iterating
+pub struct Iter<'a, K, V> {
+    guard: Guard,
+    current: *const Node<K, V>,
+    _marker: PhantomData<&'a LockFreeSkipList<K, V>>,
+}
+
+impl<'a, K: Clone, V: Clone> Iterator for Iter<'a, K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = unsafe { self.current.as_ref()? };
+            let next = node.next[0].load(Ordering::Acquire, &self.guard);
+            self.current = next.with_tag(0).as_raw();
+
+            // skip the removed node
+            if next.tag() == 0 {
+                return Some((node.key.clone(), node.value.clone()));
+            }
+        }
+    }
+}
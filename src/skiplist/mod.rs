@@ -0,0 +1,260 @@
+mod lockfree;
+
+pub use lockfree::LockFreeSkipList;
+
+use std::{
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    ptr,
+};
+
+use rand::{thread_rng, Rng};
+
+use crate::map::SequentialMap;
+
+const MAX_HEIGHT: usize = 32;
+
+/// skip list whose node has the height of the geometric distribution with p = 1/2
+pub struct SkipList<K, V> {
+    head: [*mut Node<K, V>; MAX_HEIGHT],
+    height: usize, // the maximum height of the nodes
+    len: usize,
+}
+
+unsafe impl<K: Send, V: Send> Send for SkipList<K, V> {}
+unsafe impl<K: Sync, V: Sync> Sync for SkipList<K, V> {}
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    next: Vec<*mut Node<K, V>>, // the tower of the links whose length is the height
+}
+
+/// get the random height in [1, MAX_HEIGHT] with P(height = h) = 1/2^h
+fn random_height() -> usize {
+    let bits: u32 = thread_rng().gen();
+    (bits.trailing_ones() as usize + 1).min(MAX_HEIGHT)
+}
+
+impl<K, V> Default for SkipList<K, V> {
+    fn default() -> Self {
+        Self {
+            head: [ptr::null_mut(); MAX_HEIGHT],
+            height: 1,
+            len: 0,
+        }
+    }
+}
+
+impl<K, V> SkipList<K, V> {
+    /// get the number of the (key, value)s on the list
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<K: Ord, V> SkipList<K, V> {
+    /// find the first node whose key is not less than(or greater than if exclusive) the key
+    fn find_first(&self, key: &K, exclusive: bool) -> *const Node<K, V> {
+        let mut links: *const *mut Node<K, V> = self.head.as_ptr();
+
+        unsafe {
+            for level in (0..self.height).rev() {
+                loop {
+                    let next = *links.add(level);
+
+                    if next.is_null() || (*next).key > *key || (!exclusive && (*next).key == *key) {
+                        break;
+                    }
+
+                    links = (*next).next.as_ptr();
+                }
+            }
+
+            *links
+        }
+    }
+
+    /// find the links on each level that should point to the node of the key
+    ///
+    /// The link on the level is the head's or the last node's whose key is less than the key.
+    fn find_links(&mut self, key: &K) -> [*mut *mut Node<K, V>; MAX_HEIGHT] {
+        let mut result = [ptr::null_mut(); MAX_HEIGHT];
+        let mut links: *mut *mut Node<K, V> = self.head.as_mut_ptr();
+
+        unsafe {
+            for level in (self.height..MAX_HEIGHT).rev() {
+                result[level] = links.add(level);
+            }
+
+            for level in (0..self.height).rev() {
+                loop {
+                    let next = *links.add(level);
+
+                    if next.is_null() || (*next).key >= *key {
+                        break;
+                    }
+
+                    links = (*next).next.as_mut_ptr();
+                }
+
+                result[level] = links.add(level);
+            }
+        }
+
+        result
+    }
+
+    /// get the iterator of (&key, &value)s in the order of the key
+    pub fn iter(&self) -> Range<'_, K, V>
+    where
+        K: Clone,
+    {
+        self.range(..)
+    }
+
+    /// get the iterator of (&key, &value)s in the range in the order of the key
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V>
+    where
+        K: Clone,
+    {
+        let current = match range.start_bound() {
+            Bound::Included(start) => self.find_first(start, false),
+            Bound::Excluded(start) => self.find_first(start, true),
+            Bound::Unbounded => self.head[0],
+        };
+
+        Range {
+            current,
+            end: range.end_bound().cloned(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V> SequentialMap<K, V> for SkipList<K, V>
+where
+    K: Ord + Clone,
+{
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(&mut self, key: &K, value: V) -> Result<(), V> {
+        let links = self.find_links(key);
+
+        unsafe {
+            let next = *links[0];
+
+            if !next.is_null() && (*next).key == *key {
+                return Err(value);
+            }
+
+            let height = random_height();
+            let node = Box::into_raw(Box::new(Node {
+                key: key.clone(),
+                value,
+                next: links.iter().take(height).map(|&link| *link).collect(),
+            }));
+
+            for &link in links.iter().take(height) {
+                *link = node;
+            }
+
+            self.height = self.height.max(height);
+        }
+
+        self.len += 1;
+        Ok(())
+    }
+
+    fn lookup(&self, key: &K) -> Option<&V> {
+        let node = self.find_first(key, false);
+
+        unsafe {
+            if !node.is_null() && (*node).key == *key {
+                Some(&(*node).value)
+            } else {
+                None
+            }
+        }
+    }
+
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V> {
+        let node = self.find_first(key, false) as *mut Node<K, V>;
+
+        unsafe {
+            if !node.is_null() && (*node).key == *key {
+                Some(&mut (*node).value)
+            } else {
+                None
+            }
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Result<V, ()> {
+        let links = self.find_links(key);
+
+        unsafe {
+            let node = *links[0];
+
+            if node.is_null() || (*node).key != *key {
+                return Err(());
+            }
+
+            let node = Box::from_raw(node);
+
+            // the node is the next of the links on all levels of its height
+            for (&link, &next) in links.iter().zip(node.next.iter()) {
+                *link = next;
+            }
+
+            self.len -= 1;
+            Ok(node.value)
+        }
+    }
+}
+
+impl<K, V> Drop for SkipList<K, V> {
+    fn drop(&mut self) {
+        let mut current = self.head[0];
+
+        while !current.is_null() {
+            let node = unsafe { Box::from_raw(current) };
+            current = node.next[0];
+        }
+    }
+}
+
+/// the iterator of the references of (key, value)s in the range on the bottom level
+pub struct Range<'a, K, V> {
+    current: *const Node<K, V>,
+    end: Bound<K>,
+    _marker: PhantomData<&'a Node<K, V>>,
+}
+
+impl<'a, K: Ord, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = unsafe { self.current.as_ref()? };
+
+        let in_range = match &self.end {
+            Bound::Included(end) => node.key <= *end,
+            Bound::Excluded(end) => node.key < *end,
+            Bound::Unbounded => true,
+        };
+
+        if !in_range {
+            self.current = ptr::null();
+            return None;
+        }
+
+        self.current = node.next[0];
+        Some((&node.key, &node.value))
+    }
+}
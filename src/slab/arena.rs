@@ -0,0 +1,156 @@
+use std::{
+    mem,
+    ops::{Index as IndexOp, IndexMut},
+};
+
+/// the handle of the value in Arena
+///
+/// The generation of the slot increases whenever its value is removed, so the handle of the removed value never
+/// reaches the value inserted later on the same slot(no ABA problem).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Index {
+    slot: usize,
+    generation: u64,
+}
+
+/// slab whose handles are checked by the generation of the slot
+pub struct Arena<T> {
+    slots: Vec<Slot<T>>,
+    free: usize, // the head of the free list, slots.len() if it is empty
+    len: usize,
+}
+
+struct Slot<T> {
+    generation: u64,
+    entry: Entry<T>,
+}
+
+enum Entry<T> {
+    Occupied(T),
+    Vacant(usize), // the next vacant slot
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            free: 0,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// insert the value, then return its handle
+    pub fn insert(&mut self, value: T) -> Index {
+        let slot = self.free;
+
+        if slot == self.slots.len() {
+            self.slots.push(Slot {
+                generation: 0,
+                entry: Entry::Occupied(value),
+            });
+            self.free = self.slots.len();
+        } else {
+            match mem::replace(&mut self.slots[slot].entry, Entry::Occupied(value)) {
+                Entry::Vacant(next) => self.free = next,
+                Entry::Occupied(_) => unreachable!("the free list should have vacant slots"),
+            }
+        }
+
+        self.len += 1;
+
+        Index {
+            slot,
+            generation: self.slots[slot].generation,
+        }
+    }
+
+    pub fn get(&self, index: Index) -> Option<&T> {
+        match self.slots.get(index.slot)? {
+            Slot {
+                generation,
+                entry: Entry::Occupied(value),
+            } if *generation == index.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, index: Index) -> Option<&mut T> {
+        match self.slots.get_mut(index.slot)? {
+            Slot {
+                generation,
+                entry: Entry::Occupied(value),
+            } if *generation == index.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn contains(&self, index: Index) -> bool {
+        self.get(index).is_some()
+    }
+
+    /// remove the value of the handle, then the handle becomes invalid
+    pub fn remove(&mut self, index: Index) -> Option<T> {
+        self.get(index)?;
+
+        let slot = &mut self.slots[index.slot];
+        slot.generation += 1;
+
+        match mem::replace(&mut slot.entry, Entry::Vacant(self.free)) {
+            Entry::Occupied(value) => {
+                self.free = index.slot;
+                self.len -= 1;
+                Some(value)
+            }
+            Entry::Vacant(_) => unreachable!(),
+        }
+    }
+
+    /// get the iterator of (handle, value)s in the order of the slot
+    pub fn iter(&self) -> impl Iterator<Item = (Index, &T)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, Slot { generation, entry })| match entry {
+                Entry::Occupied(value) => Some((
+                    Index {
+                        slot,
+                        generation: *generation,
+                    },
+                    value,
+                )),
+                Entry::Vacant(_) => None,
+            })
+    }
+}
+
+impl<T> IndexOp<Index> for Arena<T> {
+    type Output = T;
+
+    fn index(&self, index: Index) -> &T {
+        self.get(index).expect("the handle should not be stale")
+    }
+}
+
+impl<T> IndexMut<Index> for Arena<T> {
+    fn index_mut(&mut self, index: Index) -> &mut T {
+        self.get_mut(index).expect("the handle should not be stale")
+    }
+}
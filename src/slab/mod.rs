@@ -0,0 +1,140 @@
+mod arena;
+
+pub use arena::{Arena, Index};
+
+use std::{
+    mem,
+    ops::{Index as IndexOp, IndexMut},
+};
+
+/// storage of the values on the array, whose key is the stable index of the value until it is removed
+///
+/// The vacant entries are linked as the free list, so insert and remove take O(1) and reuse the removed keys.
+pub struct Slab<T> {
+    entries: Vec<Entry<T>>,
+    free: usize, // the head of the free list, entries.len() if it is empty
+    len: usize,
+}
+
+enum Entry<T> {
+    Occupied(T),
+    Vacant(usize), // the next vacant entry
+}
+
+impl<T> Default for Slab<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Slab<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+            free: 0,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// get the key of the next inserted value
+    pub fn vacant_key(&self) -> usize {
+        self.free
+    }
+
+    /// insert the value, then return its key
+    pub fn insert(&mut self, value: T) -> usize {
+        let key = self.free;
+
+        if key == self.entries.len() {
+            self.entries.push(Entry::Occupied(value));
+            self.free = self.entries.len();
+        } else {
+            match mem::replace(&mut self.entries[key], Entry::Occupied(value)) {
+                Entry::Vacant(next) => self.free = next,
+                Entry::Occupied(_) => unreachable!("the free list should have vacant entries"),
+            }
+        }
+
+        self.len += 1;
+        key
+    }
+
+    pub fn get(&self, key: usize) -> Option<&T> {
+        match self.entries.get(key)? {
+            Entry::Occupied(value) => Some(value),
+            Entry::Vacant(_) => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, key: usize) -> Option<&mut T> {
+        match self.entries.get_mut(key)? {
+            Entry::Occupied(value) => Some(value),
+            Entry::Vacant(_) => None,
+        }
+    }
+
+    pub fn contains(&self, key: usize) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// remove the value of the key, then the key can be reused
+    pub fn remove(&mut self, key: usize) -> Option<T> {
+        let entry = self.entries.get_mut(key)?;
+
+        if let Entry::Vacant(_) = entry {
+            return None;
+        }
+
+        match mem::replace(entry, Entry::Vacant(self.free)) {
+            Entry::Occupied(value) => {
+                self.free = key;
+                self.len -= 1;
+                Some(value)
+            }
+            Entry::Vacant(_) => unreachable!(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.free = 0;
+        self.len = 0;
+    }
+
+    /// get the iterator of (key, value)s in the order of the key
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(key, entry)| match entry {
+                Entry::Occupied(value) => Some((key, value)),
+                Entry::Vacant(_) => None,
+            })
+    }
+}
+
+impl<T> IndexOp<usize> for Slab<T> {
+    type Output = T;
+
+    fn index(&self, key: usize) -> &T {
+        self.get(key).expect("the key should be occupied")
+    }
+}
+
+impl<T> IndexMut<usize> for Slab<T> {
+    fn index_mut(&mut self, key: usize) -> &mut T {
+        self.get_mut(key).expect("the key should be occupied")
+    }
+}
@@ -0,0 +1,253 @@
+/*
+ Refer to
+ Jon Louis Bentley, "Multidimensional Binary Search Trees Used for Associative Searching", 1975
+*/
+
+use std::{
+    cmp::{Ordering, Reverse},
+    mem,
+};
+
+use super::{contains, distance};
+use crate::heap::{BinaryHeap, PriorityQueue};
+
+/// k-d tree of the D-dimensional points, whose node splits its subtree by the coordinate on the axis of its depth
+///
+/// The left subtree has the coordinates <= the node's on the axis, and the right one has >=. The tree built at once
+/// splits at the median on each level, so it is balanced. The inserted points are not rebalanced, so the queries
+/// and drop run with the explicit stacks not to overflow on a deep tree.
+pub struct KdTree<V, const D: usize> {
+    root: Subtree<V, D>,
+    len: usize,
+}
+
+type Subtree<V, const D: usize> = Option<Box<Node<V, D>>>;
+
+struct Node<V, const D: usize> {
+    point: [f64; D],
+    value: V,
+    left: Subtree<V, D>,
+    right: Subtree<V, D>,
+}
+
+/// the candidate of the nearest neighbors, ordered by the distance
+struct Candidate<'a, V, const D: usize> {
+    distance: f64,
+    node: &'a Node<V, D>,
+}
+
+impl<'a, V, const D: usize> PartialEq for Candidate<'a, V, D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a, V, const D: usize> Eq for Candidate<'a, V, D> {}
+
+impl<'a, V, const D: usize> PartialOrd for Candidate<'a, V, D> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, V, const D: usize> Ord for Candidate<'a, V, D> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance.total_cmp(&other.distance)
+    }
+}
+
+impl<V, const D: usize> Node<V, D> {
+    /// build the balanced tree of the items by the median split from the axis
+    fn build(mut items: Vec<([f64; D], V)>, axis: usize) -> Subtree<V, D> {
+        if items.is_empty() {
+            return None;
+        }
+
+        let mid = items.len() / 2;
+        items.select_nth_unstable_by(mid, |(a, _), (b, _)| a[axis].total_cmp(&b[axis]));
+
+        let right = items.split_off(mid + 1);
+        let (point, value) = items.pop().unwrap();
+        let next = (axis + 1) % D;
+
+        Some(Box::new(Self {
+            point,
+            value,
+            left: Self::build(items, next),
+            right: Self::build(right, next),
+        }))
+    }
+
+    /// push the nodes nearer than the farthest candidate into the candidates, keeping at most k of them
+    ///
+    /// It visits the near side first with the explicit stack, since the inserted points may make a deep tree.
+    fn nearest<'a>(
+        root: &'a Subtree<V, D>,
+        point: &[f64; D],
+        k: usize,
+        candidates: &mut BinaryHeap<Reverse<Candidate<'a, V, D>>>,
+    ) {
+        // (subtree, axis, the squared distance to the splitting plane to cross into it)
+        let mut stack = vec![(root, 0, 0.0)];
+
+        while let Some((tree, axis, plane)) = stack.pop() {
+            let node = match tree {
+                Some(node) => node,
+                None => continue,
+            };
+
+            if candidates.len() == k && plane >= candidates.peek().unwrap().0.distance {
+                continue;
+            }
+
+            let candidate = Candidate {
+                distance: distance(point, &node.point),
+                node,
+            };
+
+            if candidates.len() < k {
+                candidates.push(Reverse(candidate));
+            } else if candidate.distance < candidates.peek().unwrap().0.distance {
+                candidates.pop();
+                candidates.push(Reverse(candidate));
+            }
+
+            let diff = point[axis] - node.point[axis];
+            let (near, far) = if diff < 0.0 {
+                (&node.left, &node.right)
+            } else {
+                (&node.right, &node.left)
+            };
+            let next = (axis + 1) % D;
+
+            // the far side is farther than the splitting plane
+            stack.push((far, next, plane.max(diff * diff)));
+            stack.push((near, next, plane));
+        }
+    }
+
+    fn range<'a>(
+        root: &'a Subtree<V, D>,
+        min: &[f64; D],
+        max: &[f64; D],
+        result: &mut Vec<(&'a [f64; D], &'a V)>,
+    ) {
+        let mut stack = vec![(root, 0)];
+
+        while let Some((tree, axis)) = stack.pop() {
+            let node = match tree {
+                Some(node) => node,
+                None => continue,
+            };
+
+            if contains(min, max, &node.point) {
+                result.push((&node.point, &node.value));
+            }
+
+            let next = (axis + 1) % D;
+
+            if node.point[axis] <= max[axis] {
+                stack.push((&node.right, next));
+            }
+
+            if min[axis] <= node.point[axis] {
+                stack.push((&node.left, next));
+            }
+        }
+    }
+}
+
+impl<V, const D: usize> Default for KdTree<V, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, const D: usize> KdTree<V, D> {
+    pub fn new() -> Self {
+        assert!(D > 0, "the dimension should be positive");
+
+        Self { root: None, len: 0 }
+    }
+
+    /// build the balanced tree of the (point, value)s at once in O(n log n)
+    pub fn build(items: Vec<([f64; D], V)>) -> Self {
+        assert!(D > 0, "the dimension should be positive");
+
+        Self {
+            len: items.len(),
+            root: Node::build(items, 0),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// insert the point with the value, allowing the same points
+    pub fn insert(&mut self, point: [f64; D], value: V) {
+        let mut current = &mut self.root;
+        let mut axis = 0;
+
+        while let Some(node) = current {
+            current = if point[axis] < node.point[axis] {
+                &mut node.left
+            } else {
+                &mut node.right
+            };
+            axis = (axis + 1) % D;
+        }
+
+        *current = Some(Box::new(Node {
+            point,
+            value,
+            left: None,
+            right: None,
+        }));
+        self.len += 1;
+    }
+
+    /// get the k nearest (point, value)s to the point in the ascending order of the euclidean distance
+    pub fn nearest(&self, point: &[f64; D], k: usize) -> Vec<(&[f64; D], &V)> {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        let mut candidates = BinaryHeap::new();
+        Node::nearest(&self.root, point, k, &mut candidates);
+
+        // the candidates pop from the farthest
+        let mut result = Vec::with_capacity(candidates.len());
+
+        while let Some(Reverse(Candidate { node, .. })) = candidates.pop() {
+            result.push((&node.point, &node.value));
+        }
+
+        result.reverse();
+        result
+    }
+
+    /// get the (point, value)s in the closed box [min, max]
+    pub fn range(&self, min: &[f64; D], max: &[f64; D]) -> Vec<(&[f64; D], &V)> {
+        let mut result = Vec::new();
+        Node::range(&self.root, min, max, &mut result);
+        result
+    }
+}
+
+impl<V, const D: usize> Drop for KdTree<V, D> {
+    fn drop(&mut self) {
+        // drop the nodes iteratively not to overflow the stack on a deep tree
+        let mut stack: Vec<Box<Node<V, D>>> = self.root.take().into_iter().collect();
+
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+            mem::drop(node);
+        }
+    }
+}
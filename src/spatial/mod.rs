@@ -0,0 +1,15 @@
+mod kdtree;
+mod orthtree;
+
+pub use kdtree::KdTree;
+pub use orthtree::{Cells, Octree, Orthtree, QuadTree};
+
+/// get the squared euclidean distance between the points
+fn distance<const D: usize>(a: &[f64; D], b: &[f64; D]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+/// check whether the point is in the closed box [min, max]
+fn contains<const D: usize>(min: &[f64; D], max: &[f64; D], point: &[f64; D]) -> bool {
+    (0..D).all(|axis| min[axis] <= point[axis] && point[axis] <= max[axis])
+}
@@ -0,0 +1,284 @@
+use std::mem;
+
+use super::contains;
+
+/// the max depth of the splits, under which the leaves may exceed the capacity(e.g. the same points)
+const MAX_DEPTH: usize = 32;
+
+/// region tree which splits the box into 2^D equal cells when its bucket exceeds the capacity
+///
+/// The leaves hold the buckets of the (point, value)s, and the branch whose cells hold at most the capacity in total
+/// is merged back into the leaf on remove.
+pub struct Orthtree<V, const D: usize> {
+    root: Node<V, D>,
+    min: [f64; D],
+    max: [f64; D],
+    capacity: usize,
+    len: usize,
+}
+
+/// region quadtree of the 2D points
+pub type QuadTree<V> = Orthtree<V, 2>;
+
+/// region octree of the 3D points
+pub type Octree<V> = Orthtree<V, 3>;
+
+enum Node<V, const D: usize> {
+    Leaf(Vec<([f64; D], V)>),
+    Branch(Box<[Node<V, D>]>), // 2^D cells, whose i-th has the upper half on the axis if the axis-th bit of i is 1
+}
+
+/// get the center of the box
+fn center<const D: usize>(min: &[f64; D], max: &[f64; D]) -> [f64; D] {
+    let mut mid = [0.0; D];
+
+    for axis in 0..D {
+        mid[axis] = (min[axis] + max[axis]) / 2.0;
+    }
+
+    mid
+}
+
+/// get the index of the cell which has the point
+fn cell<const D: usize>(mid: &[f64; D], point: &[f64; D]) -> usize {
+    (0..D)
+        .filter(|&axis| point[axis] >= mid[axis])
+        .fold(0, |index, axis| index | 1 << axis)
+}
+
+/// get the box of the index-th cell
+fn bounds<const D: usize>(
+    min: &[f64; D],
+    max: &[f64; D],
+    mid: &[f64; D],
+    index: usize,
+) -> ([f64; D], [f64; D]) {
+    let (mut low, mut high) = (*min, *max);
+
+    for axis in 0..D {
+        if index >> axis & 1 == 1 {
+            low[axis] = mid[axis];
+        } else {
+            high[axis] = mid[axis];
+        }
+    }
+
+    (low, high)
+}
+
+/// check whether the boxes overlap
+fn overlaps<const D: usize>(a: (&[f64; D], &[f64; D]), b: (&[f64; D], &[f64; D])) -> bool {
+    (0..D).all(|axis| a.0[axis] <= b.1[axis] && b.0[axis] <= a.1[axis])
+}
+
+impl<V, const D: usize> Node<V, D> {
+    fn insert(
+        &mut self,
+        min: &[f64; D],
+        max: &[f64; D],
+        depth: usize,
+        capacity: usize,
+        point: [f64; D],
+        value: V,
+    ) {
+        match self {
+            Node::Leaf(bucket) => {
+                bucket.push((point, value));
+
+                if bucket.len() > capacity && depth < MAX_DEPTH {
+                    self.split(min, max, depth, capacity);
+                }
+            }
+            Node::Branch(cells) => {
+                let mid = center(min, max);
+                let index = cell(&mid, &point);
+                let (low, high) = bounds(min, max, &mid, index);
+
+                cells[index].insert(&low, &high, depth + 1, capacity, point, value);
+            }
+        }
+    }
+
+    /// split the leaf into the branch, moving the bucket into the cells
+    fn split(&mut self, min: &[f64; D], max: &[f64; D], depth: usize, capacity: usize) {
+        let cells = (0..1 << D).map(|_| Node::Leaf(Vec::new())).collect();
+
+        if let Node::Leaf(bucket) = mem::replace(self, Node::Branch(cells)) {
+            for (point, value) in bucket {
+                self.insert(min, max, depth, capacity, point, value);
+            }
+        }
+    }
+
+    /// get the number of the points if the node is the leaf
+    fn bucket_len(&self) -> Option<usize> {
+        match self {
+            Node::Leaf(bucket) => Some(bucket.len()),
+            Node::Branch(_) => None,
+        }
+    }
+
+    fn remove(
+        &mut self,
+        min: &[f64; D],
+        max: &[f64; D],
+        capacity: usize,
+        point: &[f64; D],
+    ) -> Result<V, ()> {
+        let cells = match self {
+            Node::Leaf(bucket) => {
+                let index = bucket.iter().position(|(p, _)| p == point).ok_or(())?;
+                return Ok(bucket.swap_remove(index).1);
+            }
+            Node::Branch(cells) => cells,
+        };
+
+        let mid = center(min, max);
+        let index = cell(&mid, point);
+        let (low, high) = bounds(min, max, &mid, index);
+        let value = cells[index].remove(&low, &high, capacity, point)?;
+
+        // merge the cells if they fit in a leaf
+        let total = cells.iter().map(Node::bucket_len).sum::<Option<usize>>();
+
+        if total.map_or(false, |total| total <= capacity) {
+            let mut bucket = Vec::with_capacity(capacity);
+
+            for cell in mem::take(cells).into_vec() {
+                if let Node::Leaf(points) = cell {
+                    bucket.extend(points);
+                }
+            }
+
+            *self = Node::Leaf(bucket);
+        }
+
+        Ok(value)
+    }
+
+    fn query<'a>(
+        &'a self,
+        min: &[f64; D],
+        max: &[f64; D],
+        low: &[f64; D],
+        high: &[f64; D],
+        result: &mut Vec<(&'a [f64; D], &'a V)>,
+    ) {
+        match self {
+            Node::Leaf(bucket) => result.extend(
+                bucket
+                    .iter()
+                    .filter(|(point, _)| contains(low, high, point))
+                    .map(|(point, value)| (point, value)),
+            ),
+            Node::Branch(cells) => {
+                let mid = center(min, max);
+
+                for (index, cell) in cells.iter().enumerate() {
+                    let (cell_min, cell_max) = bounds(min, max, &mid, index);
+
+                    if overlaps((&cell_min, &cell_max), (low, high)) {
+                        cell.query(&cell_min, &cell_max, low, high, result);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<V, const D: usize> Orthtree<V, D> {
+    /// make the tree on the closed box [min, max], whose leaves split when they have more than `capacity` points
+    pub fn new(min: [f64; D], max: [f64; D], capacity: usize) -> Self {
+        assert!(D > 0, "the dimension should be positive");
+        assert!(capacity > 0, "the capacity should be positive");
+        assert!(
+            (0..D).all(|axis| min[axis] < max[axis]),
+            "the box should not be empty"
+        );
+
+        Self {
+            root: Node::Leaf(Vec::new()),
+            min,
+            max,
+            capacity,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// insert the point with the value, or return Err(value) if the point is out of the box
+    pub fn insert(&mut self, point: [f64; D], value: V) -> Result<(), V> {
+        if !contains(&self.min, &self.max, &point) {
+            return Err(value);
+        }
+
+        self.root
+            .insert(&self.min, &self.max, 0, self.capacity, point, value);
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// remove one of the same points, then return its value
+    pub fn remove(&mut self, point: &[f64; D]) -> Result<V, ()> {
+        if !contains(&self.min, &self.max, point) {
+            return Err(());
+        }
+
+        let value = self
+            .root
+            .remove(&self.min, &self.max, self.capacity, point)?;
+        self.len -= 1;
+
+        Ok(value)
+    }
+
+    /// get the (point, value)s in the closed box [low, high]
+    pub fn query(&self, low: &[f64; D], high: &[f64; D]) -> Vec<(&[f64; D], &V)> {
+        let mut result = Vec::new();
+        self.root
+            .query(&self.min, &self.max, low, high, &mut result);
+        result
+    }
+
+    /// get the iterator of (cell min, cell max, (point, value)s) of the leaves which have the points
+    pub fn cells(&self) -> Cells<'_, V, D> {
+        Cells {
+            stack: vec![(&self.root, self.min, self.max)],
+        }
+    }
+}
+
+pub struct Cells<'a, V, const D: usize> {
+    stack: Vec<(&'a Node<V, D>, [f64; D], [f64; D])>,
+}
+
+impl<'a, V, const D: usize> Iterator for Cells<'a, V, D> {
+    type Item = ([f64; D], [f64; D], &'a [([f64; D], V)]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, min, max) = self.stack.pop()?;
+
+            match node {
+                Node::Leaf(bucket) if bucket.is_empty() => {}
+                Node::Leaf(bucket) => return Some((min, max, bucket)),
+                Node::Branch(cells) => {
+                    let mid = center(&min, &max);
+
+                    for (index, cell) in cells.iter().enumerate().rev() {
+                        let (low, high) = bounds(&min, &max, &mid, index);
+                        self.stack.push((cell, low, high));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use std::{cell::Cell, cmp::Ordering, ptr};
+
+use crate::map::SequentialMap;
+
+/// splay tree with top-down splaying
+///
+/// Every operation moves the node of the key (or the last node on the search path) to the root,
+/// so the keys accessed frequently stay near the root.
+pub struct SplayTree<K, V> {
+    root: Cell<*mut Node<K, V>>,
+    len: usize,
+}
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    left: *mut Node<K, V>,
+    right: *mut Node<K, V>,
+}
+
+unsafe impl<K: Send, V: Send> Send for SplayTree<K, V> {}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: V) -> *mut Self {
+        Box::into_raw(Box::new(Self {
+            key,
+            value,
+            left: ptr::null_mut(),
+            right: ptr::null_mut(),
+        }))
+    }
+}
+
+impl<K: Ord, V> Node<K, V> {
+    /// splay the tree by the key top-down, then return new root
+    ///
+    /// The new root is the node of the key if exists, or the last node on the search path.
+    /// Only the pointers of the nodes are changed, so the references of the keys and values are still valid.
+    unsafe fn splay(mut root: *mut Self, key: &K) -> *mut Self {
+        if root.is_null() {
+            return root;
+        }
+
+        // the tree less than the root, and its node that has the greatest key
+        let (mut left, mut left_last) = (ptr::null_mut::<Self>(), ptr::null_mut::<Self>());
+        // the tree greater than the root, and its node that has the smallest key
+        let (mut right, mut right_first) = (ptr::null_mut::<Self>(), ptr::null_mut::<Self>());
+
+        loop {
+            match key.cmp(&(*root).key) {
+                Ordering::Less => {
+                    let child = (*root).left;
+
+                    if child.is_null() {
+                        break;
+                    }
+
+                    if *key < (*child).key {
+                        // zig-zig: rotate right
+                        (*root).left = (*child).right;
+                        (*child).right = root;
+                        root = child;
+
+                        if (*root).left.is_null() {
+                            break;
+                        }
+                    }
+
+                    // link the root to the right tree
+                    if right_first.is_null() {
+                        right = root;
+                    } else {
+                        (*right_first).left = root;
+                    }
+
+                    right_first = root;
+                    root = (*root).left;
+                }
+                Ordering::Greater => {
+                    let child = (*root).right;
+
+                    if child.is_null() {
+                        break;
+                    }
+
+                    if *key > (*child).key {
+                        // zag-zag: rotate left
+                        (*root).right = (*child).left;
+                        (*child).left = root;
+                        root = child;
+
+                        if (*root).right.is_null() {
+                            break;
+                        }
+                    }
+
+                    // link the root to the left tree
+                    if left_last.is_null() {
+                        left = root;
+                    } else {
+                        (*left_last).right = root;
+                    }
+
+                    left_last = root;
+                    root = (*root).right;
+                }
+                Ordering::Equal => break,
+            }
+        }
+
+        // assemble the left tree, the root and the right tree
+        if !left_last.is_null() {
+            (*left_last).right = (*root).left;
+            (*root).left = left;
+        }
+
+        if !right_first.is_null() {
+            (*right_first).left = (*root).right;
+            (*root).right = right;
+        }
+
+        root
+    }
+}
+
+impl<K, V> Default for SplayTree<K, V> {
+    fn default() -> Self {
+        Self {
+            root: Cell::new(ptr::null_mut()),
+            len: 0,
+        }
+    }
+}
+
+impl<K, V> SplayTree<K, V> {
+    /// get the number of the (key, value)s on the tree
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// get the (key, value) on the root, which is accessed at last
+    pub fn root(&self) -> Option<(&K, &V)> {
+        unsafe {
+            self.root
+                .get()
+                .as_ref()
+                .map(|node| (&node.key, &node.value))
+        }
+    }
+}
+
+impl<K: Ord, V> SplayTree<K, V> {
+    /// splay the tree by the key, then return the root if it has the key
+    fn find(&self, key: &K) -> Option<*mut Node<K, V>> {
+        unsafe {
+            let root = Node::splay(self.root.get(), key);
+            self.root.set(root);
+
+            if !root.is_null() && (*root).key == *key {
+                Some(root)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+impl<K, V> SequentialMap<K, V> for SplayTree<K, V>
+where
+    K: Ord + Clone,
+{
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(&mut self, key: &K, value: V) -> Result<(), V> {
+        unsafe {
+            let root = Node::splay(self.root.get(), key);
+
+            if root.is_null() {
+                self.root.set(Node::new(key.clone(), value));
+                self.len += 1;
+                return Ok(());
+            }
+
+            let node = match key.cmp(&(*root).key) {
+                Ordering::Less => {
+                    let node = Node::new(key.clone(), value);
+                    (*node).left = (*root).left;
+                    (*node).right = root;
+                    (*root).left = ptr::null_mut();
+                    node
+                }
+                Ordering::Equal => {
+                    self.root.set(root);
+                    return Err(value);
+                }
+                Ordering::Greater => {
+                    let node = Node::new(key.clone(), value);
+                    (*node).right = (*root).right;
+                    (*node).left = root;
+                    (*root).right = ptr::null_mut();
+                    node
+                }
+            };
+
+            self.root.set(node);
+            self.len += 1;
+            Ok(())
+        }
+    }
+
+    fn lookup(&self, key: &K) -> Option<&V> {
+        self.find(key).map(|node| unsafe { &(*node).value })
+    }
+
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.find(key).map(|node| unsafe { &mut (*node).value })
+    }
+
+    fn remove(&mut self, key: &K) -> Result<V, ()> {
+        let node = self.find(key).ok_or(())?;
+
+        unsafe {
+            let (left, right) = ((*node).left, (*node).right);
+
+            // the greatest node of the left tree becomes the root, which has no right child
+            let root = if left.is_null() {
+                right
+            } else {
+                let root = Node::splay(left, key);
+                (*root).right = right;
+                root
+            };
+
+            self.root.set(root);
+            self.len -= 1;
+
+            Ok(Box::from_raw(node).value)
+        }
+    }
+}
+
+impl<K, V> Drop for SplayTree<K, V> {
+    fn drop(&mut self) {
+        // the tree can be very deep, so drop the nodes iteratively
+        let mut stack = vec![self.root.get()];
+
+        while let Some(node) = stack.pop() {
+            if !node.is_null() {
+                let node = unsafe { Box::from_raw(node) };
+                stack.push(node.left);
+                stack.push(node.right);
+            }
+        }
+    }
+}
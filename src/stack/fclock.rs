@@ -0,0 +1,110 @@
+use std::hint::unreachable_unchecked;
+
+use crossbeam_epoch::pin;
+use crossbeam_utils::Backoff;
+
+use crate::lock::{
+    fclock::{FCLock, FlatCombining},
+    RawSimpleLock,
+};
+
+use super::{ConcurrentStack, Stack};
+
+#[derive(Debug, PartialEq)]
+enum StackOp<V> {
+    PushRequest(V),
+    PushResponse,
+    PopRequest,
+    PopResponse(Option<V>),
+}
+
+// The operation is handed over between the publisher and the combiner, and never accessed by both at once.
+unsafe impl<T: Send> Send for StackOp<T> {}
+unsafe impl<T: Send> Sync for StackOp<T> {}
+
+impl<V> FlatCombining<StackOp<V>> for Stack<V> {
+    fn apply(&mut self, operation: StackOp<V>) -> StackOp<V> {
+        match operation {
+            StackOp::PushRequest(value) => {
+                self.push(value);
+                StackOp::PushResponse
+            }
+            StackOp::PopRequest => StackOp::PopResponse(self.pop()),
+            _ => unreachable!("The response cannot be applied."),
+        }
+    }
+}
+
+/// concurrent stack by flat combining, whose values are sent to the combiner so should be Send
+///
+/// ```compile_fail
+/// use std::rc::Rc;
+///
+/// use cds::{lock::RawSpinLock, stack::FCStack};
+///
+/// fn assert_sync<T: Sync>() {}
+///
+/// assert_sync::<FCStack<Rc<i32>, RawSpinLock>>();
+/// ```
+pub struct FCStack<V: Send, L: RawSimpleLock> {
+    stack: FCLock<StackOp<V>, L>,
+}
+
+unsafe impl<V: Send, L: RawSimpleLock> Send for FCStack<V, L> {}
+unsafe impl<V: Send, L: RawSimpleLock> Sync for FCStack<V, L> {}
+
+impl<V: Send, L: RawSimpleLock> FCStack<V, L> {
+    #[cfg(feature = "concurrent_stat")]
+    pub fn print_stat(&self) {
+        self.stack.print_stat();
+    }
+}
+
+impl<V: 'static + Send, L: RawSimpleLock> ConcurrentStack<V> for FCStack<V, L> {
+    fn new() -> Self {
+        Self {
+            stack: FCLock::new(Stack::new()),
+        }
+    }
+
+    fn push(&self, value: V) {
+        let guard = pin();
+
+        let record = self.stack.acquire_record(&guard);
+        let record_ref = unsafe { record.deref() };
+
+        record_ref.set(StackOp::PushRequest(value));
+
+        self.stack.try_combine(record, &guard);
+    }
+
+    fn try_pop(&self) -> Option<V> {
+        let guard = pin();
+
+        let record = self.stack.acquire_record(&guard);
+        let record_ref = unsafe { record.deref() };
+
+        record_ref.set(StackOp::PopRequest);
+
+        self.stack.try_combine(record, &guard);
+
+        let operation = record_ref.get_operation(&guard);
+
+        if let StackOp::PopResponse(value) = operation {
+            value
+        } else {
+            unsafe { unreachable_unchecked() }
+        }
+    }
+
+    fn pop(&self) -> V {
+        let backoff = Backoff::new();
+
+        loop {
+            match self.try_pop() {
+                Some(value) => return value,
+                None => backoff.snooze(),
+            }
+        }
+    }
+}
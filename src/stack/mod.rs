@@ -1,6 +1,8 @@
+mod fclock;
 mod lock;
 mod lockfree;
 
+pub use fclock::FCStack;
 pub use lock::MutexStack;
 pub use lock::SpinLockStack;
 pub use lockfree::EBStack;
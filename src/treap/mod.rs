@@ -0,0 +1,328 @@
+use std::{cmp::Ordering, fmt::Debug};
+
+use rand::{thread_rng, Rng};
+
+use crate::map::SequentialMap;
+
+/// randomized BST whose nodes are ordered by the key, and heap-ordered by the random priority
+///
+/// The shape of the tree is the same as the BST built by inserting in the order of the priorities,
+/// so the expected height is O(log n).
+pub struct Treap<K, V> {
+    root: Subtree<K, V>,
+}
+
+type Subtree<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    priority: u32,
+    size: usize,
+    left: Subtree<K, V>,
+    right: Subtree<K, V>,
+}
+
+/// get the size of the subtree
+fn size<K, V>(tree: &Subtree<K, V>) -> usize {
+    tree.as_ref().map_or(0, |node| node.size)
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: V) -> Self {
+        Self {
+            key,
+            value,
+            priority: thread_rng().gen(),
+            size: 1,
+            left: None,
+            right: None,
+        }
+    }
+
+    /// renew the size of the node from the childs
+    fn renew(&mut self) {
+        self.size = size(&self.left) + size(&self.right) + 1;
+    }
+
+    /// merge two trees whose keys are all less(left) and greater(right), then return new root
+    fn merge(left: Subtree<K, V>, right: Subtree<K, V>) -> Subtree<K, V> {
+        match (left, right) {
+            (None, right) => right,
+            (left, None) => left,
+            (Some(mut left), Some(mut right)) => {
+                if left.priority > right.priority {
+                    left.right = Node::merge(left.right.take(), Some(right));
+                    left.renew();
+                    Some(left)
+                } else {
+                    right.left = Node::merge(Some(left), right.left.take());
+                    right.renew();
+                    Some(right)
+                }
+            }
+        }
+    }
+}
+
+impl<K: Ord, V> Node<K, V> {
+    /// split the tree into (the tree less than key, the node of key, the tree greater than key)
+    fn split(tree: Subtree<K, V>, key: &K) -> (Subtree<K, V>, Subtree<K, V>, Subtree<K, V>) {
+        let mut node = match tree {
+            Some(node) => node,
+            None => return (None, None, None),
+        };
+
+        match key.cmp(&node.key) {
+            Ordering::Less => {
+                let (less, found, greater) = Node::split(node.left.take(), key);
+                node.left = greater;
+                node.renew();
+                (less, found, Some(node))
+            }
+            Ordering::Equal => {
+                let (left, right) = (node.left.take(), node.right.take());
+                node.renew();
+                (left, Some(node), right)
+            }
+            Ordering::Greater => {
+                let (less, found, greater) = Node::split(node.right.take(), key);
+                node.right = less;
+                node.renew();
+                (Some(node), found, greater)
+            }
+        }
+    }
+
+    fn insert(tree: &mut Subtree<K, V>, mut node: Box<Self>) -> Result<(), Box<Self>> {
+        let current = match tree {
+            Some(current) => current,
+            None => {
+                *tree = Some(node);
+                return Ok(());
+            }
+        };
+
+        if node.priority > current.priority {
+            // the new node should be the root of the subtree
+            let (less, found, greater) = Node::split(tree.take(), &node.key);
+
+            if let Some(found) = found {
+                // restore the subtree, since the key already exists
+                *tree = Node::merge(Node::merge(less, Some(found)), greater);
+                return Err(node);
+            }
+
+            node.left = less;
+            node.right = greater;
+            node.renew();
+            *tree = Some(node);
+            return Ok(());
+        }
+
+        match node.key.cmp(&current.key) {
+            Ordering::Less => Node::insert(&mut current.left, node)?,
+            Ordering::Equal => return Err(node),
+            Ordering::Greater => Node::insert(&mut current.right, node)?,
+        }
+
+        current.size += 1;
+        Ok(())
+    }
+
+    fn remove(tree: &mut Subtree<K, V>, key: &K) -> Result<Box<Self>, ()> {
+        let current = tree.as_mut().ok_or(())?;
+
+        let node = match key.cmp(&current.key) {
+            Ordering::Less => Node::remove(&mut current.left, key)?,
+            Ordering::Equal => {
+                let mut node = tree.take().unwrap();
+                *tree = Node::merge(node.left.take(), node.right.take());
+                return Ok(node);
+            }
+            Ordering::Greater => Node::remove(&mut current.right, key)?,
+        };
+
+        current.size -= 1;
+        Ok(node)
+    }
+}
+
+impl<K: Ord + Debug, V> Node<K, V> {
+    /// check the invariants of the subtree whose keys should be in (from, to), then return the size
+    fn validate(&self, from: Option<&K>, to: Option<&K>) -> Result<usize, String> {
+        if from.map_or(false, |from| *from >= self.key) || to.map_or(false, |to| self.key >= *to) {
+            return Err(format!(
+                "The key {:?} is out of the range ({:?}, {:?}).",
+                self.key, from, to
+            ));
+        }
+
+        let mut size = 1;
+
+        for child in self.left.iter().chain(self.right.iter()) {
+            if child.priority > self.priority {
+                return Err(format!(
+                    "The priority of the key {:?} is greater than its parent {:?}.",
+                    child.key, self.key
+                ));
+            }
+        }
+
+        if let Some(left) = &self.left {
+            size += left.validate(from, Some(&self.key))?;
+        }
+
+        if let Some(right) = &self.right {
+            size += right.validate(Some(&self.key), to)?;
+        }
+
+        if self.size != size {
+            return Err(format!(
+                "The size of the key {:?} is {}, but it should be {}.",
+                self.key, self.size, size
+            ));
+        }
+
+        Ok(size)
+    }
+}
+
+impl<K: Ord + Debug, V> Treap<K, V> {
+    /// check the ordering of the keys, the heap order of the priorities and the size of every node
+    ///
+    /// If the tree is valid, return Ok(()), or Err(the description of the first violation).
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(node) = &self.root {
+            node.validate(None, None)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<K, V> Default for Treap<K, V> {
+    fn default() -> Self {
+        Self { root: None }
+    }
+}
+
+impl<K, V> Treap<K, V> {
+    /// get the number of the (key, value)s on the tree
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// get the height of the tree
+    pub fn get_height(&self) -> usize {
+        let mut height = 0;
+        let mut stack: Vec<(&Node<K, V>, usize)> = self.root.iter().map(|n| (&**n, 1)).collect();
+
+        while let Some((node, depth)) = stack.pop() {
+            height = height.max(depth);
+            stack.extend(node.left.iter().map(|n| (&**n, depth + 1)));
+            stack.extend(node.right.iter().map(|n| (&**n, depth + 1)));
+        }
+
+        height
+    }
+}
+
+impl<K: Ord, V> Treap<K, V> {
+    /// split the tree into two at the key, then return the tree that has the keys greater than or equal to the key
+    pub fn split_off(&mut self, key: &K) -> Self {
+        let (less, found, greater) = Node::split(self.root.take(), key);
+        self.root = less;
+
+        Self {
+            root: Node::merge(found, greater),
+        }
+    }
+
+    /// merge the other tree whose keys are all greater than the keys of this tree
+    ///
+    /// If success, return Ok(()).
+    /// If fail since the keys are overlapped, return Err(other).
+    pub fn merge(&mut self, mut other: Self) -> Result<(), Self> {
+        let mut last = self.root.as_ref();
+        while let Some(right) = last.and_then(|node| node.right.as_ref()) {
+            last = Some(right);
+        }
+
+        let mut first = other.root.as_ref();
+        while let Some(left) = first.and_then(|node| node.left.as_ref()) {
+            first = Some(left);
+        }
+
+        if let (Some(last), Some(first)) = (last, first) {
+            if last.key >= first.key {
+                return Err(other);
+            }
+        }
+
+        self.root = Node::merge(self.root.take(), other.root.take());
+        Ok(())
+    }
+}
+
+impl<K, V> SequentialMap<K, V> for Treap<K, V>
+where
+    K: Ord + Clone,
+{
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(&mut self, key: &K, value: V) -> Result<(), V> {
+        let node = Box::new(Node::new(key.clone(), value));
+
+        Node::insert(&mut self.root, node).map_err(|node| node.value)
+    }
+
+    fn lookup(&self, key: &K) -> Option<&V> {
+        let mut current = self.root.as_ref();
+
+        while let Some(node) = current {
+            match key.cmp(&node.key) {
+                Ordering::Less => current = node.left.as_ref(),
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Greater => current = node.right.as_ref(),
+            }
+        }
+
+        None
+    }
+
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut current = self.root.as_mut();
+
+        while let Some(node) = current {
+            match key.cmp(&node.key) {
+                Ordering::Less => current = node.left.as_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+                Ordering::Greater => current = node.right.as_mut(),
+            }
+        }
+
+        None
+    }
+
+    fn remove(&mut self, key: &K) -> Result<V, ()> {
+        Node::remove(&mut self.root, key).map(|node| node.value)
+    }
+}
+
+impl<K, V> Drop for Treap<K, V> {
+    fn drop(&mut self) {
+        let mut stack: Vec<Box<Node<K, V>>> = self.root.take().into_iter().collect();
+
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
@@ -0,0 +1,276 @@
+mod radix;
+
+pub use radix::{Iter as RadixIter, RadixTrie};
+
+use std::{mem, slice};
+
+use crate::map::SequentialMap;
+
+/// byte trie whose node has the children for each next byte of the keys
+pub struct Trie<V> {
+    root: Node<V>,
+    len: usize,
+}
+
+struct Node<V> {
+    value: Option<V>,
+    children: Vec<(u8, Node<V>)>, // sorted by the byte
+}
+
+impl<V> Node<V> {
+    fn new() -> Self {
+        Self {
+            value: None,
+            children: Vec::new(),
+        }
+    }
+
+    fn child(&self, byte: u8) -> Option<&Node<V>> {
+        let index = self
+            .children
+            .binary_search_by_key(&byte, |(b, _)| *b)
+            .ok()?;
+        Some(&self.children[index].1)
+    }
+
+    fn child_mut(&mut self, byte: u8) -> Option<&mut Node<V>> {
+        let index = self
+            .children
+            .binary_search_by_key(&byte, |(b, _)| *b)
+            .ok()?;
+        Some(&mut self.children[index].1)
+    }
+
+    /// get the node of the key, or None if there is no key starting with it
+    fn find(&self, key: &[u8]) -> Option<&Node<V>> {
+        key.iter().try_fold(self, |node, byte| node.child(*byte))
+    }
+
+    /// get the node on the path of the indices of the children
+    fn walk_mut(&mut self, path: &[usize]) -> &mut Node<V> {
+        path.iter()
+            .fold(self, |node, &index| &mut node.children[index].1)
+    }
+
+    /// remove the value of the key, and prune the empty children on the path
+    fn remove(&mut self, key: &[u8]) -> Result<V, ()> {
+        // find the path, and the deepest node on it which is kept after pruning
+        let mut path = Vec::with_capacity(key.len());
+        let mut keep = 0;
+        let mut node = &*self;
+
+        for (depth, byte) in key.iter().enumerate() {
+            if node.value.is_some() || node.children.len() > 1 {
+                keep = depth;
+            }
+
+            let index = node
+                .children
+                .binary_search_by_key(byte, |(b, _)| *b)
+                .map_err(|_| ())?;
+            path.push(index);
+            node = &node.children[index].1;
+        }
+
+        if node.value.is_none() {
+            return Err(());
+        }
+
+        if key.is_empty() || !node.children.is_empty() {
+            return self.walk_mut(&path).value.take().ok_or(());
+        }
+
+        // cut the chain below the kept node, whose last node has the value
+        let (_, mut chain) = self.walk_mut(&path[..keep]).children.remove(path[keep]);
+        let mut node = &mut chain;
+
+        while let Some((_, child)) = node.children.last_mut() {
+            node = child;
+        }
+
+        node.value.take().ok_or(())
+    }
+}
+
+impl<V> Drop for Node<V> {
+    fn drop(&mut self) {
+        // drop the descendants iteratively not to overflow the stack on a long key
+        let mut stack = mem::take(&mut self.children);
+
+        while let Some((_, mut node)) = stack.pop() {
+            stack.append(&mut node.children);
+        }
+    }
+}
+
+impl<V> Default for Trie<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> Trie<V> {
+    pub fn new() -> Self {
+        Self {
+            root: Node::new(),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// insert (key, value), or return Err(value) if the key exists
+    pub fn insert<K: AsRef<[u8]>>(&mut self, key: K, value: V) -> Result<(), V> {
+        let mut node = &mut self.root;
+
+        for &byte in key.as_ref() {
+            let index = match node.children.binary_search_by_key(&byte, |(b, _)| *b) {
+                Ok(index) => index,
+                Err(index) => {
+                    node.children.insert(index, (byte, Node::new()));
+                    index
+                }
+            };
+
+            node = &mut node.children[index].1;
+        }
+
+        if node.value.is_some() {
+            return Err(value);
+        }
+
+        node.value = Some(value);
+        self.len += 1;
+
+        Ok(())
+    }
+
+    pub fn get<K: AsRef<[u8]>>(&self, key: K) -> Option<&V> {
+        self.root.find(key.as_ref())?.value.as_ref()
+    }
+
+    pub fn get_mut<K: AsRef<[u8]>>(&mut self, key: K) -> Option<&mut V> {
+        let mut node = &mut self.root;
+
+        for &byte in key.as_ref() {
+            node = node.child_mut(byte)?;
+        }
+
+        node.value.as_mut()
+    }
+
+    pub fn contains<K: AsRef<[u8]>>(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn remove<K: AsRef<[u8]>>(&mut self, key: K) -> Result<V, ()> {
+        let value = self.root.remove(key.as_ref())?;
+        self.len -= 1;
+
+        Ok(value)
+    }
+
+    /// get the iterator of (key, value)s which start with the prefix in the order of the key
+    pub fn iter_prefix<K: AsRef<[u8]>>(&self, prefix: K) -> Iter<'_, V> {
+        let prefix = prefix.as_ref();
+
+        Iter {
+            root: self.root.find(prefix),
+            stack: Vec::new(),
+            key: prefix.to_vec(),
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, V> {
+        self.iter_prefix([])
+    }
+
+    /// get the value of the longest key that is the prefix of the key, with the length of it
+    pub fn longest_prefix<K: AsRef<[u8]>>(&self, key: K) -> Option<(usize, &V)> {
+        let mut node = &self.root;
+        let mut longest = node.value.as_ref().map(|value| (0, value));
+
+        for (depth, &byte) in key.as_ref().iter().enumerate() {
+            node = match node.child(byte) {
+                Some(child) => child,
+                None => break,
+            };
+
+            if let Some(value) = &node.value {
+                longest = Some((depth + 1, value));
+            }
+        }
+
+        longest
+    }
+}
+
+impl<K: AsRef<[u8]> + Eq, V> SequentialMap<K, V> for Trie<V> {
+    fn new() -> Self {
+        Trie::new()
+    }
+
+    fn insert(&mut self, key: &K, value: V) -> Result<(), V> {
+        Trie::insert(self, key, value)
+    }
+
+    fn lookup(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.get_mut(key)
+    }
+
+    fn remove(&mut self, key: &K) -> Result<V, ()> {
+        Trie::remove(self, key)
+    }
+}
+
+/// the iterator of (key, value)s in the order of the key by DFS
+pub struct Iter<'a, V> {
+    root: Option<&'a Node<V>>, // the node of the prefix not visited yet
+    stack: Vec<slice::Iter<'a, (u8, Node<V>)>>,
+    key: Vec<u8>,
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (Vec<u8>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(root) = self.root.take() {
+            self.stack.push(root.children.iter());
+
+            if let Some(value) = &root.value {
+                return Some((self.key.clone(), value));
+            }
+        }
+
+        loop {
+            match self.stack.last_mut()?.next() {
+                Some((byte, child)) => {
+                    self.key.push(*byte);
+                    self.stack.push(child.children.iter());
+
+                    if let Some(value) = &child.value {
+                        return Some((self.key.clone(), value));
+                    }
+                }
+                None => {
+                    // keep the prefix after the node of the prefix is done
+                    self.stack.pop();
+
+                    if !self.stack.is_empty() {
+                        self.key.pop();
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use std::{mem, slice};
+
+use crate::map::SequentialMap;
+
+/// compressed trie whose edge has the bytes of the keys until they branch(radix tree, Patricia trie)
+///
+/// Every node except the root has the value or at least two children, so the number of the nodes is O(n).
+pub struct RadixTrie<V> {
+    root: Node<V>,
+    len: usize,
+}
+
+struct Node<V> {
+    prefix: Vec<u8>, // the label of the edge from the parent
+    value: Option<V>,
+    children: Vec<Node<V>>, // sorted by the first byte of the prefix
+}
+
+/// get the length of the common prefix
+fn common_prefix(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(a, b)| a == b).count()
+}
+
+impl<V> Node<V> {
+    fn new(prefix: Vec<u8>, value: Option<V>) -> Self {
+        Self {
+            prefix,
+            value,
+            children: Vec::new(),
+        }
+    }
+
+    /// find the index of the child whose prefix starts with the byte
+    fn child_index(&self, byte: u8) -> Result<usize, usize> {
+        self.children
+            .binary_search_by_key(&byte, |child| child.prefix[0])
+    }
+
+    /// get the node whose path starts with the key, and the path from the node
+    ///
+    /// The key may end in the middle of the edge to the node, so the path is the rest of the edge.
+    fn find_prefix<'a>(&'a self, key: &[u8], path: &mut Vec<u8>) -> Option<&'a Node<V>> {
+        let mut node = self;
+        let mut rest = key;
+
+        while let Some(&byte) = rest.first() {
+            let child = &node.children[node.child_index(byte).ok()?];
+            let common = common_prefix(&child.prefix, rest);
+
+            if common < rest.len() && common < child.prefix.len() {
+                return None;
+            }
+
+            path.extend_from_slice(&child.prefix);
+            rest = &rest[common..];
+            node = child;
+        }
+
+        Some(node)
+    }
+
+    /// get the node on the path of the indices of the children
+    fn walk_mut(&mut self, path: &[usize]) -> &mut Node<V> {
+        path.iter()
+            .fold(self, |node, &index| &mut node.children[index])
+    }
+
+    /// merge the only child into this node
+    fn merge_child(&mut self) {
+        let mut child = self.children.pop().unwrap();
+        self.prefix.append(&mut child.prefix);
+        self.value = child.value.take();
+        self.children = mem::take(&mut child.children);
+    }
+
+    /// remove the value of the key, and merge or prune the nodes on the path
+    ///
+    /// Only the node of the key and its parent may break the invariant, so the nodes above them are not changed.
+    fn remove(&mut self, key: &[u8]) -> Result<V, ()> {
+        let mut path = Vec::new();
+        let mut node = &*self;
+        let mut rest = key;
+
+        while let Some(&byte) = rest.first() {
+            let index = node.child_index(byte).map_err(|_| ())?;
+            node = &node.children[index];
+            rest = rest.strip_prefix(node.prefix.as_slice()).ok_or(())?;
+            path.push(index);
+        }
+
+        let node = self.walk_mut(&path);
+        let value = node.value.take().ok_or(())?;
+
+        if let Some((&index, above)) = path.split_last() {
+            match node.children.len() {
+                0 => {
+                    let parent = self.walk_mut(above);
+                    parent.children.remove(index);
+
+                    if !above.is_empty() && parent.value.is_none() && parent.children.len() == 1 {
+                        parent.merge_child();
+                    }
+                }
+                1 => node.merge_child(),
+                _ => {}
+            }
+        }
+
+        Ok(value)
+    }
+}
+
+impl<V> Drop for Node<V> {
+    fn drop(&mut self) {
+        // drop the descendants iteratively not to overflow the stack on a deep chain
+        let mut stack = mem::take(&mut self.children);
+
+        while let Some(mut node) = stack.pop() {
+            stack.append(&mut node.children);
+        }
+    }
+}
+
+impl<V> Default for RadixTrie<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> RadixTrie<V> {
+    pub fn new() -> Self {
+        Self {
+            root: Node::new(Vec::new(), None),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// insert (key, value), or return Err(value) if the key exists
+    pub fn insert<K: AsRef<[u8]>>(&mut self, key: K, value: V) -> Result<(), V> {
+        let mut node = &mut self.root;
+        let mut rest = key.as_ref();
+
+        while let Some(&byte) = rest.first() {
+            let index = match node.child_index(byte) {
+                Ok(index) => index,
+                Err(index) => {
+                    node.children
+                        .insert(index, Node::new(rest.to_vec(), Some(value)));
+                    self.len += 1;
+                    return Ok(());
+                }
+            };
+
+            let child = &mut node.children[index];
+            let common = common_prefix(&child.prefix, rest);
+
+            if common < child.prefix.len() {
+                // split the edge at the common prefix
+                let suffix = child.prefix.split_off(common);
+                let mut lower = Node::new(suffix, child.value.take());
+                lower.children = mem::take(&mut child.children);
+                child.children.push(lower);
+            }
+
+            rest = &rest[common..];
+            node = child;
+        }
+
+        if node.value.is_some() {
+            return Err(value);
+        }
+
+        node.value = Some(value);
+        self.len += 1;
+
+        Ok(())
+    }
+
+    pub fn get<K: AsRef<[u8]>>(&self, key: K) -> Option<&V> {
+        let mut node = &self.root;
+        let mut rest = key.as_ref();
+
+        while let Some(&byte) = rest.first() {
+            node = &node.children[node.child_index(byte).ok()?];
+            rest = rest.strip_prefix(node.prefix.as_slice())?;
+        }
+
+        node.value.as_ref()
+    }
+
+    pub fn get_mut<K: AsRef<[u8]>>(&mut self, key: K) -> Option<&mut V> {
+        let mut node = &mut self.root;
+        let mut rest = key.as_ref();
+
+        while let Some(&byte) = rest.first() {
+            let index = node.child_index(byte).ok()?;
+            node = &mut node.children[index];
+            rest = rest.strip_prefix(node.prefix.as_slice())?;
+        }
+
+        node.value.as_mut()
+    }
+
+    pub fn contains<K: AsRef<[u8]>>(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn remove<K: AsRef<[u8]>>(&mut self, key: K) -> Result<V, ()> {
+        let value = self.root.remove(key.as_ref())?;
+        self.len -= 1;
+
+        Ok(value)
+    }
+
+    /// get the iterator of (key, value)s which start with the prefix in the order of the key
+    pub fn iter_prefix<K: AsRef<[u8]>>(&self, prefix: K) -> Iter<'_, V> {
+        let mut key = Vec::new();
+        let root = self.root.find_prefix(prefix.as_ref(), &mut key);
+
+        Iter {
+            root,
+            stack: Vec::new(),
+            key,
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, V> {
+        self.iter_prefix([])
+    }
+
+    /// get the value of the longest key that is the prefix of the key, with the length of it
+    pub fn longest_prefix<K: AsRef<[u8]>>(&self, key: K) -> Option<(usize, &V)> {
+        let key = key.as_ref();
+        let mut node = &self.root;
+        let mut depth = 0;
+        let mut longest = node.value.as_ref().map(|value| (0, value));
+
+        while let Some(&byte) = key.get(depth) {
+            node = match node.child_index(byte) {
+                Ok(index) => &node.children[index],
+                Err(_) => break,
+            };
+
+            if !key[depth..].starts_with(&node.prefix) {
+                break;
+            }
+
+            depth += node.prefix.len();
+
+            if let Some(value) = &node.value {
+                longest = Some((depth, value));
+            }
+        }
+
+        longest
+    }
+}
+
+impl<K: AsRef<[u8]> + Eq, V> SequentialMap<K, V> for RadixTrie<V> {
+    fn new() -> Self {
+        RadixTrie::new()
+    }
+
+    fn insert(&mut self, key: &K, value: V) -> Result<(), V> {
+        RadixTrie::insert(self, key, value)
+    }
+
+    fn lookup(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.get_mut(key)
+    }
+
+    fn remove(&mut self, key: &K) -> Result<V, ()> {
+        RadixTrie::remove(self, key)
+    }
+}
+
+/// the iterator of (key, value)s in the order of the key by DFS
+pub struct Iter<'a, V> {
+    root: Option<&'a Node<V>>, // the node of the prefix not visited yet
+    stack: Vec<(slice::Iter<'a, Node<V>>, usize)>, // the children and the length of the edge to them
+    key: Vec<u8>,
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (Vec<u8>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(root) = self.root.take() {
+            // the edge to the node of the prefix is not truncated, so the prefix is kept
+            self.stack.push((root.children.iter(), 0));
+
+            if let Some(value) = &root.value {
+                return Some((self.key.clone(), value));
+            }
+        }
+
+        loop {
+            let (children, _) = self.stack.last_mut()?;
+
+            match children.next() {
+                Some(child) => {
+                    self.key.extend_from_slice(&child.prefix);
+                    self.stack.push((child.children.iter(), child.prefix.len()));
+
+                    if let Some(value) = &child.value {
+                        return Some((self.key.clone(), value));
+                    }
+                }
+                None => {
+                    let (_, edge) = self.stack.pop().unwrap();
+                    self.key.truncate(self.key.len() - edge);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,226 @@
+/*
+ Refer to
+ Hazard Pointers: Safe Memory Reclamation for Lock-Free Objects(Michael, 2004)
+*/
+
+use std::{
+    collections::HashSet,
+    marker::PhantomData,
+    ptr,
+    sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+};
+
+/// the number of the retired pointers that triggers the scan
+const SCAN_THRESHOLD: usize = 64;
+
+/// hazard pointer domain that reclaims the retired Box<T> when no hazard protects it
+///
+/// The slots are reused by threads, so the memory overhead is bounded by the number of the slots in use.
+pub struct HazardDomain<T> {
+    slots: AtomicPtr<Slot>,
+    retired: AtomicPtr<Retired<T>>,
+    retired_count: AtomicUsize,
+}
+
+unsafe impl<T: Send> Send for HazardDomain<T> {}
+unsafe impl<T: Send> Sync for HazardDomain<T> {}
+
+struct Slot {
+    active: AtomicBool,
+    pointer: AtomicPtr<u8>,
+    next: *mut Slot,
+}
+
+struct Retired<T> {
+    pointer: *mut T,
+    next: *mut Retired<T>,
+}
+
+/// the owned slot of the domain which protects one pointer until it is changed or dropped
+pub struct Hazard<'d, T> {
+    slot: &'d Slot,
+    _marker: PhantomData<*mut T>,
+}
+
+impl<T> Default for HazardDomain<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> HazardDomain<T> {
+    pub fn new() -> Self {
+        Self {
+            slots: AtomicPtr::new(ptr::null_mut()),
+            retired: AtomicPtr::new(ptr::null_mut()),
+            retired_count: AtomicUsize::new(0),
+        }
+    }
+
+    /// acquire the inactive slot, or make a new slot if all slots are active
+    pub fn acquire(&self) -> Hazard<'_, T> {
+        let mut current = self.slots.load(Ordering::Acquire);
+
+        while let Some(slot) = unsafe { current.as_ref() } {
+            if !slot.active.load(Ordering::Relaxed)
+                && slot
+                    .active
+                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            {
+                return Hazard {
+                    slot,
+                    _marker: PhantomData,
+                };
+            }
+
+            current = slot.next;
+        }
+
+        let slot = Box::into_raw(Box::new(Slot {
+            active: AtomicBool::new(true),
+            pointer: AtomicPtr::new(ptr::null_mut()),
+            next: ptr::null_mut(),
+        }));
+
+        let mut head = self.slots.load(Ordering::Relaxed);
+
+        loop {
+            unsafe { (*slot).next = head };
+
+            match self
+                .slots
+                .compare_exchange(head, slot, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => break,
+                Err(current) => head = current,
+            }
+        }
+
+        Hazard {
+            slot: unsafe { &*slot },
+            _marker: PhantomData,
+        }
+    }
+
+    /// retire the pointer that is already unlinked, and reclaim it later when no hazard protects it
+    ///
+    /// # Safety
+    /// The pointer should be made by Box::into_raw, and unreachable from the shared structure.
+    pub unsafe fn retire(&self, pointer: *mut T) {
+        let retired = Box::into_raw(Box::new(Retired {
+            pointer,
+            next: ptr::null_mut(),
+        }));
+
+        self.push_retired(retired, retired);
+
+        if self.retired_count.fetch_add(1, Ordering::Relaxed) + 1 >= SCAN_THRESHOLD {
+            self.scan();
+        }
+    }
+
+    /// push the chain of the retired from first to last
+    unsafe fn push_retired(&self, first: *mut Retired<T>, last: *mut Retired<T>) {
+        let mut head = self.retired.load(Ordering::Relaxed);
+
+        loop {
+            (*last).next = head;
+
+            match self
+                .retired
+                .compare_exchange(head, first, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    /// reclaim the retired pointers which are not protected, and push back the others
+    unsafe fn scan(&self) {
+        let mut current = self.retired.swap(ptr::null_mut(), Ordering::SeqCst);
+
+        let mut hazards = HashSet::new();
+        let mut slot = self.slots.load(Ordering::SeqCst);
+
+        while let Some(slot_ref) = slot.as_ref() {
+            let pointer = slot_ref.pointer.load(Ordering::SeqCst);
+
+            if !pointer.is_null() {
+                hazards.insert(pointer as *mut T);
+            }
+
+            slot = slot_ref.next;
+        }
+
+        let mut first: *mut Retired<T> = ptr::null_mut();
+        let mut last: *mut Retired<T> = ptr::null_mut();
+        let mut reclaimed = 0;
+
+        while !current.is_null() {
+            let retired = current;
+            current = (*retired).next;
+
+            if hazards.contains(&(*retired).pointer) {
+                (*retired).next = first;
+                first = retired;
+
+                if last.is_null() {
+                    last = retired;
+                }
+            } else {
+                let retired = Box::from_raw(retired);
+                drop(Box::from_raw(retired.pointer));
+                reclaimed += 1;
+            }
+        }
+
+        self.retired_count.fetch_sub(reclaimed, Ordering::Relaxed);
+
+        if !first.is_null() {
+            self.push_retired(first, last);
+        }
+    }
+}
+
+impl<T> Drop for HazardDomain<T> {
+    fn drop(&mut self) {
+        unsafe {
+            let mut retired = *self.retired.get_mut();
+
+            while !retired.is_null() {
+                let node = Box::from_raw(retired);
+                drop(Box::from_raw(node.pointer));
+                retired = node.next;
+            }
+
+            let mut slot = *self.slots.get_mut();
+
+            while !slot.is_null() {
+                let node = Box::from_raw(slot);
+                slot = node.next;
+            }
+        }
+    }
+}
+
+impl<'d, T> Hazard<'d, T> {
+    /// protect the pointer, then the caller should validate that it is still reachable
+    pub fn protect(&self, pointer: *mut T) {
+        self.slot
+            .pointer
+            .store(pointer as *mut u8, Ordering::SeqCst);
+    }
+
+    pub fn clear(&self) {
+        self.slot.pointer.store(ptr::null_mut(), Ordering::Release);
+    }
+}
+
+impl<'d, T> Drop for Hazard<'d, T> {
+    fn drop(&mut self) {
+        self.clear();
+        self.slot.active.store(false, Ordering::Release);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod hazard;
 pub mod random;
 
 #[macro_export]
@@ -0,0 +1,156 @@
+use std::ops::Range;
+
+use crate::bitset::{BitVec, RankSelect};
+
+/// static sequence of the integers which answers rank, select, and quantile in O(log σ) for the max value σ
+///
+/// It is the levelwise form of the wavelet tree(the wavelet matrix). Each level has the bits of the values at the bit
+/// from the highest one, then the values are stably partitioned by the bit, 0s first, for the next level.
+pub struct WaveletTree {
+    levels: Vec<Level>, // from the highest bit
+    len: usize,
+}
+
+struct Level {
+    bits: RankSelect,
+    zeros: usize,
+}
+
+impl Level {
+    /// map the index on this level to the next level by the bit
+    fn next(&self, index: usize, bit: bool) -> usize {
+        if bit {
+            self.zeros + self.bits.rank1(index)
+        } else {
+            self.bits.rank0(index)
+        }
+    }
+}
+
+impl WaveletTree {
+    pub fn new(values: &[u64]) -> Self {
+        let max = values.iter().copied().max().unwrap_or(0);
+        let height = (64 - max.leading_zeros() as usize).max(1);
+
+        let mut current = values.to_vec();
+        let mut levels = Vec::with_capacity(height);
+
+        for bit in (0..height).rev() {
+            let bits: BitVec = current.iter().map(|value| value >> bit & 1 == 1).collect();
+            let (zeros, ones): (Vec<_>, Vec<_>) =
+                current.iter().partition(|value| *value >> bit & 1 == 0);
+
+            levels.push(Level {
+                bits: RankSelect::new(bits),
+                zeros: zeros.len(),
+            });
+
+            current = zeros;
+            current.extend(ones);
+        }
+
+        Self {
+            levels,
+            len: values.len(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// get the bit of the value for the level
+    fn bit(&self, value: u64, level: usize) -> bool {
+        value >> (self.levels.len() - 1 - level) & 1 == 1
+    }
+
+    /// get the value at the index
+    pub fn get(&self, mut index: usize) -> u64 {
+        assert!(index < self.len, "the index is out of range");
+
+        let mut value = 0;
+
+        for level in &self.levels {
+            let bit = level.bits.test(index);
+            value = value << 1 | bit as u64;
+            index = level.next(index, bit);
+        }
+
+        value
+    }
+
+    /// get the number of the symbol in [0, index)
+    pub fn rank(&self, symbol: u64, index: usize) -> usize {
+        assert!(index <= self.len, "the index is out of range");
+
+        if symbol.checked_shr(self.levels.len() as u32).unwrap_or(0) != 0 {
+            return 0;
+        }
+
+        let (mut start, mut end) = (0, index);
+
+        for (i, level) in self.levels.iter().enumerate() {
+            let bit = self.bit(symbol, i);
+            start = level.next(start, bit);
+            end = level.next(end, bit);
+        }
+
+        end - start
+    }
+
+    /// get the index of the k-th(from 0) symbol, or None if there are not more than k symbols
+    pub fn select(&self, symbol: u64, k: usize) -> Option<usize> {
+        if self.rank(symbol, self.len) <= k {
+            return None;
+        }
+
+        // the position on the last level, where the same values are contiguous
+        let mut index = self.levels.iter().enumerate().fold(0, |start, (i, level)| {
+            level.next(start, self.bit(symbol, i))
+        }) + k;
+
+        for (i, level) in self.levels.iter().enumerate().rev() {
+            index = if self.bit(symbol, i) {
+                level.bits.select1(index - level.zeros).unwrap()
+            } else {
+                level.bits.select0(index).unwrap()
+            };
+        }
+
+        Some(index)
+    }
+
+    /// get the k-th(from 0) smallest value in the range, or None if the range has not more than k values
+    pub fn quantile(&self, range: Range<usize>, mut k: usize) -> Option<u64> {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "the range is out of range"
+        );
+
+        if k >= range.end - range.start {
+            return None;
+        }
+
+        let (mut start, mut end) = (range.start, range.end);
+        let mut value = 0;
+
+        for level in &self.levels {
+            let zeros = level.bits.rank0(end) - level.bits.rank0(start);
+            let bit = k >= zeros;
+
+            if bit {
+                k -= zeros;
+            }
+
+            value = value << 1 | bit as u64;
+            start = level.next(start, bit);
+            end = level.next(end, bit);
+        }
+
+        Some(value)
+    }
+}
@@ -0,0 +1,464 @@
+use std::{cmp::Ordering, fmt::Debug, mem};
+
+use crate::map::SequentialMap;
+
+/// the parameters of the balance from "Balancing weight-balanced trees"(Hirai and Yamamoto)
+///
+/// The weight of a subtree is its size + 1.
+/// Siblings are balanced if DELTA * (weight of one) >= (weight of the other).
+/// On rebalancing, single rotation is used if (weight of inner grandchild) < GAMMA * (weight of outer grandchild).
+const DELTA: usize = 3;
+const GAMMA: usize = 2;
+
+/// weight-balanced(BB[α]) tree
+pub struct WBTree<K, V> {
+    root: Subtree<K, V>,
+}
+
+type Subtree<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    size: usize,
+    left: Subtree<K, V>,
+    right: Subtree<K, V>,
+}
+
+/// get the size of the subtree
+fn size<K, V>(tree: &Subtree<K, V>) -> usize {
+    tree.as_ref().map_or(0, |node| node.size)
+}
+
+/// check that the weight of a is not too small compared to the weight of b
+fn is_balanced(a: usize, b: usize) -> bool {
+    DELTA * (a + 1) > b
+}
+
+/// check that the single rotation is enough to balance, with the sizes of the inner and outer grandchilds
+fn is_single(inner: usize, outer: usize) -> bool {
+    inner + 1 < GAMMA * (outer + 1)
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: V) -> Self {
+        Self {
+            key,
+            value,
+            size: 1,
+            left: None,
+            right: None,
+        }
+    }
+
+    /// renew the size of the node from the childs
+    fn renew(&mut self) {
+        self.size = size(&self.left) + size(&self.right) + 1;
+    }
+
+    /// rotate left the node, then return new parent(old right child)
+    fn rotate_left(mut node: Box<Self>) -> Box<Self> {
+        let mut right = node.right.take().unwrap();
+        node.right = right.left.take();
+        node.renew();
+        right.left = Some(node);
+        right.renew();
+        right
+    }
+
+    /// rotate right the node, then return new parent(old left child)
+    fn rotate_right(mut node: Box<Self>) -> Box<Self> {
+        let mut left = node.left.take().unwrap();
+        node.left = left.right.take();
+        node.renew();
+        left.right = Some(node);
+        left.renew();
+        left
+    }
+
+    /// rebalance the node by the rule of weight-balanced tree, then return new parent
+    ///
+    /// The childs should be balanced, and the node should be unbalanced by at most one insertion or removal.
+    fn balance(mut node: Box<Self>) -> Box<Self> {
+        let (left_size, right_size) = (size(&node.left), size(&node.right));
+
+        if !is_balanced(left_size, right_size) {
+            let right = node.right.as_ref().unwrap();
+
+            if !is_single(size(&right.left), size(&right.right)) {
+                node.right = Some(Node::rotate_right(node.right.take().unwrap()));
+            }
+
+            Node::rotate_left(node)
+        } else if !is_balanced(right_size, left_size) {
+            let left = node.left.as_ref().unwrap();
+
+            if !is_single(size(&left.right), size(&left.left)) {
+                node.left = Some(Node::rotate_left(node.left.take().unwrap()));
+            }
+
+            Node::rotate_right(node)
+        } else {
+            node.renew();
+            node
+        }
+    }
+
+    /// join two trees with the middle node whose key is between them, then return new root
+    ///
+    /// All keys of left should be less than the key of mid, and all keys of right should be greater.
+    fn join(left: Subtree<K, V>, mut mid: Box<Self>, right: Subtree<K, V>) -> Box<Self> {
+        let (left_size, right_size) = (size(&left), size(&right));
+
+        if !is_balanced(left_size, right_size) {
+            let mut right = right.unwrap();
+            right.left = Some(Node::join(left, mid, right.left.take()));
+            Node::balance(right)
+        } else if !is_balanced(right_size, left_size) {
+            let mut left = left.unwrap();
+            left.right = Some(Node::join(left.right.take(), mid, right));
+            Node::balance(left)
+        } else {
+            mid.left = left;
+            mid.right = right;
+            mid.renew();
+            mid
+        }
+    }
+
+    /// join two trees whose keys are all less(left) and greater(right), then return new root
+    fn join2(left: Subtree<K, V>, right: Subtree<K, V>) -> Subtree<K, V> {
+        match right {
+            Some(right) => {
+                let (right, mid) = Node::remove_first(right);
+                Some(Node::join(left, mid, right))
+            }
+            None => left,
+        }
+    }
+
+    /// remove the node that has the smallest key on the tree, then return (new root, the node)
+    fn remove_first(mut node: Box<Self>) -> (Subtree<K, V>, Box<Self>) {
+        match node.left.take() {
+            Some(left) => {
+                let (left, first) = Node::remove_first(left);
+                node.left = left;
+                (Some(Node::balance(node)), first)
+            }
+            None => {
+                let right = node.right.take();
+                (right, node)
+            }
+        }
+    }
+
+    /// remove the node that has the greatest key on the tree, then return (new root, the node)
+    fn remove_last(mut node: Box<Self>) -> (Subtree<K, V>, Box<Self>) {
+        match node.right.take() {
+            Some(right) => {
+                let (right, last) = Node::remove_last(right);
+                node.right = right;
+                (Some(Node::balance(node)), last)
+            }
+            None => {
+                let left = node.left.take();
+                (left, node)
+            }
+        }
+    }
+}
+
+impl<K: Ord, V> Node<K, V> {
+    fn insert(tree: &mut Subtree<K, V>, node: Box<Self>) -> Result<(), Box<Self>> {
+        let current = match tree {
+            Some(current) => current,
+            None => {
+                *tree = Some(node);
+                return Ok(());
+            }
+        };
+
+        match node.key.cmp(&current.key) {
+            Ordering::Less => Node::insert(&mut current.left, node)?,
+            Ordering::Equal => return Err(node),
+            Ordering::Greater => Node::insert(&mut current.right, node)?,
+        }
+
+        *tree = Some(Node::balance(tree.take().unwrap()));
+        Ok(())
+    }
+
+    fn remove(tree: &mut Subtree<K, V>, key: &K) -> Result<Box<Self>, ()> {
+        let current = tree.as_mut().ok_or(())?;
+
+        let node = match key.cmp(&current.key) {
+            Ordering::Less => Node::remove(&mut current.left, key)?,
+            Ordering::Greater => Node::remove(&mut current.right, key)?,
+            Ordering::Equal => {
+                let mut node = tree.take().unwrap();
+
+                // replace the node with the neighbor from the heavier subtree
+                *tree = match (node.left.take(), node.right.take()) {
+                    (None, right) => right,
+                    (left, None) => left,
+                    (Some(left), Some(right)) => {
+                        let mid = if left.size > right.size {
+                            let (left, mut mid) = Node::remove_last(left);
+                            mid.left = left;
+                            mid.right = Some(right);
+                            mid
+                        } else {
+                            let (right, mut mid) = Node::remove_first(right);
+                            mid.left = Some(left);
+                            mid.right = right;
+                            mid
+                        };
+
+                        Some(Node::balance(mid))
+                    }
+                };
+
+                return Ok(node);
+            }
+        };
+
+        *tree = Some(Node::balance(tree.take().unwrap()));
+        Ok(node)
+    }
+
+    /// split the tree into (the tree less than key, the node of key, the tree greater than key)
+    fn split(tree: Subtree<K, V>, key: &K) -> (Subtree<K, V>, Subtree<K, V>, Subtree<K, V>) {
+        let mut node = match tree {
+            Some(node) => node,
+            None => return (None, None, None),
+        };
+
+        let (left, right) = (node.left.take(), node.right.take());
+
+        match key.cmp(&node.key) {
+            Ordering::Less => {
+                let (less, found, greater) = Node::split(left, key);
+                (less, found, Some(Node::join(greater, node, right)))
+            }
+            Ordering::Equal => {
+                node.renew();
+                (left, Some(node), right)
+            }
+            Ordering::Greater => {
+                let (less, found, greater) = Node::split(right, key);
+                (Some(Node::join(left, node, less)), found, greater)
+            }
+        }
+    }
+}
+
+impl<K: Ord + Debug, V> Node<K, V> {
+    /// check the invariants of the subtree whose keys should be in (from, to), then return the size
+    fn validate(&self, from: Option<&K>, to: Option<&K>) -> Result<usize, String> {
+        if from.map_or(false, |from| *from >= self.key) || to.map_or(false, |to| self.key >= *to) {
+            return Err(format!(
+                "The key {:?} is out of the range ({:?}, {:?}).",
+                self.key, from, to
+            ));
+        }
+
+        let left_size = match &self.left {
+            Some(left) => left.validate(from, Some(&self.key))?,
+            None => 0,
+        };
+
+        let right_size = match &self.right {
+            Some(right) => right.validate(Some(&self.key), to)?,
+            None => 0,
+        };
+
+        if !is_balanced(left_size, right_size) || !is_balanced(right_size, left_size) {
+            return Err(format!(
+                "The key {:?} is unbalanced with the sizes ({}, {}).",
+                self.key, left_size, right_size
+            ));
+        }
+
+        let size = left_size + right_size + 1;
+        if self.size != size {
+            return Err(format!(
+                "The size of the key {:?} is {}, but it should be {}.",
+                self.key, self.size, size
+            ));
+        }
+
+        Ok(size)
+    }
+}
+
+impl<K: Ord + Debug, V> WBTree<K, V> {
+    /// check the ordering of the keys, and the weight balance and the size of every node
+    ///
+    /// If the tree is valid, return Ok(()), or Err(the description of the first violation).
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(node) = &self.root {
+            node.validate(None, None)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<K, V> Default for WBTree<K, V> {
+    fn default() -> Self {
+        Self { root: None }
+    }
+}
+
+impl<K, V> WBTree<K, V> {
+    /// get the number of the (key, value)s on the tree
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// get the height of the tree
+    pub fn get_height(&self) -> usize {
+        fn height<K, V>(tree: &Subtree<K, V>) -> usize {
+            tree.as_ref()
+                .map_or(0, |node| height(&node.left).max(height(&node.right)) + 1)
+        }
+
+        height(&self.root)
+    }
+
+    /// get the k-th smallest (key, value) on the tree (k starts from 0)
+    ///
+    /// If k >= len, return None.
+    pub fn select(&self, mut k: usize) -> Option<(&K, &V)> {
+        let mut current = self.root.as_ref();
+
+        while let Some(node) = current {
+            let left_size = size(&node.left);
+
+            match k.cmp(&left_size) {
+                Ordering::Less => current = node.left.as_ref(),
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                Ordering::Greater => {
+                    k -= left_size + 1;
+                    current = node.right.as_ref();
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<K: Ord, V> WBTree<K, V> {
+    /// get the number of the keys on the tree that are less than the key
+    pub fn rank(&self, key: &K) -> usize {
+        let mut current = self.root.as_ref();
+        let mut rank = 0;
+
+        while let Some(node) = current {
+            if *key <= node.key {
+                current = node.left.as_ref();
+            } else {
+                rank += size(&node.left) + 1;
+                current = node.right.as_ref();
+            }
+        }
+
+        rank
+    }
+
+    /// split the tree into two at the key, then return the tree that has the keys greater than or equal to the key
+    pub fn split_off(&mut self, key: &K) -> Self {
+        let (less, found, greater) = Node::split(self.root.take(), key);
+        self.root = less;
+
+        let root = match found {
+            Some(node) => Some(Node::join(None, node, greater)),
+            None => greater,
+        };
+
+        Self { root }
+    }
+
+    /// join the other tree whose keys are all greater than the keys of this tree
+    ///
+    /// If success, return Ok(()).
+    /// If fail since the keys are overlapped, return Err(other).
+    pub fn join(&mut self, mut other: Self) -> Result<(), Self> {
+        if !self.is_empty() && !other.is_empty() {
+            let (last, _) = self.select(self.len() - 1).unwrap();
+            let (first, _) = other.select(0).unwrap();
+
+            if last >= first {
+                return Err(other);
+            }
+        }
+
+        self.root = Node::join2(self.root.take(), other.root.take());
+        Ok(())
+    }
+}
+
+impl<K, V> SequentialMap<K, V> for WBTree<K, V>
+where
+    K: Ord + Clone,
+{
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(&mut self, key: &K, value: V) -> Result<(), V> {
+        let node = Box::new(Node::new(key.clone(), value));
+
+        Node::insert(&mut self.root, node).map_err(|node| node.value)
+    }
+
+    fn lookup(&self, key: &K) -> Option<&V> {
+        let mut current = self.root.as_ref();
+
+        while let Some(node) = current {
+            match key.cmp(&node.key) {
+                Ordering::Less => current = node.left.as_ref(),
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Greater => current = node.right.as_ref(),
+            }
+        }
+
+        None
+    }
+
+    fn lookup_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut current = self.root.as_mut();
+
+        while let Some(node) = current {
+            match key.cmp(&node.key) {
+                Ordering::Less => current = node.left.as_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+                Ordering::Greater => current = node.right.as_mut(),
+            }
+        }
+
+        None
+    }
+
+    fn remove(&mut self, key: &K) -> Result<V, ()> {
+        Node::remove(&mut self.root, key).map(|node| node.value)
+    }
+}
+
+impl<K, V> Drop for WBTree<K, V> {
+    fn drop(&mut self) {
+        // drop the nodes iteratively not to overflow the stack on a large tree
+        let mut stack: Vec<Box<Node<K, V>>> = self.root.take().into_iter().collect();
+
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+            mem::drop(node);
+        }
+    }
+}
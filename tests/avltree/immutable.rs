@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use cds::avltree::ImmutableAVLTree;
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_immutable_avl_tree() {
+    let num = 64;
+    let mut tree: ImmutableAVLTree<i32, i32> = ImmutableAVLTree::new();
+
+    for i in 0..num {
+        tree = tree.insert(&i, i).unwrap();
+    }
+
+    for i in 0..num {
+        assert_eq!(tree.insert(&i, i).err(), Some(i));
+    }
+
+    assert_eq!(tree.len(), num as usize);
+    assert_eq!(tree.get_height(), f32::log2(num as f32) as usize + 1);
+
+    for i in 0..num {
+        assert_eq!(tree.lookup(&i), Some(&i));
+    }
+
+    for i in 0..num {
+        let (next, value) = tree.remove(&i).unwrap();
+        assert_eq!(value, i);
+        assert!(next.remove(&i).is_err());
+        tree = next;
+    }
+
+    assert!(tree.is_empty());
+}
+
+#[test]
+fn test_immutable_avl_tree_snapshot() {
+    let num = 100;
+    let mut versions = vec![ImmutableAVLTree::new()];
+
+    for i in 0..num {
+        let tree = versions.last().unwrap().insert(&i, i * 2).unwrap();
+        versions.push(tree);
+    }
+
+    for i in 0..num {
+        let (tree, value) = versions.last().unwrap().remove(&i).unwrap();
+        assert_eq!(value, i * 2);
+        versions.push(tree);
+    }
+
+    // the old versions are not changed by the later insert and remove
+    for (v, tree) in versions.iter().enumerate() {
+        let v = v as i32;
+        let range = if v <= num { 0..v } else { v - num..num };
+
+        assert_eq!(tree.len(), range.len());
+
+        for i in 0..num {
+            if range.contains(&i) {
+                assert_eq!(tree.lookup(&i), Some(&(i * 2)));
+            } else {
+                assert_eq!(tree.lookup(&i), None);
+            }
+        }
+    }
+}
+
+#[test]
+fn stress_immutable_avl_tree() {
+    let iter = 100_000;
+    let mut rng = thread_rng();
+    let mut tree: ImmutableAVLTree<u16, u32> = ImmutableAVLTree::new();
+    let mut ref_map = BTreeMap::new();
+    let mut snapshots = Vec::new();
+
+    for i in 0..iter {
+        let key = rng.gen_range(0..1024);
+        let value: u32 = rng.gen();
+
+        match rng.gen_range(0..3) {
+            0 => match tree.insert(&key, value) {
+                Ok(next) => {
+                    assert_eq!(ref_map.insert(key, value), None);
+                    tree = next;
+                }
+                Err(v) => {
+                    assert_eq!(v, value);
+                    assert!(ref_map.contains_key(&key));
+                }
+            },
+            1 => assert_eq!(tree.lookup(&key), ref_map.get(&key)),
+            _ => match tree.remove(&key) {
+                Ok((next, v)) => {
+                    assert_eq!(ref_map.remove(&key), Some(v));
+                    tree = next;
+                }
+                Err(()) => assert!(!ref_map.contains_key(&key)),
+            },
+        }
+
+        assert_eq!(tree.len(), ref_map.len());
+
+        if i % 10_000 == 0 {
+            snapshots.push((tree.clone(), ref_map.clone()));
+        }
+    }
+
+    for (tree, ref_map) in snapshots {
+        assert_eq!(tree.len(), ref_map.len());
+
+        for (key, value) in ref_map {
+            assert_eq!(tree.lookup(&key), Some(&value));
+        }
+    }
+}
@@ -1,8 +1,13 @@
+mod immutable;
 mod rwlock;
 mod seqlock;
 
-use crate::util::map::stress_sequential;
-use cds::{avltree::AVLTree, map::SequentialMap};
+use crate::util::map::stress_sequential_with_check;
+use cds::{
+    avltree::{AVLTree, Entry},
+    map::SequentialMap,
+};
+use rand::{prelude::SliceRandom, thread_rng};
 
 #[test]
 fn test_insert_lookup_avl_tree() {
@@ -69,7 +74,414 @@ fn test_remove_avl_tree() {
     assert_eq!(avl.lookup(&6), Some(&6));
 }
 
+#[test]
+fn test_lookup_mut_avl_tree() {
+    let mut avl: AVLTree<i32, i32> = AVLTree::new();
+
+    for i in 0..1024 {
+        assert_eq!(avl.insert(&i, i), Ok(()));
+    }
+
+    for i in 0..1024 {
+        *avl.lookup_mut(&i).unwrap() *= 2;
+    }
+
+    assert_eq!(avl.lookup_mut(&1024), None);
+
+    for i in 0..1024 {
+        assert_eq!(avl.lookup(&i), Some(&(i * 2)));
+    }
+}
+
+#[test]
+fn test_from_iter_avl_tree() {
+    let mut avl: AVLTree<i32, i32> = (0..1024).map(|i| (i, i)).collect();
+
+    avl.extend((512..2048).map(|i| (i, -i)));
+
+    for i in 0..512 {
+        assert_eq!(avl.lookup(&i), Some(&i));
+    }
+
+    for i in 512..2048 {
+        assert_eq!(avl.lookup(&i), Some(&-i));
+    }
+}
+
+#[test]
+fn test_select_rank_avl_tree() {
+    let mut avl: AVLTree<i32, i32> = AVLTree::new();
+    let mut rng = thread_rng();
+
+    let mut keys: Vec<i32> = (0..1024).map(|i| i * 2).collect();
+    keys.shuffle(&mut rng);
+
+    for key in &keys {
+        assert_eq!(avl.insert(key, -key), Ok(()));
+    }
+
+    // remove a half of keys to check the sizes are maintained by removing
+    for key in keys.drain(..512) {
+        assert_eq!(avl.remove(&key), Ok(-key));
+    }
+
+    keys.sort();
+    assert_eq!(avl.len(), keys.len());
+
+    for (k, key) in keys.iter().enumerate() {
+        assert_eq!(avl.select(k), Some((key, &-key)));
+        assert_eq!(avl.rank(key), k);
+        assert_eq!(avl.rank(&(key + 1)), k + 1);
+    }
+
+    assert_eq!(avl.select(keys.len()), None);
+    assert_eq!(avl.rank(&-1), 0);
+}
+
+#[test]
+fn test_split_off_join_avl_tree() {
+    let num = 1024;
+    let mut rng = thread_rng();
+
+    for at in [-1, 0, 1, 2, 511, 512, 1023, 2046, 2047, 2048, 3000] {
+        let mut keys: Vec<i32> = (0..num).map(|i| i * 2).collect();
+        keys.shuffle(&mut rng);
+
+        let mut left: AVLTree<i32, i32> = AVLTree::new();
+
+        for key in &keys {
+            assert_eq!(left.insert(key, *key), Ok(()));
+        }
+
+        let right = left.split_off(&at);
+        let expected = keys.iter().filter(|key| **key < at).count();
+
+        assert_eq!(left.len(), expected);
+        assert_eq!(right.len(), num as usize - expected);
+        assert_eq!(left.validate(), Ok(()));
+        assert_eq!(right.validate(), Ok(()));
+
+        // the height of AVL tree is at most 1.44 * log2(n + 2)
+        assert!(left.get_height() as f32 <= 1.44 * f32::log2(left.len() as f32 + 2.0));
+        assert!(right.get_height() as f32 <= 1.44 * f32::log2(right.len() as f32 + 2.0));
+
+        for key in &keys {
+            if *key < at {
+                assert_eq!(left.lookup(key), Some(key));
+                assert_eq!(right.lookup(key), None);
+            } else {
+                assert_eq!(left.lookup(key), None);
+                assert_eq!(right.lookup(key), Some(key));
+            }
+        }
+
+        // cannot join since the keys are overlapped
+        if !left.is_empty() {
+            let mut overlapped = AVLTree::new();
+            assert_eq!(overlapped.insert(&-2, -2), Ok(()));
+
+            let overlapped = left.join(overlapped).unwrap_err();
+            assert_eq!(overlapped.lookup(&-2), Some(&-2));
+            assert_eq!(left.len(), expected);
+        }
+
+        assert!(left.join(right).is_ok());
+        assert_eq!(left.len(), num as usize);
+        assert_eq!(left.validate(), Ok(()));
+        assert!(left.get_height() as f32 <= 1.44 * f32::log2(left.len() as f32 + 2.0));
+
+        for key in &keys {
+            assert_eq!(left.lookup(key), Some(key));
+        }
+    }
+}
+
+#[test]
+fn test_from_sorted_iter_avl_tree() {
+    for num in [0, 1, 2, 3, 7, 8, 1000, 65535, 65536] {
+        let mut avl: AVLTree<i32, i32> = AVLTree::from_sorted_iter((0..num).map(|i| (i, i)));
+
+        // perfectly balanced: the height is ceil(log2(num + 1))
+        assert_eq!(avl.len(), num as usize);
+        assert_eq!(
+            avl.get_height(),
+            f64::log2(num as f64 + 1.0).ceil() as usize
+        );
+
+        for i in 0..num {
+            assert_eq!(avl.lookup(&i), Some(&i));
+        }
+
+        // the tree still works as usual
+        assert_eq!(avl.insert(&num, num), Ok(()));
+        assert_eq!(avl.remove(&0), Ok(0));
+        assert_eq!(avl.len(), num as usize);
+        assert_eq!(avl.validate(), Ok(()));
+    }
+}
+
+#[test]
+fn test_entry_avl_tree() {
+    let mut avl: AVLTree<i32, i32> = AVLTree::new();
+
+    // count the remainders by 100
+    for i in 0..10000 {
+        *avl.entry(i % 100).or_insert(0) += 1;
+    }
+
+    assert_eq!(avl.len(), 100);
+    assert!(avl.get_height() <= 8);
+
+    for i in 0..100 {
+        assert_eq!(avl.lookup(&i), Some(&100));
+    }
+
+    avl.entry(0).and_modify(|v| *v = -1).or_insert(1);
+    avl.entry(100).and_modify(|v| *v = -1).or_insert(1);
+    assert_eq!(avl.lookup(&0), Some(&-1));
+    assert_eq!(avl.lookup(&100), Some(&1));
+
+    assert_eq!(*avl.entry(101).or_default(), 0);
+    assert_eq!(*avl.entry(102).or_insert_with(|| 102), 102);
+    assert_eq!(avl.entry(102).key(), &102);
+
+    match avl.entry(50) {
+        Entry::Occupied(mut entry) => {
+            assert_eq!(entry.get(), &100);
+            assert_eq!(entry.insert(50), 100);
+            assert_eq!(entry.remove(), 50);
+        }
+        Entry::Vacant(_) => panic!(),
+    }
+
+    match avl.entry(50) {
+        Entry::Occupied(_) => panic!(),
+        Entry::Vacant(entry) => assert_eq!(entry.into_key(), 50),
+    }
+
+    assert_eq!(avl.lookup(&50), None);
+    assert_eq!(avl.len(), 102);
+
+    for k in 0..avl.len() {
+        let (key, _) = avl.select(k).unwrap();
+        assert_eq!(avl.rank(key), k);
+    }
+}
+
+#[test]
+fn test_first_last_avl_tree() {
+    let mut avl: AVLTree<i32, i32> = AVLTree::new();
+    let mut rng = thread_rng();
+
+    assert_eq!(avl.first(), None);
+    assert_eq!(avl.last(), None);
+    assert_eq!(avl.pop_first(), None);
+    assert_eq!(avl.pop_last(), None);
+
+    let mut keys: Vec<i32> = (0..1000).collect();
+    keys.shuffle(&mut rng);
+
+    for key in &keys {
+        assert_eq!(avl.insert(key, -key), Ok(()));
+    }
+
+    assert_eq!(avl.first(), Some((&0, &0)));
+    assert_eq!(avl.last(), Some((&999, &-999)));
+
+    for i in 0..500 {
+        assert_eq!(avl.pop_first(), Some((i, -i)));
+        assert_eq!(avl.pop_last(), Some((999 - i, i - 999)));
+        assert_eq!(avl.len(), 998 - 2 * i as usize);
+    }
+
+    assert_eq!(avl.pop_first(), None);
+    assert_eq!(avl.get_height(), 0);
+}
+
+#[test]
+fn test_display_avl_tree() {
+    let mut avl: AVLTree<i32, i32> = AVLTree::new();
+    assert_eq!(avl.to_string(), "Root: null\n");
+
+    for i in [3, 2, 4, 1] {
+        assert_eq!(avl.insert(&i, i * 10), Ok(()));
+    }
+
+    assert_eq!(
+        avl.to_string(),
+        "Root: (3, 30) height: 3, factor: 1\n    \
+             L: (2, 20) height: 2, factor: 1\n        \
+                 L: (1, 10) height: 1, factor: 0\n    \
+             R: (4, 40) height: 1, factor: 0\n"
+    );
+
+    assert_eq!(
+        avl.to_dot(),
+        "digraph AVLTree {\n    \
+             node [shape=box];\n    \
+             n0 [label=\"3: 30\\nheight: 3, factor: 1\"];\n    \
+             n1 [label=\"2: 20\\nheight: 2, factor: 1\"];\n    \
+             n2 [label=\"1: 10\\nheight: 1, factor: 0\"];\n    \
+             n1 -> n2 [label=\"L\"];\n    \
+             n0 -> n1 [label=\"L\"];\n    \
+             n3 [label=\"4: 40\\nheight: 1, factor: 0\"];\n    \
+             n0 -> n3 [label=\"R\"];\n\
+         }\n"
+    );
+}
+
+#[test]
+fn test_floor_ceiling_avl_tree() {
+    let mut avl: AVLTree<i32, i32> = AVLTree::new();
+
+    assert_eq!(avl.floor(&0), None);
+    assert_eq!(avl.ceiling(&0), None);
+
+    // 0, 10, 20, ..., 990
+    let mut keys: Vec<i32> = (0..100).map(|i| i * 10).collect();
+    keys.shuffle(&mut thread_rng());
+
+    for key in &keys {
+        assert_eq!(avl.insert(key, -key), Ok(()));
+    }
+
+    for query in -5..1000i32 {
+        let floor = query.div_euclid(10) * 10;
+        let ceiling = floor + if query % 10 == 0 { 0 } else { 10 };
+
+        if floor >= 0 {
+            assert_eq!(avl.floor(&query), Some((&floor, &-floor)));
+        } else {
+            assert_eq!(avl.floor(&query), None);
+        }
+
+        if ceiling < 1000 {
+            assert_eq!(avl.ceiling(&query), Some((&ceiling, &-ceiling)));
+        } else {
+            assert_eq!(avl.ceiling(&query), None);
+        }
+    }
+}
+
+#[test]
+fn test_retain_drain_avl_tree() {
+    let mut avl: AVLTree<i32, i32> = AVLTree::new();
+
+    let mut keys: Vec<i32> = (0..1000).collect();
+    keys.shuffle(&mut thread_rng());
+
+    for key in &keys {
+        assert_eq!(avl.insert(key, *key), Ok(()));
+    }
+
+    // remain the multiples of 3, and negate them
+    avl.retain(|key, value| {
+        *value = -*value;
+        key % 3 == 0
+    });
+
+    assert_eq!(avl.len(), 334);
+    assert_eq!(avl.validate(), Ok(()));
+
+    for key in 0..1000 {
+        if key % 3 == 0 {
+            assert_eq!(avl.lookup(&key), Some(&-key));
+        } else {
+            assert_eq!(avl.lookup(&key), None);
+        }
+    }
+
+    let mut drain = avl.drain();
+    assert_eq!(drain.len(), 334);
+    assert_eq!(drain.next(), Some((0, 0)));
+    assert_eq!(drain.next(), Some((3, -3)));
+    assert_eq!(drain.len(), 332);
+    assert_eq!(
+        drain.collect::<Vec<_>>(),
+        (2..334).map(|i| (i * 3, -i * 3)).collect::<Vec<_>>()
+    );
+
+    assert!(avl.is_empty());
+    assert_eq!(avl.lookup(&0), None);
+
+    // the tree still works after draining partially
+    assert_eq!(avl.insert(&1, 1), Ok(()));
+    assert_eq!(avl.insert(&2, 2), Ok(()));
+    assert_eq!(avl.drain().next(), Some((1, 1)));
+    assert!(avl.is_empty());
+}
+
+#[test]
+fn test_iter_avl_tree() {
+    let mut avl: AVLTree<i32, i32> = AVLTree::new();
+    assert_eq!(avl.iter().next(), None);
+    assert_eq!(avl.iter_mut().next(), None);
+
+    let mut keys: Vec<i32> = (0..1000).collect();
+    keys.shuffle(&mut thread_rng());
+
+    for key in &keys {
+        assert_eq!(avl.insert(key, *key), Ok(()));
+    }
+
+    assert_eq!(avl.iter().len(), 1000);
+    assert!(avl
+        .iter()
+        .map(|(k, v)| (*k, *v))
+        .eq((0..1000).map(|i| (i, i))));
+
+    for (key, value) in avl.iter_mut() {
+        *value += key;
+    }
+
+    avl.map_values_in_place(|_, value| *value += 1);
+
+    for (key, value) in &mut avl {
+        assert_eq!(*value, key * 2 + 1);
+    }
+
+    assert!((&avl)
+        .into_iter()
+        .map(|(_, v)| *v)
+        .eq((0..1000).map(|i| i * 2 + 1)));
+    assert_eq!(avl.validate(), Ok(()));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_avl_tree() {
+    use std::collections::BTreeMap;
+
+    let mut keys: Vec<i32> = (0..1000).collect();
+    keys.shuffle(&mut thread_rng());
+
+    let mut avl: AVLTree<i32, i32> = AVLTree::new();
+    for key in keys {
+        assert_eq!(avl.insert(&key, -key), Ok(()));
+    }
+
+    // same format as the std map
+    let json = serde_json::to_string(&avl).unwrap();
+    let std_map: BTreeMap<i32, i32> = (0..1000).map(|i| (i, -i)).collect();
+    assert_eq!(json, serde_json::to_string(&std_map).unwrap());
+
+    let avl: AVLTree<i32, i32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(avl.validate(), Ok(()));
+    assert!(avl.iter().map(|(k, v)| (*k, *v)).eq(std_map.into_iter()));
+
+    // not sorted, and duplicated keys are overwritten
+    let avl: AVLTree<i32, i32> =
+        serde_json::from_str(r#"{"3": 3, "1": 1, "2": 2, "1": 10}"#).unwrap();
+    assert_eq!(avl.validate(), Ok(()));
+    assert!(avl
+        .iter()
+        .map(|(k, v)| (*k, *v))
+        .eq(vec![(1, 10), (2, 2), (3, 3)]));
+}
+
 #[test]
 fn stress_avl_tree() {
-    stress_sequential::<String, AVLTree<_, _>>(100_000);
+    stress_sequential_with_check::<String, AVLTree<_, _>, _>(100_000, |tree| {
+        tree.validate().unwrap()
+    });
 }
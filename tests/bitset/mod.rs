@@ -0,0 +1,114 @@
+mod rankselect;
+
+use cds::bitset::BitVec;
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_bitvec() {
+    let mut vec = BitVec::new();
+
+    for i in 0..100 {
+        vec.push(i % 3 == 0);
+    }
+
+    assert_eq!(vec.len(), 100);
+    assert!(vec.test(0));
+    assert!(!vec.test(1));
+    assert_eq!(vec.count_ones(), 34);
+    assert_eq!(vec.count_zeros(), 66);
+
+    vec.set(1);
+    vec.clear(0);
+    vec.toggle(2);
+    assert_eq!(
+        format!("{:?}", vec.iter().take(6).collect::<BitVec>()),
+        "011100"
+    );
+
+    assert_eq!(vec.rank1(0), 0);
+    assert_eq!(vec.rank1(4), 3);
+    assert_eq!(vec.rank0(4), 1);
+    assert_eq!(vec.rank1(100), vec.count_ones());
+
+    assert_eq!(vec.pop(), Some(true));
+    assert_eq!(vec.len(), 99);
+
+    vec.resize(200, true);
+    assert_eq!(vec.count_ones(), 34 + 101);
+
+    vec.resize(64, false);
+    assert_eq!(vec.as_words().len(), 1);
+    assert_eq!(vec.ones().count(), vec.count_ones());
+
+    vec.fill(true);
+    assert_eq!(vec.count_ones(), 64);
+    assert_eq!(vec, BitVec::from_elem(64, true));
+}
+
+#[test]
+fn test_bitvec_ops() {
+    let a: BitVec = [true, true, false, false].iter().copied().collect();
+    let b: BitVec = [true, false, true, false, true].iter().copied().collect();
+
+    assert_eq!(format!("{:?}", &a & &b), "10000");
+    assert_eq!(format!("{:?}", &a | &b), "11101");
+    assert_eq!(format!("{:?}", &a ^ &b), "01101");
+
+    let mut c = b.clone();
+    c ^= &b;
+    assert_eq!(c, BitVec::from_elem(5, false));
+}
+
+#[test]
+fn stress_bitvec() {
+    let mut rng = thread_rng();
+    let mut vec = BitVec::new();
+    let mut bits: Vec<bool> = Vec::new();
+
+    for _ in 0..100_000 {
+        match rng.gen_range(0..10) {
+            0..=4 => {
+                let bit = rng.gen();
+                vec.push(bit);
+                bits.push(bit);
+            }
+            5 => assert_eq!(vec.pop(), bits.pop()),
+            6..=8 if !bits.is_empty() => {
+                let index = rng.gen_range(0..bits.len());
+                let bit = rng.gen();
+                vec.assign(index, bit);
+                bits[index] = bit;
+            }
+            _ => {
+                let len = rng.gen_range(0..bits.len() + 100);
+                let bit = rng.gen();
+                vec.resize(len, bit);
+                bits.resize(len, bit);
+            }
+        }
+    }
+
+    assert!(vec.iter().eq(bits.iter().copied()));
+    assert!(vec
+        .ones()
+        .eq(bits.iter().enumerate().filter(|(_, b)| **b).map(|(i, _)| i)));
+
+    for index in (0..=bits.len()).step_by(7) {
+        assert_eq!(
+            vec.rank1(index),
+            bits[..index].iter().filter(|b| **b).count()
+        );
+    }
+
+    let other: BitVec = (0..bits.len() / 2).map(|_| rng.gen::<bool>()).collect();
+    let and = &vec & &other;
+    let or = &vec | &other;
+    let xor = &vec ^ &other;
+
+    for (index, &a) in bits.iter().enumerate() {
+        let b = index < other.len() && other.test(index);
+        assert_eq!(and.test(index), a & b);
+        assert_eq!(or.test(index), a | b);
+        assert_eq!(xor.test(index), a ^ b);
+    }
+}
@@ -0,0 +1,96 @@
+use cds::bitset::{BitVec, RankSelect};
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_rank_select() {
+    let bits: BitVec = [true, false, false, true, true, false, true]
+        .iter()
+        .copied()
+        .collect();
+    let vec = RankSelect::new(bits);
+
+    assert_eq!(vec.count_ones(), 4);
+    assert_eq!(vec.rank1(0), 0);
+    assert_eq!(vec.rank1(4), 2);
+    assert_eq!(vec.rank0(4), 2);
+    assert_eq!(vec.rank1(7), 4);
+
+    assert_eq!(vec.select1(0), Some(0));
+    assert_eq!(vec.select1(2), Some(4));
+    assert_eq!(vec.select1(3), Some(6));
+    assert_eq!(vec.select1(4), None);
+    assert_eq!(vec.select0(0), Some(1));
+    assert_eq!(vec.select0(2), Some(5));
+    assert_eq!(vec.select0(3), None);
+
+    let empty = RankSelect::new(BitVec::new());
+    assert_eq!(empty.rank1(0), 0);
+    assert_eq!(empty.select1(0), None);
+    assert_eq!(empty.select0(0), None);
+}
+
+#[test]
+fn stress_rank_select() {
+    let mut rng = thread_rng();
+
+    for &(len, density) in &[
+        (100_000, 0.5),
+        (100_000, 0.01),
+        (100_000, 0.99),
+        (4_097, 1.0),
+        (1_000, 0.0),
+    ] {
+        let bits: BitVec = (0..len).map(|_| rng.gen_bool(density)).collect();
+        let vec = RankSelect::from(bits.clone());
+
+        let mut ones = 0;
+
+        for index in 0..len {
+            assert_eq!(vec.rank1(index), ones);
+
+            if bits.test(index) {
+                assert_eq!(vec.select1(ones), Some(index));
+                ones += 1;
+            } else {
+                assert_eq!(vec.select0(index - ones), Some(index));
+            }
+        }
+
+        assert_eq!(vec.rank1(len), ones);
+        assert_eq!(vec.select1(ones), None);
+        assert_eq!(vec.select0(len - ones), None);
+    }
+}
+
+#[test]
+fn test_rank_select_sparse() {
+    let mut rng = thread_rng();
+
+    // the groups spanning 2^20 bits, the sparse subgroups, and the dense ones
+    let len = 3_000_000;
+    let bits: BitVec = (0..len)
+        .map(|index| match index / 1_000_000 {
+            0 => index % 300 == 0,
+            1 => rng.gen_bool(0.01),
+            _ => rng.gen_bool(0.5),
+        })
+        .collect();
+    let vec = RankSelect::new(bits.clone());
+
+    let ones: Vec<usize> = bits.ones().collect();
+    assert_eq!(vec.count_ones(), ones.len());
+
+    for (k, &index) in ones.iter().enumerate() {
+        assert_eq!(vec.select1(k), Some(index));
+    }
+
+    assert_eq!(vec.select1(ones.len()), None);
+
+    // the 0s are sparse at the first part of the inverted vector
+    let inverted: BitVec = (0..len).map(|index| !bits.test(index)).collect();
+    let vec = RankSelect::new(inverted);
+
+    for (k, &index) in ones.iter().enumerate().step_by(7) {
+        assert_eq!(vec.select0(k), Some(index));
+    }
+}
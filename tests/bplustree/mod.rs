@@ -0,0 +1,81 @@
+use std::{collections::BTreeMap, ops::Bound};
+
+use crate::util::map::stress_sequential_with_check;
+use cds::{bplustree::BPlusTree, map::SequentialMap};
+use rand::{prelude::SliceRandom, thread_rng, Rng};
+
+#[test]
+fn test_bplus_tree() {
+    let num = 4096;
+    let mut keys: Vec<i32> = (0..num).collect();
+    keys.shuffle(&mut thread_rng());
+
+    let mut tree: BPlusTree<i32, i32> = BPlusTree::new();
+
+    for &key in &keys {
+        assert_eq!(tree.insert(&key, key), Ok(()));
+    }
+
+    for &key in &keys {
+        assert_eq!(tree.insert(&key, key), Err(key));
+    }
+
+    assert_eq!(tree.len(), num as usize);
+    assert_eq!(tree.validate(), Ok(()));
+
+    for &key in &keys {
+        assert_eq!(tree.lookup(&key), Some(&key));
+        *tree.lookup_mut(&key).unwrap() += 1;
+    }
+
+    keys.shuffle(&mut thread_rng());
+
+    for (i, &key) in keys.iter().enumerate() {
+        assert_eq!(tree.remove(&key), Ok(key + 1));
+        assert_eq!(tree.remove(&key), Err(()));
+
+        if i % 100 == 0 {
+            assert_eq!(tree.validate(), Ok(()));
+        }
+    }
+
+    assert!(tree.is_empty());
+    assert_eq!(tree.validate(), Ok(()));
+}
+
+#[test]
+fn test_range_bplus_tree() {
+    let mut rng = thread_rng();
+    let mut tree: BPlusTree<i32, i32> = BPlusTree::new();
+    let mut ref_map = BTreeMap::new();
+
+    for _ in 0..2000 {
+        let key = rng.gen_range(0..5000);
+
+        if tree.insert(&key, -key).is_ok() {
+            ref_map.insert(key, -key);
+        }
+    }
+
+    assert!(tree.iter().eq(ref_map.iter()));
+
+    for _ in 0..1000 {
+        let (a, b) = (rng.gen_range(-100..5100), rng.gen_range(-100..5100));
+        let (start, end) = (a.min(b), a.max(b));
+
+        assert!(tree.range(start..end).eq(ref_map.range(start..end)));
+        assert!(tree.range(start..=end).eq(ref_map.range(start..=end)));
+        assert!(tree.range(start..).eq(ref_map.range(start..)));
+        assert!(tree.range(..end).eq(ref_map.range(..end)));
+
+        let bounds = (Bound::Excluded(start), Bound::Included(end));
+        assert!(tree.range(bounds).eq(ref_map.range(bounds)));
+    }
+}
+
+#[test]
+fn stress_bplus_tree() {
+    stress_sequential_with_check::<String, BPlusTree<_, _>, _>(100_000, |tree| {
+        tree.validate().unwrap()
+    });
+}
@@ -0,0 +1,45 @@
+use cds::{bst::LockCouplingBST, map::ConcurrentMap};
+
+use crate::util::map::{stress_concurrent, stress_concurrent_as_sequential};
+
+#[test]
+fn test_lock_coupling_bst() {
+    let num = 64;
+    let bst: LockCouplingBST<i32, i32> = LockCouplingBST::new();
+
+    for i in 0..num {
+        assert_eq!(bst.insert(&i, i), Ok(()));
+    }
+
+    for i in 0..num {
+        assert_eq!(bst.insert(&i, i), Err(i));
+    }
+
+    for i in 0..num {
+        assert_eq!(bst.get(&i), Some(i));
+    }
+
+    for i in 0..num {
+        assert_eq!(bst.remove(&i), Ok(i));
+    }
+
+    for i in 0..num {
+        assert_eq!(bst.remove(&i), Err(()));
+    }
+}
+
+#[test]
+fn stress_lock_coupling_bst_sequential() {
+    stress_concurrent_as_sequential::<u8, LockCouplingBST<_, _>>(100_000);
+}
+
+#[test]
+fn stress_lock_coupling_bst_concurrent() {
+    stress_concurrent::<u32, LockCouplingBST<_, _>>(200_000, 16, false);
+}
+
+#[test]
+fn assert_lock_coupling_bst_concurrent() {
+    stress_concurrent::<u8, LockCouplingBST<_, _>>(100_000, 32, true);
+    stress_concurrent::<u64, LockCouplingBST<_, _>>(100_000, 32, true);
+}
@@ -0,0 +1 @@
+mod lockcoupling;
@@ -1,6 +1,7 @@
 use cds::{btree::BTree, map::SequentialMap};
 
 use crate::util::map::{stress_sequential};
+use rand::{prelude::SliceRandom, thread_rng};
 
 #[test]
 fn test_insert_lookup_btree() {
@@ -464,7 +465,108 @@ fn test_remove_btree() {
     }
 }
 
+#[test]
+fn test_lookup_mut_btree() {
+    let num = 1023;
+    let mut tree: BTree<i32, i32> = BTree::new();
+
+    for i in 0..num {
+        assert_eq!(tree.insert(&i, i), Ok(()));
+    }
+
+    for i in 0..num {
+        *tree.lookup_mut(&i).unwrap() += 1;
+    }
+
+    assert_eq!(tree.lookup_mut(&num), None);
+
+    for i in 0..num {
+        assert_eq!(tree.lookup(&i), Some(&(i + 1)));
+    }
+}
+
+#[test]
+fn test_from_iter_btree() {
+    let mut tree: BTree<i32, i32> = (0..1024).map(|i| (i, i)).collect();
+
+    tree.extend((512..2048).map(|i| (i, -i)));
+    tree.assert();
+
+    for i in 0..512 {
+        assert_eq!(tree.lookup(&i), Some(&i));
+    }
+
+    for i in 512..2048 {
+        assert_eq!(tree.lookup(&i), Some(&-i));
+    }
+}
+
+fn insert_remove_btree<const B: usize>() {
+    let num = 2048;
+    let mut keys: Vec<i32> = (0..num).collect();
+    keys.shuffle(&mut thread_rng());
+
+    let mut tree: BTree<i32, i32, B> = BTree::new();
+
+    for &key in &keys {
+        assert_eq!(tree.insert(&key, key), Ok(()));
+    }
+
+    tree.assert();
+    keys.shuffle(&mut thread_rng());
+
+    for &key in &keys {
+        assert_eq!(tree.lookup(&key), Some(&key));
+        assert_eq!(tree.remove(&key), Ok(key));
+        assert_eq!(tree.lookup(&key), None);
+    }
+
+    tree.assert();
+}
+
+#[test]
+fn test_fanout_btree() {
+    insert_remove_btree::<2>();
+    insert_remove_btree::<3>();
+    insert_remove_btree::<4>();
+    insert_remove_btree::<8>();
+    insert_remove_btree::<64>();
+}
+
+fn from_sorted_iter_btree<const B: usize>() {
+    for num in (0..600).chain([4095, 4096, 10_000]) {
+        let mut tree: BTree<i32, i32, B> = BTree::from_sorted_iter((0..num).map(|i| (i, -i)));
+        tree.assert();
+
+        for i in 0..num {
+            assert_eq!(tree.lookup(&i), Some(&-i));
+        }
+
+        // the bulk loaded tree can be modified as usual
+        for i in (0..num).step_by(2) {
+            assert_eq!(tree.remove(&i), Ok(-i));
+        }
+
+        assert_eq!(tree.insert(&num, num), Ok(()));
+        tree.assert();
+    }
+}
+
+#[test]
+fn test_from_sorted_iter_btree() {
+    from_sorted_iter_btree::<2>();
+    from_sorted_iter_btree::<3>();
+    from_sorted_iter_btree::<11>();
+    from_sorted_iter_btree::<32>();
+}
+
 #[test]
 fn stress_btree() {
     stress_sequential::<String, BTree<_, _>>(100_000);
 }
+
+#[test]
+fn stress_btree_fanout() {
+    stress_sequential::<String, BTree<_, _, 3>>(100_000);
+    stress_sequential::<String, BTree<_, _, 64>>(100_000);
+}
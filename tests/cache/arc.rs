@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use cds::cache::ARCCache;
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_arc_cache() {
+    let mut cache = ARCCache::new(2);
+
+    assert_eq!(cache.insert(1, "one"), None);
+    assert_eq!(cache.insert(2, "two"), None);
+    assert_eq!(cache.get(&1), Some(&"one"));
+    assert_eq!(cache.insert(3, "three"), None);
+
+    // 1 is frequent, so 2 is evicted instead
+    assert_eq!(cache.len(), 2);
+    assert!(cache.contains(&1));
+    assert!(!cache.contains(&2));
+    assert_eq!(cache.get(&2), None);
+
+    assert_eq!(cache.insert(1, "uno"), Some("one"));
+    assert_eq!(cache.get(&1), Some(&"uno"));
+    assert_eq!(cache.hits(), 2);
+    assert_eq!(cache.misses(), 1);
+
+    assert_eq!(cache.remove(&1), Some("uno"));
+    assert_eq!(cache.remove(&1), None);
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn test_arc_cache_scan_resistance() {
+    let mut cache = ARCCache::new(100);
+
+    // the hot keys are used twice
+    for _ in 0..2 {
+        for key in 0..50 {
+            if cache.get(&key).is_none() {
+                cache.insert(key, key);
+            }
+        }
+    }
+
+    // the scan of the cold keys used once
+    for key in 1_000..11_000 {
+        if cache.get(&key).is_none() {
+            cache.insert(key, key);
+        }
+    }
+
+    assert!((0..50).all(|key| cache.contains(&key)));
+}
+
+#[test]
+fn stress_arc_cache() {
+    let capacity = 64;
+    let mut rng = thread_rng();
+    let mut cache = ARCCache::new(capacity);
+    let mut values = HashMap::new();
+
+    for i in 0..100_000 {
+        // the recency and the frequency change by the phase
+        let key = if (i / 10_000) % 2 == 0 {
+            rng.gen_range(0..100)
+        } else {
+            (rng.gen::<f64>().powi(3) * 1_000.0) as u32
+        };
+
+        match rng.gen_range(0..10) {
+            0..=5 => {
+                if let Some(value) = cache.get(&key) {
+                    assert_eq!(Some(value), values.get(&key));
+                }
+            }
+            6..=8 => {
+                let value = rng.gen::<u32>();
+                let old = cache.insert(key, value);
+
+                if let Some(old) = old {
+                    assert_eq!(values.get(&key), Some(&old));
+                }
+
+                values.insert(key, value);
+                assert!(cache.contains(&key));
+            }
+            _ => {
+                if let Some(old) = cache.remove(&key) {
+                    assert_eq!(values.get(&key), Some(&old));
+                }
+
+                values.remove(&key);
+            }
+        }
+
+        assert!(cache.len() <= capacity);
+    }
+
+    assert!(cache.hits() > 0 && cache.misses() > 0);
+}
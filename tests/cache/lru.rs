@@ -0,0 +1,87 @@
+use std::collections::VecDeque;
+
+use cds::cache::LruCache;
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_lru_cache() {
+    let mut cache = LruCache::new(2);
+
+    assert_eq!(cache.insert(1, "one"), None);
+    assert_eq!(cache.insert(2, "two"), None);
+    assert_eq!(cache.get(&1), Some(&"one"));
+
+    // 2 is the least recently used
+    assert_eq!(cache.insert(3, "three"), None);
+    assert!(!cache.contains(&2));
+    assert_eq!(cache.peek(&1), Some(&"one"));
+
+    // peek does not change the order
+    assert_eq!(cache.insert(4, "four"), None);
+    assert!(!cache.contains(&1));
+
+    assert_eq!(cache.insert(3, "tres"), Some("three"));
+    assert_eq!(cache.pop_lru(), Some((4, "four")));
+    assert_eq!(cache.remove(&3), Some("tres"));
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_lru_cache_weight() {
+    let mut cache = LruCache::new(10);
+
+    cache.insert_with_weight("a", 1, 4);
+    cache.insert_with_weight("b", 2, 4);
+    assert_eq!(cache.size(), 8);
+
+    cache.insert_with_weight("c", 3, 4);
+    assert_eq!(cache.size(), 8);
+    assert!(!cache.contains(&"a"));
+
+    // the new weight replaces the old one
+    cache.insert_with_weight("b", 20, 1);
+    assert_eq!(cache.size(), 5);
+
+    // the entry heavier than the capacity is not kept
+    cache.insert_with_weight("d", 4, 11);
+    assert!(cache.is_empty());
+    assert_eq!(cache.size(), 0);
+}
+
+#[test]
+fn stress_lru_cache() {
+    let capacity = 32;
+    let mut rng = thread_rng();
+    let mut cache = LruCache::new(capacity);
+    let mut order: VecDeque<(u32, u32)> = VecDeque::new(); // the naive LRU, the front is the most recent
+
+    for _ in 0..100_000 {
+        let key = rng.gen_range(0..64);
+        let position = order.iter().position(|(k, _)| *k == key);
+
+        match rng.gen_range(0..3) {
+            0 => {
+                let value = position.map(|position| order.remove(position).unwrap());
+                assert_eq!(cache.get(&key), value.as_ref().map(|(_, v)| v));
+
+                if let Some(value) = value {
+                    order.push_front(value);
+                }
+            }
+            1 => {
+                let value = rng.gen();
+                let old = position.map(|position| order.remove(position).unwrap().1);
+                assert_eq!(cache.insert(key, value), old);
+
+                order.push_front((key, value));
+                order.truncate(capacity);
+            }
+            _ => {
+                let old = position.map(|position| order.remove(position).unwrap().1);
+                assert_eq!(cache.remove(&key), old);
+            }
+        }
+
+        assert_eq!(cache.len(), order.len());
+    }
+}
@@ -0,0 +1,3 @@
+mod arc;
+mod lru;
+mod sharded;
@@ -0,0 +1,83 @@
+use std::{sync::Barrier, thread};
+
+use cds::cache::ShardedLruCache;
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_sharded_lru_cache() {
+    let cache = ShardedLruCache::with_shards(100, 3);
+    assert_eq!(cache.shards(), 4);
+
+    for i in 0..100 {
+        assert_eq!(cache.insert(i, i), None);
+    }
+
+    assert_eq!(cache.size(), 100);
+    assert_eq!(cache.len(), 100);
+    assert_eq!(cache.insert(0, 10), Some(0));
+    assert_eq!(cache.get(&0), Some(10));
+    assert_eq!(cache.lookup(&1, |value| value.copied()), Some(1));
+
+    for i in 100..200 {
+        cache.insert(i, i);
+        assert_eq!(cache.size(), 100);
+    }
+
+    assert_eq!(cache.len(), 100);
+    assert_eq!(cache.remove(&199), Some(199));
+    assert_eq!(cache.size(), 99);
+}
+
+#[test]
+fn test_sharded_lru_cache_single_shard() {
+    // with one shard, it is the exact LRU
+    let cache = ShardedLruCache::with_shards(3, 1);
+
+    cache.insert(1, 1);
+    cache.insert(2, 2);
+    cache.insert(3, 3);
+    cache.get(&1);
+    cache.insert(4, 4);
+
+    assert!(!cache.contains(&2));
+    assert!(cache.contains(&1));
+}
+
+#[test]
+fn stress_sharded_lru_cache() {
+    let capacity = 1_000;
+    let threads = 16;
+    let cache = ShardedLruCache::with_shards(capacity, 8);
+    let barrier = Barrier::new(threads);
+
+    thread::scope(|s| {
+        for _ in 0..threads {
+            s.spawn(|| {
+                let mut rng = thread_rng();
+                barrier.wait();
+
+                for _ in 0..50_000 {
+                    let key = rng.gen_range(0..5_000u32);
+
+                    match rng.gen_range(0..10) {
+                        0..=5 => {
+                            // the value is always derived from the key
+                            if let Some(value) = cache.get(&key) {
+                                assert_eq!(value, key as u64 * 2);
+                            }
+                        }
+                        6..=8 => {
+                            cache.insert_with_weight(key, key as u64 * 2, 1 + key as usize % 3);
+                        }
+                        _ => {
+                            cache.remove(&key);
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    assert!(cache.size() <= capacity);
+    assert!(cache.len() <= capacity);
+}
@@ -0,0 +1,54 @@
+mod rollback;
+
+use cds::dsu::UnionFind;
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_union_find() {
+    let mut sets = UnionFind::new(10);
+
+    assert_eq!(sets.count(), 10);
+    assert!(sets.union(0, 1));
+    assert!(sets.union(2, 3));
+    assert!(sets.union(1, 3));
+    assert!(!sets.union(0, 2));
+    assert_eq!(sets.count(), 7);
+
+    assert!(sets.connected(0, 3));
+    assert!(!sets.connected(0, 4));
+
+    let element = sets.push();
+    assert_eq!(element, 10);
+    assert_eq!(sets.len(), 11);
+    assert!(sets.union(element, 4));
+    assert!(sets.connected(4, 10));
+    assert_eq!(sets.count(), 7);
+}
+
+#[test]
+fn stress_union_find() {
+    let n = 1_000;
+    let mut rng = thread_rng();
+    let mut sets = UnionFind::new(n);
+    let mut labels: Vec<usize> = (0..n).collect(); // the naive sets by relabeling
+
+    for _ in 0..10_000 {
+        let (a, b) = (rng.gen_range(0..n), rng.gen_range(0..n));
+
+        if rng.gen_bool(0.3) {
+            let (from, to) = (labels[a], labels[b]);
+            assert_eq!(sets.union(a, b), from != to);
+
+            for label in labels.iter_mut().filter(|label| **label == from) {
+                *label = to;
+            }
+        } else {
+            assert_eq!(sets.connected(a, b), labels[a] == labels[b]);
+        }
+    }
+
+    let mut roots: Vec<_> = labels.clone();
+    roots.sort_unstable();
+    roots.dedup();
+    assert_eq!(sets.count(), roots.len());
+}
@@ -0,0 +1,61 @@
+use cds::dsu::RollbackUnionFind;
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_rollback_union_find() {
+    let mut sets = RollbackUnionFind::new(6);
+
+    assert!(sets.union(0, 1));
+    let snapshot = sets.snapshot();
+
+    assert!(sets.union(1, 2));
+    assert!(!sets.union(0, 2));
+    assert!(sets.union(3, 4));
+    assert_eq!(sets.count(), 3);
+    assert_eq!(sets.size(2), 3);
+
+    assert!(sets.undo());
+    assert!(!sets.connected(3, 4));
+    assert!(sets.connected(0, 2));
+
+    sets.rollback(snapshot);
+    assert!(sets.connected(0, 1));
+    assert!(!sets.connected(1, 2));
+    assert_eq!(sets.size(0), 2);
+    assert_eq!(sets.count(), 5);
+
+    sets.rollback(0);
+    assert_eq!(sets.count(), 6);
+    assert!(!sets.undo());
+}
+
+#[test]
+fn stress_rollback_union_find() {
+    let n = 200;
+    let mut rng = thread_rng();
+    let mut sets = RollbackUnionFind::new(n);
+    let mut states = vec![(0, (0..n).map(|i| sets.find(i)).collect::<Vec<_>>())];
+
+    for _ in 0..10_000 {
+        if rng.gen_bool(0.7) {
+            sets.union(rng.gen_range(0..n), rng.gen_range(0..n));
+
+            if rng.gen_bool(0.1) {
+                let roots = (0..n).map(|i| sets.find(i)).collect();
+                states.push((sets.snapshot(), roots));
+            }
+        } else {
+            // roll back to a recorded state, then the partition should be the same
+            let index = rng.gen_range(0..states.len());
+            states.truncate(index + 1);
+            let (snapshot, roots) = states.last().unwrap();
+
+            sets.rollback(*snapshot);
+
+            for a in 0..n {
+                let b = rng.gen_range(0..n);
+                assert_eq!(sets.connected(a, b), roots[a] == roots[b]);
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::{hash_map::RandomState, HashMap};
+
+use cds::filter::CountingBloomFilter;
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_counting_bloom_filter() {
+    let mut filter = CountingBloomFilter::new(1_000, 0.01);
+
+    assert_eq!(filter.num_counters(), 9_586);
+    assert_eq!(filter.num_hashes(), 7);
+
+    for i in 0..1_000 {
+        filter.insert(&i);
+    }
+
+    assert!((0..1_000).all(|i| filter.contains(&i)));
+
+    for i in 0..500 {
+        assert_eq!(filter.remove(&i), Ok(()));
+    }
+
+    assert!((500..1_000).all(|i| filter.contains(&i)));
+
+    let false_positives = (0..500).filter(|i| filter.contains(i)).count();
+    assert!(
+        false_positives < 20,
+        "too many false positives: {}",
+        false_positives
+    );
+
+    for i in 500..1_000 {
+        assert_eq!(filter.remove(&i), Ok(()));
+    }
+
+    assert!(filter.is_empty());
+    assert_eq!(filter.remove(&0), Err(()));
+}
+
+#[test]
+fn test_counting_bloom_filter_saturation() {
+    // every item shares the only counter
+    let mut filter: CountingBloomFilter<i32> =
+        CountingBloomFilter::with_size_and_hasher(1, 1, RandomState::new());
+
+    for _ in 0..300 {
+        filter.insert(&0);
+    }
+
+    for _ in 0..300 {
+        assert_eq!(filter.remove(&0), Ok(()));
+    }
+
+    // the saturated counter is never decremented
+    assert!(filter.contains(&0));
+    assert!(filter.contains(&1));
+}
+
+#[test]
+fn stress_counting_bloom_filter() {
+    let mut rng = thread_rng();
+    let mut filter = CountingBloomFilter::new(1_000, 0.01);
+    let mut counts: HashMap<u32, usize> = HashMap::new();
+
+    for _ in 0..100_000 {
+        let item = rng.gen_range(0..2_000);
+        let count = counts.entry(item).or_insert(0);
+
+        if rng.gen_bool(0.5) {
+            filter.insert(&item);
+            *count += 1;
+        } else if *count > 0 {
+            assert_eq!(filter.remove(&item), Ok(()));
+            *count -= 1;
+        }
+
+        // no false negative after removing the others
+        assert!(*count == 0 || filter.contains(&item));
+    }
+
+    assert!(counts
+        .iter()
+        .filter(|(_, count)| **count > 0)
+        .all(|(item, _)| filter.contains(item)));
+}
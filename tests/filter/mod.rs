@@ -0,0 +1,92 @@
+mod counting;
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{BuildHasherDefault, Hash},
+};
+
+use cds::filter::BloomFilter;
+
+type FixedState = BuildHasherDefault<DefaultHasher>;
+
+#[test]
+fn test_bloom_filter() {
+    let mut filter = BloomFilter::new(1_000, 0.01);
+
+    assert!(filter.is_empty());
+    assert_eq!(filter.num_bits(), 9_586);
+    assert_eq!(filter.num_hashes(), 7);
+
+    for i in 0..1_000 {
+        filter.insert(&i);
+    }
+
+    // no false negative
+    assert!((0..1_000).all(|i| filter.contains(&i)));
+    assert!(!filter.insert(&0));
+
+    let false_positives = (1_000..101_000).filter(|i| filter.contains(i)).count();
+    assert!(
+        false_positives < 2_000,
+        "too many false positives: {}",
+        false_positives
+    );
+    assert!(filter.false_positive_rate() < 0.02);
+
+    filter.clear();
+    assert!(filter.is_empty());
+    assert!(!filter.contains(&0));
+}
+
+#[test]
+fn test_bloom_filter_str() {
+    let mut filter: BloomFilter<str> = BloomFilter::new(100, 0.001);
+
+    filter.insert("apple");
+    filter.insert("banana");
+
+    assert!(filter.contains("apple"));
+    assert!(filter.contains("banana"));
+    assert!(!filter.contains("cherry"));
+}
+
+fn filter_of<T: Hash>(items: impl Iterator<Item = T>) -> BloomFilter<T, FixedState> {
+    let mut filter = BloomFilter::with_hasher(1_000, 0.001, FixedState::default());
+    items.for_each(|item| {
+        filter.insert(&item);
+    });
+    filter
+}
+
+#[test]
+fn test_bloom_filter_union_intersect() {
+    let a = filter_of(0..600);
+    let b = filter_of(400..1_000);
+
+    let mut union = a.clone();
+    union.union(&b).unwrap();
+    assert!((0..1_000).all(|i| union.contains(&i)));
+
+    let mut intersection = a.clone();
+    intersection.intersect(&b).unwrap();
+    assert!((400..600).all(|i| intersection.contains(&i)));
+    assert!((0..400).filter(|i| intersection.contains(i)).count() < 20);
+
+    let mut other = BloomFilter::with_hasher(10, 0.1, FixedState::default());
+    assert_eq!(other.union(&a), Err(()));
+    assert_eq!(other.intersect(&a), Err(()));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_bloom_filter() {
+    let filter = filter_of(0..100);
+
+    let json = serde_json::to_string(&filter).unwrap();
+    let filter: BloomFilter<i32, FixedState> = serde_json::from_str(&json).unwrap();
+
+    assert!((0..100).all(|i| filter.contains(&i)));
+    assert_eq!(json, serde_json::to_string(&filter).unwrap());
+
+    assert!(serde_json::from_str::<BloomFilter<i32, FixedState>>("[100, 3, [0]]").is_err());
+}
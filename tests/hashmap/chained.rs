@@ -0,0 +1,95 @@
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+use crate::util::{hash::ConstantHasher, map::stress_sequential};
+use cds::{hashmap::ChainedHashMap, map::SequentialMap};
+use rand::{prelude::SliceRandom, thread_rng};
+
+#[test]
+fn test_chained_hashmap() {
+    let num = 10_000;
+    let mut keys: Vec<i32> = (0..num).collect();
+    keys.shuffle(&mut thread_rng());
+
+    let mut map: ChainedHashMap<i32, i32> = ChainedHashMap::new();
+
+    for &key in &keys {
+        assert_eq!(map.insert(&key, key), Ok(()));
+    }
+
+    for &key in &keys {
+        assert_eq!(map.insert(&key, key), Err(key));
+    }
+
+    assert_eq!(map.len(), num as usize);
+    assert!(map.capacity() as f64 * map.max_load_factor() >= num as f64);
+
+    for &key in &keys {
+        *map.lookup_mut(&key).unwrap() += 1;
+    }
+
+    keys.shuffle(&mut thread_rng());
+
+    for &key in &keys {
+        assert_eq!(map.lookup(&key), Some(&(key + 1)));
+        assert_eq!(map.remove(&key), Ok(key + 1));
+        assert_eq!(map.remove(&key), Err(()));
+    }
+
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_chained_hashmap_incremental_rehashing() {
+    let mut map: ChainedHashMap<u32, u32> = ChainedHashMap::new();
+    let mut rehashed = 0;
+
+    for key in 0..100_000 {
+        let capacity = map.capacity();
+        assert_eq!(map.insert(&key, key), Ok(()));
+
+        if map.capacity() != capacity {
+            rehashed += 1;
+            assert_eq!(map.capacity(), capacity * 2);
+            assert!(map.is_rehashing());
+
+            // every key is found while rehashing
+            for key in 0..=key {
+                assert_eq!(map.lookup(&key), Some(&key));
+            }
+        }
+    }
+
+    assert!(rehashed > 10);
+}
+
+#[test]
+fn test_chained_hashmap_load_factor() {
+    let mut map: ChainedHashMap<u32, u32> = ChainedHashMap::new();
+    map.set_max_load_factor(4.0);
+
+    for key in 0..1_000 {
+        assert_eq!(map.insert(&key, key), Ok(()));
+    }
+
+    assert_eq!(map.capacity(), 256);
+}
+
+#[test]
+fn test_chained_hashmap_hasher() {
+    let mut map = ChainedHashMap::with_hasher(BuildHasherDefault::<ConstantHasher>::default());
+
+    for key in 0..1_000 {
+        assert_eq!(map.insert(&key, key), Ok(()));
+    }
+
+    for key in 0..1_000 {
+        assert_eq!(map.lookup(&key), Some(&key));
+        assert_eq!(map.remove(&key), Ok(key));
+    }
+}
+
+#[test]
+fn stress_chained_hashmap() {
+    stress_sequential::<String, ChainedHashMap<_, _>>(100_000);
+    stress_sequential::<u64, ChainedHashMap<_, _, BuildHasherDefault<DefaultHasher>>>(100_000);
+}
@@ -0,0 +1,80 @@
+use std::{collections::hash_map::RandomState, hash::BuildHasherDefault};
+
+use crate::util::{hash::ConstantHasher, map::stress_sequential};
+use cds::{hashmap::ExtendibleHashMap, map::SequentialMap};
+use rand::{prelude::SliceRandom, thread_rng};
+
+#[test]
+fn test_extendible_hashmap() {
+    let num = 10_000;
+    let mut keys: Vec<i32> = (0..num).collect();
+    keys.shuffle(&mut thread_rng());
+
+    let mut map: ExtendibleHashMap<i32, i32> = ExtendibleHashMap::new();
+
+    for &key in &keys {
+        assert_eq!(map.insert(&key, key), Ok(()));
+    }
+
+    for &key in &keys {
+        assert_eq!(map.insert(&key, key), Err(key));
+    }
+
+    assert_eq!(map.len(), num as usize);
+
+    for &key in &keys {
+        *map.lookup_mut(&key).unwrap() += 1;
+    }
+
+    keys.shuffle(&mut thread_rng());
+
+    for &key in &keys {
+        assert_eq!(map.lookup(&key), Some(&(key + 1)));
+        assert_eq!(map.remove(&key), Ok(key + 1));
+        assert_eq!(map.remove(&key), Err(()));
+    }
+
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_extendible_hashmap_split() {
+    let mut map = ExtendibleHashMap::with_bucket_size_and_hasher(4, RandomState::new());
+
+    for key in 0..10_000u32 {
+        assert_eq!(map.insert(&key, key), Ok(()));
+    }
+
+    // every bucket has at most 4 (key, value)s, since the hashes hardly collide
+    assert!(map.buckets() >= map.len() / 4);
+    // the buckets are not sparse on average
+    assert!(map.buckets() <= map.len());
+    assert!(1 << map.global_depth() <= 4 * map.len());
+
+    for key in 0..10_000 {
+        assert_eq!(map.lookup(&key), Some(&key));
+    }
+}
+
+#[test]
+fn test_extendible_hashmap_hasher() {
+    let mut map = ExtendibleHashMap::with_hasher(BuildHasherDefault::<ConstantHasher>::default());
+
+    for key in 0..1_000 {
+        assert_eq!(map.insert(&key, key), Ok(()));
+    }
+
+    // the directory is bounded by the number of the (key, value)s
+    assert!(1 << map.global_depth() <= 2 * map.len());
+
+    for key in 0..1_000 {
+        assert_eq!(map.lookup(&key), Some(&key));
+        assert_eq!(map.remove(&key), Ok(key));
+    }
+}
+
+#[test]
+fn stress_extendible_hashmap() {
+    stress_sequential::<String, ExtendibleHashMap<_, _>>(100_000);
+    stress_sequential::<u64, ExtendibleHashMap<_, _>>(100_000);
+}
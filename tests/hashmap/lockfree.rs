@@ -0,0 +1,95 @@
+use std::thread;
+
+use crate::util::map::{stress_concurrent, stress_concurrent_as_sequential};
+use cds::{hashmap::LockFreeHashMap, map::ConcurrentMap};
+
+#[test]
+fn test_lockfree_hashmap() {
+    let num = 10_000;
+    let map: LockFreeHashMap<i32, i32> = LockFreeHashMap::new();
+
+    for i in 0..num {
+        assert_eq!(map.insert(&i, i), Ok(()));
+    }
+
+    for i in 0..num {
+        assert_eq!(map.insert(&i, i), Err(i));
+    }
+
+    assert_eq!(map.len(), num as usize);
+    assert!(map.capacity() >= num as usize);
+
+    for i in 0..num {
+        assert_eq!(map.get(&i), Some(i));
+        assert_eq!(map.remove(&i), Ok(i));
+        assert_eq!(map.remove(&i), Err(()));
+        assert_eq!(map.get(&i), None);
+    }
+
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_lockfree_hashmap_churn() {
+    let map: LockFreeHashMap<u32, u32> = LockFreeHashMap::new();
+
+    // the removed slots are cleaned by resizing on the same capacity
+    for i in 0..100_000 {
+        assert_eq!(map.insert(&i, i), Ok(()));
+        assert_eq!(map.remove(&i), Ok(i));
+    }
+
+    assert!(map.is_empty());
+    assert!(map.capacity() <= 64);
+}
+
+#[test]
+fn test_lockfree_hashmap_resize_with_readers() {
+    let num = 100_000;
+    let map: LockFreeHashMap<u64, u64> = LockFreeHashMap::new();
+
+    for i in 0..num {
+        assert_eq!(map.insert(&i, i), Ok(()));
+    }
+
+    // the readers always see the inserted keys while the writers resize the table
+    thread::scope(|s| {
+        for t in 0..4 {
+            let map = &map;
+            s.spawn(move || {
+                for i in (0..num).map(|i| num * (t + 1) + i) {
+                    assert_eq!(map.insert(&i, i), Ok(()));
+                }
+            });
+        }
+
+        for _ in 0..4 {
+            let map = &map;
+            s.spawn(move || {
+                for _ in 0..5 {
+                    for i in 0..num {
+                        assert_eq!(map.get(&i), Some(i));
+                    }
+                }
+            });
+        }
+    });
+
+    assert_eq!(map.len(), num as usize * 5);
+}
+
+#[test]
+fn stress_lockfree_hashmap_sequential() {
+    stress_concurrent_as_sequential::<String, LockFreeHashMap<_, _>>(100_000);
+}
+
+#[test]
+fn stress_lockfree_hashmap_concurrent() {
+    stress_concurrent::<u32, LockFreeHashMap<_, _>>(200_000, 16, false);
+}
+
+#[test]
+fn assert_lockfree_hashmap_concurrent() {
+    stress_concurrent::<u8, LockFreeHashMap<_, _>>(100_000, 32, true);
+    stress_concurrent::<u64, LockFreeHashMap<_, _>>(100_000, 32, true);
+}
@@ -0,0 +1,5 @@
+mod chained;
+mod extendible;
+mod lockfree;
+mod sharded;
+mod swiss;
@@ -0,0 +1,67 @@
+use std::thread;
+
+use crate::util::map::{stress_concurrent, stress_concurrent_as_sequential};
+use cds::{hashmap::ShardedHashMap, map::ConcurrentMap};
+
+#[test]
+fn test_sharded_hashmap() {
+    let num = 10_000;
+    let map = ShardedHashMap::with_shards(6);
+    assert_eq!(map.shards(), 8);
+
+    for i in 0..num {
+        assert_eq!(map.insert(&i, i), Ok(()));
+    }
+
+    for i in 0..num {
+        assert_eq!(map.insert(&i, i), Err(i));
+    }
+
+    assert_eq!(map.len(), num as usize);
+
+    for i in 0..num {
+        *map.write(&i).unwrap() += 1;
+    }
+
+    for i in 0..num {
+        assert_eq!(*map.read(&i).unwrap(), i + 1);
+        assert_eq!(map.lookup(&i, |value| value.copied()), Some(i + 1));
+        assert_eq!(map.remove(&i), Ok(i + 1));
+        assert!(map.read(&i).is_none());
+    }
+
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_sharded_hashmap_guard() {
+    let map = ShardedHashMap::with_shards(1);
+    assert_eq!(map.insert(&1, vec![1, 2, 3]), Ok(()));
+
+    let guard = map.read(&1).unwrap();
+
+    // the other readers share the shard while the guard lives
+    thread::scope(|s| {
+        s.spawn(|| assert_eq!(map.get(&1), Some(vec![1, 2, 3])));
+    });
+
+    drop(guard);
+    map.write(&1).unwrap().push(4);
+    assert_eq!(map.get(&1), Some(vec![1, 2, 3, 4]));
+}
+
+#[test]
+fn stress_sharded_hashmap_sequential() {
+    stress_concurrent_as_sequential::<String, ShardedHashMap<_, _>>(100_000);
+}
+
+#[test]
+fn stress_sharded_hashmap_concurrent() {
+    stress_concurrent::<u32, ShardedHashMap<_, _>>(200_000, 16, false);
+}
+
+#[test]
+fn assert_sharded_hashmap_concurrent() {
+    stress_concurrent::<u8, ShardedHashMap<_, _>>(100_000, 32, true);
+    stress_concurrent::<u64, ShardedHashMap<_, _>>(100_000, 32, true);
+}
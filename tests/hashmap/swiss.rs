@@ -0,0 +1,105 @@
+use std::{hash::BuildHasherDefault, sync::Arc};
+
+use crate::util::{hash::ConstantHasher, map::stress_sequential};
+use cds::{hashmap::SwissHashMap, map::SequentialMap};
+use rand::{prelude::SliceRandom, thread_rng};
+
+#[test]
+fn test_swiss_hashmap() {
+    let num = 10_000;
+    let mut keys: Vec<i32> = (0..num).collect();
+    keys.shuffle(&mut thread_rng());
+
+    let mut map: SwissHashMap<i32, i32> = SwissHashMap::new();
+
+    for &key in &keys {
+        assert_eq!(map.insert(&key, key), Ok(()));
+    }
+
+    for &key in &keys {
+        assert_eq!(map.insert(&key, key), Err(key));
+    }
+
+    assert_eq!(map.len(), num as usize);
+    assert!(map.capacity() >= num as usize);
+
+    for &key in &keys {
+        *map.lookup_mut(&key).unwrap() += 1;
+    }
+
+    keys.shuffle(&mut thread_rng());
+
+    for &key in &keys {
+        assert_eq!(map.lookup(&key), Some(&(key + 1)));
+        assert_eq!(map.remove(&key), Ok(key + 1));
+        assert_eq!(map.remove(&key), Err(()));
+    }
+
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_swiss_hashmap_churn() {
+    let mut map: SwissHashMap<u32, u32> = SwissHashMap::new();
+
+    for key in 0..10 {
+        assert_eq!(map.insert(&key, key), Ok(()));
+    }
+
+    let capacity = map.capacity();
+
+    // the DELETED slots are reclaimed without growing
+    for key in 10..100_000 {
+        assert_eq!(map.insert(&key, key), Ok(()));
+        assert_eq!(map.remove(&(key - 10)), Ok(key - 10));
+        assert_eq!(map.len(), 10);
+    }
+
+    assert_eq!(map.capacity(), capacity);
+
+    for key in 100_000 - 10..100_000 {
+        assert_eq!(map.lookup(&key), Some(&key));
+    }
+}
+
+#[test]
+fn test_swiss_hashmap_hasher() {
+    let mut map = SwissHashMap::with_hasher(BuildHasherDefault::<ConstantHasher>::default());
+
+    for key in 0..1_000 {
+        assert_eq!(map.insert(&key, key), Ok(()));
+    }
+
+    for key in (0..1_000).step_by(2) {
+        assert_eq!(map.remove(&key), Ok(key));
+    }
+
+    for key in 0..1_000 {
+        let expected = if key % 2 == 0 { None } else { Some(&key) };
+        assert_eq!(map.lookup(&key), expected);
+    }
+}
+
+#[test]
+fn test_swiss_hashmap_drop() {
+    let value = Arc::new(0);
+    let mut map = SwissHashMap::<_, _>::new();
+
+    for key in 0..100 {
+        assert!(map.insert(&key, value.clone()).is_ok());
+    }
+
+    for key in 0..30 {
+        assert!(map.remove(&key).is_ok());
+    }
+
+    assert_eq!(Arc::strong_count(&value), 71);
+    drop(map);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
+
+#[test]
+fn stress_swiss_hashmap() {
+    stress_sequential::<String, SwissHashMap<_, _>>(100_000);
+    stress_sequential::<u64, SwissHashMap<_, _>>(100_000);
+}
@@ -0,0 +1,71 @@
+use cds::heap::{IntervalHeap, PriorityQueue};
+use rand::{thread_rng, Rng};
+
+use crate::util::heap::*;
+
+#[test]
+fn test_interval_heap() {
+    test_simple_priority_queue::<IntervalHeap<_>>();
+}
+
+#[test]
+fn stress_interval_heap() {
+    stress_priority_queue::<IntervalHeap<_>>(1_000_000);
+}
+
+#[test]
+fn test_interval_heap_double_ended() {
+    let mut heap = IntervalHeap::new();
+
+    for value in [5, 3, 8, 1, 9, 2, 7] {
+        heap.push(value);
+    }
+
+    assert_eq!(heap.peek_min(), Some(&1));
+    assert_eq!(heap.peek_max(), Some(&9));
+
+    assert_eq!(heap.pop_max(), Some(9));
+    assert_eq!(heap.pop_min(), Some(1));
+    assert_eq!(heap.pop_max(), Some(8));
+    assert_eq!(heap.pop_max(), Some(7));
+    assert_eq!(heap.pop_min(), Some(2));
+    assert_eq!(heap.pop_max(), Some(5));
+    assert_eq!(heap.peek_min(), Some(&3));
+    assert_eq!(heap.peek_max(), Some(&3));
+    assert_eq!(heap.pop_max(), Some(3));
+
+    assert_eq!(heap.pop_min(), None);
+    assert_eq!(heap.pop_max(), None);
+}
+
+#[test]
+fn stress_interval_heap_double_ended() {
+    let mut heap = IntervalHeap::new();
+    let mut reference = Vec::new(); // sorted
+    let mut rng = thread_rng();
+
+    for _ in 0..200_000 {
+        match rng.gen_range(0..5) {
+            0..=2 => {
+                let value = rng.gen_range(0..1_000);
+                heap.push(value);
+
+                let index = reference.partition_point(|v| *v < value);
+                reference.insert(index, value);
+            }
+            3 => {
+                let expected = if reference.is_empty() {
+                    None
+                } else {
+                    Some(reference.remove(0))
+                };
+                assert_eq!(heap.pop_min(), expected);
+            }
+            _ => assert_eq!(heap.pop_max(), reference.pop()),
+        }
+
+        assert_eq!(heap.len(), reference.len());
+        assert_eq!(heap.peek_min(), reference.first());
+        assert_eq!(heap.peek_max(), reference.last());
+    }
+}
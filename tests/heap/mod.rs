@@ -0,0 +1,18 @@
+mod interval;
+mod pairing;
+mod radix;
+mod skiplist;
+
+use cds::heap::BinaryHeap;
+
+use crate::util::heap::*;
+
+#[test]
+fn test_binary_heap() {
+    test_simple_priority_queue::<BinaryHeap<_>>();
+}
+
+#[test]
+fn stress_binary_heap() {
+    stress_priority_queue::<BinaryHeap<_>>(1_000_000);
+}
@@ -0,0 +1,208 @@
+use std::sync::Arc;
+
+use cds::heap::{PairingHeap, PriorityQueue};
+
+use crate::util::heap::*;
+
+#[test]
+fn test_pairing_heap() {
+    test_simple_priority_queue::<PairingHeap<_>>();
+}
+
+#[test]
+fn stress_pairing_heap() {
+    stress_priority_queue::<PairingHeap<_>>(1_000_000);
+}
+
+#[test]
+fn test_pairing_heap_meld() {
+    let mut even = PairingHeap::new();
+    let mut odd = PairingHeap::new();
+
+    for i in 0..1_000 {
+        even.push(2 * i);
+        odd.push(2 * i + 1);
+    }
+
+    even.meld(odd);
+    assert_eq!(even.len(), 2_000);
+
+    for i in 0..2_000 {
+        assert_eq!(even.pop(), Some(i));
+    }
+
+    assert_eq!(even.pop(), None);
+}
+
+#[test]
+fn test_pairing_heap_decrease_key() {
+    let mut heap = PairingHeap::new();
+    let handles = (0..1_000)
+        .map(|i| heap.push_with_handle(1_000 + i))
+        .collect::<Vec<_>>();
+
+    // reverse the order by decreasing the keys
+    for (i, handle) in handles.iter().enumerate() {
+        assert_eq!(heap.decrease_key(*handle, 999 - i), Ok(()));
+        assert_eq!(heap.decrease_key(*handle, 2_000), Err(2_000));
+    }
+
+    assert_eq!(heap.peek(), Some(&0));
+
+    for i in 0..1_000 {
+        assert_eq!(heap.pop(), Some(i));
+    }
+
+    assert_eq!(heap.pop(), None);
+}
+
+#[test]
+fn test_pairing_heap_decrease_key_after_pop() {
+    let mut heap = PairingHeap::new();
+    let handles = (0..100)
+        .map(|i| heap.push_with_handle(10 * i))
+        .collect::<Vec<_>>();
+
+    // restructure the heap, then decrease the keys of the remaining values
+    for i in 0..50 {
+        assert_eq!(heap.pop(), Some(10 * i));
+    }
+
+    for (i, handle) in handles[50..].iter().enumerate() {
+        assert_eq!(heap.decrease_key(*handle, 10 * (50 + i) - 5), Ok(()));
+    }
+
+    for i in 50..100 {
+        assert_eq!(heap.pop(), Some(10 * i - 5));
+    }
+}
+
+#[test]
+fn test_pairing_heap_stale_handle() {
+    let mut heap = PairingHeap::new();
+    let first = heap.push_with_handle(1);
+    let second = heap.push_with_handle(2);
+
+    // the popped value
+    assert_eq!(heap.pop(), Some(1));
+    assert!(!heap.contains(first));
+    assert_eq!(heap.decrease_key(first, 0), Err(0));
+
+    // the slot of the popped value is reused
+    let third = heap.push_with_handle(3);
+    assert_eq!(heap.decrease_key(first, 0), Err(0));
+    assert_eq!(heap.decrease_key(third, 0), Ok(()));
+
+    // the handle of another heap
+    let mut other = PairingHeap::new();
+    let foreign = other.push_with_handle(5);
+    assert!(!heap.contains(foreign));
+    assert_eq!(heap.decrease_key(foreign, 0), Err(0));
+
+    // the handle of the melded heap is still valid
+    heap.meld(other);
+    assert!(heap.contains(foreign));
+    assert_eq!(heap.decrease_key(foreign, 1), Ok(()));
+    assert_eq!(heap.decrease_key(second, 1), Ok(()));
+
+    assert_eq!(heap.pop(), Some(0));
+    assert_eq!(heap.pop(), Some(1));
+    assert_eq!(heap.pop(), Some(1));
+    assert_eq!(heap.pop(), None);
+    assert!(!heap.contains(foreign));
+}
+
+#[test]
+fn test_pairing_heap_meld_handles() {
+    let mut heaps = (0..4).map(|_| PairingHeap::new()).collect::<Vec<_>>();
+
+    // the heap i has the values i, i + 4, ...
+    let handles = (0..100_000)
+        .map(|i| heaps[i % 4].push_with_handle(100_000 + i))
+        .collect::<Vec<_>>();
+
+    let mut left = heaps.remove(0);
+    left.meld(heaps.remove(0));
+
+    let mut right = heaps.remove(0);
+    right.meld(heaps.remove(0));
+
+    right.meld(left);
+    assert_eq!(right.len(), 100_000);
+
+    // reverse the order by decreasing the keys
+    for (i, handle) in handles.iter().enumerate() {
+        assert!(right.contains(*handle));
+        assert_eq!(right.decrease_key(*handle, 99_999 - i), Ok(()));
+    }
+
+    for i in 0..50_000 {
+        assert_eq!(right.pop(), Some(i));
+    }
+
+    // the handles of the remaining values are still valid
+    for (i, handle) in handles.iter().enumerate() {
+        assert_eq!(right.contains(*handle), i < 50_000);
+    }
+
+    for handle in &handles[..50_000] {
+        assert_eq!(right.decrease_key(*handle, 0), Ok(()));
+    }
+
+    for _ in 0..50_000 {
+        assert_eq!(right.pop(), Some(0));
+    }
+
+    assert_eq!(right.pop(), None);
+}
+
+#[test]
+fn test_pairing_heap_meld_into_smaller() {
+    let mut heaps = (0..3).map(|_| PairingHeap::new()).collect::<Vec<_>>();
+    let handles = (0..300)
+        .map(|i| heaps[i % 3].push_with_handle(1_000 + i))
+        .collect::<Vec<_>>();
+
+    // the receiver has fewer arenas than the melded heap
+    let mut small = heaps.remove(0);
+    let mut large = heaps.remove(0);
+    large.meld(heaps.remove(0));
+    small.meld(large);
+
+    // a new value is pushed into the arena of the receiver
+    let pushed = small.push_with_handle(2_000);
+
+    for handle in handles.iter().chain([&pushed]) {
+        assert!(small.contains(*handle));
+    }
+
+    assert_eq!(small.decrease_key(pushed, 0), Ok(()));
+
+    for (i, handle) in handles.iter().enumerate() {
+        assert_eq!(small.decrease_key(*handle, 1 + i), Ok(()));
+    }
+
+    for i in 0..=300 {
+        assert_eq!(small.pop(), Some(i));
+    }
+
+    assert_eq!(small.pop(), None);
+}
+
+#[test]
+fn test_pairing_heap_drop() {
+    let value = Arc::new(0);
+    let mut heap = PairingHeap::new();
+
+    for i in 0..100 {
+        heap.push((i, value.clone()));
+    }
+
+    for _ in 0..30 {
+        drop(heap.pop());
+    }
+
+    assert_eq!(Arc::strong_count(&value), 71);
+    drop(heap);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
@@ -0,0 +1,112 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use cds::heap::RadixHeap;
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_radix_heap() {
+    let mut heap = RadixHeap::new();
+    assert!(heap.is_empty());
+    assert_eq!(heap.pop(), None);
+
+    for priority in [5u32, 3, 8, 1, 9, 2, 7] {
+        assert_eq!(heap.push(priority, priority * 10), Ok(()));
+    }
+
+    assert_eq!(heap.len(), 7);
+    assert_eq!(heap.pop(), Some((1, 10)));
+    assert_eq!(heap.pop(), Some((2, 20)));
+
+    // monotone: the priority should not be smaller than the last popped one
+    assert_eq!(heap.push(1, 0), Err(0));
+    assert_eq!(heap.push(2, 0), Ok(()));
+
+    for priority in [2, 3, 5, 7, 8, 9] {
+        assert_eq!(heap.pop().map(|(p, _)| p), Some(priority));
+    }
+
+    assert_eq!(heap.pop(), None);
+    assert!(heap.is_empty());
+}
+
+#[test]
+fn stress_radix_heap() {
+    let mut heap = RadixHeap::new();
+    let mut reference = BinaryHeap::new();
+    let mut rng = thread_rng();
+    let mut last = 0u64;
+
+    for _ in 0..1_000_000 {
+        if rng.gen_bool(0.6) {
+            let bits = rng.gen_range(0..40);
+            let priority = last + rng.gen_range(0..1 << bits);
+            assert_eq!(heap.push(priority, ()), Ok(()));
+            reference.push(Reverse(priority));
+        } else {
+            let popped = heap.pop().map(|(p, _)| p);
+            assert_eq!(popped, reference.pop().map(|Reverse(p)| p));
+            last = popped.unwrap_or(last);
+        }
+
+        assert_eq!(heap.len(), reference.len());
+    }
+
+    while let Some(Reverse(priority)) = reference.pop() {
+        assert_eq!(heap.pop().map(|(p, _)| p), Some(priority));
+    }
+
+    assert_eq!(heap.pop(), None);
+}
+
+#[test]
+fn test_radix_heap_dijkstra() {
+    let node_num = 1_000;
+    let mut rng = thread_rng();
+    let graph = (0..node_num)
+        .map(|_| {
+            (0..10)
+                .map(|_| (rng.gen_range(0..node_num), rng.gen_range(0..1_000u32)))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // the distances by RadixHeap
+    let mut dist = vec![u32::MAX; node_num];
+    let mut heap = RadixHeap::new();
+    dist[0] = 0;
+    heap.push(0, 0).unwrap();
+
+    while let Some((d, node)) = heap.pop() {
+        if d > dist[node] {
+            continue;
+        }
+
+        for &(next, weight) in &graph[node] {
+            if d + weight < dist[next] {
+                dist[next] = d + weight;
+                heap.push(d + weight, next).unwrap();
+            }
+        }
+    }
+
+    // the distances by the binary heap of std
+    let mut expected = vec![u32::MAX; node_num];
+    let mut heap = BinaryHeap::new();
+    expected[0] = 0;
+    heap.push(Reverse((0, 0)));
+
+    while let Some(Reverse((d, node))) = heap.pop() {
+        if d > expected[node] {
+            continue;
+        }
+
+        for &(next, weight) in &graph[node] {
+            if d + weight < expected[next] {
+                expected[next] = d + weight;
+                heap.push(Reverse((d + weight, next)));
+            }
+        }
+    }
+
+    assert_eq!(dist, expected);
+}
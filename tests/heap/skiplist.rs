@@ -0,0 +1,83 @@
+use std::{sync::Mutex, thread};
+
+use cds::heap::{ConcurrentPriorityQueue, SkipListPriorityQueue};
+use rand::{prelude::SliceRandom, thread_rng, Rng};
+
+#[test]
+fn test_skiplist_priority_queue_sequential() {
+    let queue = SkipListPriorityQueue::new();
+    assert_eq!(queue.pop_min(), None);
+
+    let mut priorities = (0..1_000).map(|i| i % 100).collect::<Vec<_>>();
+    priorities.shuffle(&mut thread_rng());
+
+    for (i, priority) in priorities.iter().enumerate() {
+        queue.insert(*priority, i);
+    }
+
+    priorities.sort_unstable();
+
+    for priority in priorities {
+        assert_eq!(queue.pop_min().map(|(p, _)| p), Some(priority));
+    }
+
+    assert_eq!(queue.pop_min(), None);
+}
+
+#[test]
+fn test_skiplist_priority_queue_fifo() {
+    let queue = SkipListPriorityQueue::new();
+
+    for i in 0..100 {
+        queue.insert(i % 2, i);
+    }
+
+    // the same priorities are popped in the order of the insertion
+    for i in (0..100).step_by(2).chain((1..100).step_by(2)) {
+        assert_eq!(queue.pop_min(), Some((i % 2, i)));
+    }
+}
+
+#[test]
+fn test_skiplist_priority_queue_concurrent() {
+    let thread_num = 16;
+    let per_thread = 10_000;
+    let queue = SkipListPriorityQueue::new();
+    let popped = Mutex::new(Vec::new());
+
+    thread::scope(|s| {
+        for t in 0..thread_num {
+            let (queue, popped) = (&queue, &popped);
+            s.spawn(move || {
+                let mut rng = thread_rng();
+                let mut local = Vec::new();
+
+                for i in 0..per_thread {
+                    queue.insert(rng.gen_range(0..1_000u32), t * per_thread + i);
+
+                    if rng.gen_bool(0.5) {
+                        if let Some((_, value)) = queue.pop_min() {
+                            local.push(value);
+                        }
+                    }
+                }
+
+                popped.lock().unwrap().extend(local);
+            });
+        }
+    });
+
+    let mut popped = popped.into_inner().unwrap();
+    let mut last = 0;
+
+    // the rest is popped in the order of the priority
+    while let Some((priority, value)) = queue.pop_min() {
+        assert!(last <= priority);
+        last = priority;
+        popped.push(value);
+    }
+
+    // every value is popped exactly once
+    popped.sort_unstable();
+    assert_eq!(popped, (0..thread_num * per_thread).collect::<Vec<_>>());
+}
@@ -0,0 +1,95 @@
+use std::ops::Range;
+
+use cds::intervaltree::IntervalTree;
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_interval_tree() {
+    let mut tree = IntervalTree::new();
+
+    tree.insert(15..20, "a");
+    tree.insert(10..30, "b");
+    tree.insert(17..19, "c");
+    tree.insert(5..20, "d");
+    tree.insert(12..15, "e");
+    tree.insert(30..40, "f");
+    tree.insert(12..15, "g");
+    assert_eq!(tree.len(), 7);
+
+    let values: Vec<_> = tree.query_point(&15).map(|(_, v)| *v).collect();
+    assert_eq!(values, vec!["d", "b", "a"]);
+
+    let values: Vec<_> = tree.query_point(&30).map(|(_, v)| *v).collect();
+    assert_eq!(values, vec!["f"]);
+
+    let values: Vec<_> = tree.query_overlap(14..18).map(|(_, v)| *v).collect();
+    assert_eq!(values, vec!["d", "b", "e", "g", "a", "c"]);
+
+    assert_eq!(tree.query_overlap(40..50).count(), 0);
+    assert_eq!(tree.query_overlap(0..5).count(), 0);
+
+    assert!(tree.remove(&(12..15)).is_ok());
+    assert!(tree.remove(&(12..15)).is_ok());
+    assert_eq!(tree.remove(&(12..15)), Err(()));
+    assert_eq!(tree.remove(&(10..30)), Ok("b"));
+    assert_eq!(tree.len(), 4);
+
+    let values: Vec<_> = tree.query_overlap(14..18).map(|(_, v)| *v).collect();
+    assert_eq!(values, vec!["d", "a", "c"]);
+}
+
+#[test]
+fn stress_interval_tree() {
+    let mut rng = thread_rng();
+    let mut tree = IntervalTree::new();
+    let mut expected: Vec<(Range<u32>, u32)> = Vec::new();
+
+    for i in 0..10_000 {
+        if expected.is_empty() || rng.gen_bool(0.7) {
+            let start = rng.gen_range(0..1_000);
+            let range = start..start + rng.gen_range(1..50);
+
+            tree.insert(range.clone(), i);
+            expected.push((range, i));
+        } else {
+            let (range, _) = expected[rng.gen_range(0..expected.len())].clone();
+            let value = tree.remove(&range).unwrap();
+            let index = expected
+                .iter()
+                .position(|(r, v)| *r == range && *v == value)
+                .unwrap();
+            expected.swap_remove(index);
+        }
+
+        assert_eq!(tree.len(), expected.len());
+
+        if i % 10 == 0 {
+            let start = rng.gen_range(0..1_000);
+            let query = start..start + rng.gen_range(1..100);
+
+            let mut result: Vec<_> = tree
+                .query_overlap(query.clone())
+                .map(|(r, v)| (r.clone(), *v))
+                .collect();
+            assert!(result
+                .windows(2)
+                .all(|w| (w[0].0.start, w[0].0.end) <= (w[1].0.start, w[1].0.end)));
+
+            let mut answer: Vec<_> = expected
+                .iter()
+                .filter(|(r, _)| r.start < query.end && query.start < r.end)
+                .cloned()
+                .collect();
+
+            result.sort_by_key(|(r, v)| (r.start, r.end, *v));
+            answer.sort_by_key(|(r, v)| (r.start, r.end, *v));
+            assert_eq!(result, answer);
+
+            let point = rng.gen_range(0..1_000);
+            assert_eq!(
+                tree.query_point(&point).count(),
+                expected.iter().filter(|(r, _)| r.contains(&point)).count()
+            );
+        }
+    }
+}
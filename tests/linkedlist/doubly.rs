@@ -0,0 +1,127 @@
+use crate::util::map::stress_sequential;
+use cds::linkedlist::DoublyLinkedList;
+use cds::map::SequentialMap;
+
+fn collect(list: &mut DoublyLinkedList<i32, i32>) -> Vec<i32> {
+    let mut keys = Vec::new();
+    let mut cursor = list.cursor_front_mut();
+
+    while let Some((key, _)) = cursor.current() {
+        keys.push(*key);
+        cursor.move_next();
+    }
+
+    keys
+}
+
+#[test]
+fn test_doubly_linkedlist() {
+    let mut list: DoublyLinkedList<i32, i32> = DoublyLinkedList::new();
+
+    for i in 0..10 {
+        assert_eq!(list.insert(&i, i), Ok(()));
+    }
+
+    assert_eq!(list.insert(&5, 5), Err(5));
+    assert_eq!(list.len(), 10);
+
+    assert_eq!(list.remove(&0), Ok(0));
+    assert_eq!(list.remove(&9), Ok(9));
+    assert_eq!(list.remove(&5), Ok(5));
+    assert_eq!(list.remove(&5), Err(()));
+
+    assert_eq!(list.lookup(&4), Some(&4));
+    *list.lookup_mut(&4).unwrap() = 40;
+    assert_eq!(list.lookup(&4), Some(&40));
+
+    assert_eq!(collect(&mut list), vec![1, 2, 3, 4, 6, 7, 8]);
+}
+
+#[test]
+fn test_cursor_doubly_linkedlist() {
+    let mut list: DoublyLinkedList<i32, i32> = DoublyLinkedList::new();
+
+    let mut cursor = list.cursor_front_mut();
+    assert_eq!(cursor.index(), None);
+    assert_eq!(cursor.current(), None);
+
+    // on the ghost, insert_after pushes front and insert_before pushes back
+    cursor.insert_after(2, 2);
+    cursor.insert_before(4, 4);
+    assert_eq!(cursor.index(), None);
+
+    cursor.move_next();
+    assert_eq!(cursor.index(), Some(0));
+    assert_eq!(cursor.current(), Some((&2, &mut 2)));
+
+    cursor.insert_before(1, 1);
+    cursor.insert_after(3, 3);
+    assert_eq!(cursor.index(), Some(1));
+    assert_eq!(cursor.peek_prev(), Some((&1, &mut 1)));
+    assert_eq!(cursor.peek_next(), Some((&3, &mut 3)));
+
+    cursor.move_prev();
+    cursor.move_prev();
+    assert_eq!(cursor.index(), None);
+    cursor.move_prev();
+    assert_eq!(cursor.index(), Some(3));
+    assert_eq!(cursor.current(), Some((&4, &mut 4)));
+
+    assert_eq!(collect(&mut list), vec![1, 2, 3, 4]);
+
+    let mut cursor = list.cursor_back_mut();
+    cursor.move_prev();
+    assert_eq!(cursor.remove_current(), Some((3, 3)));
+    assert_eq!(cursor.index(), Some(2));
+    assert_eq!(cursor.current(), Some((&4, &mut 4)));
+
+    assert_eq!(cursor.remove_current(), Some((4, 4)));
+    assert_eq!(cursor.index(), None);
+    assert_eq!(cursor.remove_current(), None);
+
+    assert_eq!(collect(&mut list), vec![1, 2]);
+    assert_eq!(list.len(), 2);
+}
+
+#[test]
+fn test_split_after_doubly_linkedlist() {
+    let mut list: DoublyLinkedList<i32, i32> = DoublyLinkedList::new();
+
+    for i in 0..10 {
+        assert_eq!(list.insert(&i, i), Ok(()));
+    }
+
+    let mut cursor = list.cursor_front_mut();
+    for _ in 0..3 {
+        cursor.move_next();
+    }
+
+    let mut rest = cursor.split_after();
+    assert_eq!(cursor.index(), Some(3));
+    cursor.move_next();
+    assert_eq!(cursor.index(), None);
+
+    assert_eq!(list.len(), 4);
+    assert_eq!(rest.len(), 6);
+    assert_eq!(collect(&mut list), vec![0, 1, 2, 3]);
+    assert_eq!(collect(&mut rest), vec![4, 5, 6, 7, 8, 9]);
+
+    // split at the back returns the empty list
+    let empty = list.cursor_back_mut().split_after();
+    assert!(empty.is_empty());
+
+    // split on the ghost takes the whole list
+    let mut cursor = rest.cursor_back_mut();
+    cursor.move_next();
+    let mut whole = cursor.split_after();
+    assert!(rest.is_empty());
+    assert_eq!(collect(&mut whole), vec![4, 5, 6, 7, 8, 9]);
+
+    assert_eq!(whole.insert(&10, 10), Ok(()));
+    assert_eq!(collect(&mut whole), vec![4, 5, 6, 7, 8, 9, 10]);
+}
+
+#[test]
+fn stress_doubly_linkedlist() {
+    stress_sequential::<String, DoublyLinkedList<_, _>>(100_000);
+}
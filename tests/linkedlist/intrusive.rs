@@ -0,0 +1,79 @@
+use std::pin::Pin;
+
+use cds::impl_linked;
+use cds::linkedlist::{IntrusiveList, ListLink};
+
+struct Task {
+    id: usize,
+    link: ListLink,
+}
+
+impl_linked!(Task, link);
+
+fn ids(list: &IntrusiveList<Task>) -> Vec<usize> {
+    list.iter().map(|task| task.id).collect()
+}
+
+#[test]
+fn test_intrusive_list() {
+    let tasks: Vec<Pin<Box<Task>>> = (0..8)
+        .map(|id| {
+            Box::pin(Task {
+                id,
+                link: ListLink::new(),
+            })
+        })
+        .collect();
+
+    let mut list = IntrusiveList::new();
+    assert!(list.is_empty());
+    assert!(list.front().is_none());
+
+    for task in &tasks[4..] {
+        assert!(list.push_back(task.as_ref()).is_ok());
+    }
+
+    for task in tasks[..4].iter().rev() {
+        assert!(list.push_front(task.as_ref()).is_ok());
+    }
+
+    assert_eq!(list.len(), 8);
+    assert_eq!(ids(&list), (0..8).collect::<Vec<_>>());
+
+    // the element can be in at most one list
+    assert!(list.push_back(tasks[3].as_ref()).is_err());
+
+    let mut other = IntrusiveList::new();
+    assert!(other.push_back(tasks[3].as_ref()).is_err());
+    assert!(!other.remove(tasks[3].as_ref()));
+
+    assert!(list.contains(tasks[3].as_ref()));
+    assert!(list.remove(tasks[3].as_ref()));
+    assert!(!list.contains(tasks[3].as_ref()));
+    assert!(!list.remove(tasks[3].as_ref()));
+
+    assert!(other.push_back(tasks[3].as_ref()).is_ok());
+    assert!(other.contains(tasks[3].as_ref()));
+    assert_eq!(ids(&other), vec![3]);
+
+    assert_eq!(list.pop_front().map(|task| task.id), Some(0));
+    assert_eq!(list.pop_back().map(|task| task.id), Some(7));
+    assert_eq!(list.front().map(|task| task.id), Some(1));
+    assert_eq!(list.back().map(|task| task.id), Some(6));
+    assert_eq!(ids(&list), vec![1, 2, 4, 5, 6]);
+
+    // dropping the list unlinks all elements
+    drop(list);
+    drop(other);
+
+    let mut list = IntrusiveList::new();
+
+    for task in tasks.iter().rev() {
+        assert!(list.push_back(task.as_ref()).is_ok());
+    }
+
+    assert_eq!(ids(&list), (0..8).rev().collect::<Vec<_>>());
+
+    while list.pop_back().is_some() {}
+    assert!(list.is_empty());
+}
@@ -0,0 +1,45 @@
+use cds::{linkedlist::LazyList, map::ConcurrentMap};
+
+use crate::util::map::{stress_concurrent, stress_concurrent_as_sequential};
+
+#[test]
+fn test_lazy_list() {
+    let num = 64;
+    let list: LazyList<i32, i32> = LazyList::new();
+
+    for i in 0..num {
+        assert_eq!(list.insert(&i, i), Ok(()));
+    }
+
+    for i in 0..num {
+        assert_eq!(list.insert(&i, i), Err(i));
+    }
+
+    for i in 0..num {
+        assert_eq!(list.get(&i), Some(i));
+    }
+
+    for i in 0..num {
+        assert_eq!(list.remove(&i), Ok(i));
+    }
+
+    for i in 0..num {
+        assert_eq!(list.remove(&i), Err(()));
+    }
+}
+
+#[test]
+fn stress_lazy_list_sequential() {
+    stress_concurrent_as_sequential::<u8, LazyList<_, _>>(100_000);
+}
+
+#[test]
+fn stress_lazy_list_concurrent() {
+    stress_concurrent::<u8, LazyList<_, _>>(200_000, 16, false);
+}
+
+#[test]
+fn assert_lazy_list_concurrent() {
+    stress_concurrent::<u8, LazyList<_, _>>(100_000, 32, true);
+    stress_concurrent::<u16, LazyList<_, _>>(1_000, 32, true);
+}
@@ -39,7 +39,127 @@ fn test_linkedlist() {
     assert_eq!(list.lookup(&0), None);
 }
 
+#[test]
+fn test_lookup_mut_linkedlist() {
+    let mut list: LinkedList<i32, i32> = LinkedList::new();
+
+    assert_eq!(list.lookup_mut(&1), None);
+
+    assert_eq!(list.insert(&1, 1), Ok(()));
+    assert_eq!(list.insert(&2, 2), Ok(()));
+
+    *list.lookup_mut(&2).unwrap() = 20;
+
+    assert_eq!(list.lookup(&1), Some(&1));
+    assert_eq!(list.lookup(&2), Some(&20));
+    assert_eq!(list.lookup_mut(&3), None);
+}
+
+#[test]
+fn test_from_iter_linkedlist() {
+    let mut list: LinkedList<i32, i32> = vec![(1, 1), (2, 2), (3, 3)].into_iter().collect();
+
+    list.extend(vec![(3, 30), (4, 40)]);
+
+    assert_eq!(list.lookup(&1), Some(&1));
+    assert_eq!(list.lookup(&2), Some(&2));
+    assert_eq!(list.lookup(&3), Some(&30));
+    assert_eq!(list.lookup(&4), Some(&40));
+}
+
+#[test]
+fn test_iter_linkedlist() {
+    let mut list: LinkedList<i32, i32> = vec![(3, 3), (1, 1), (2, 2)].into_iter().collect();
+
+    assert!(list.iter().eq(vec![(&3, &3), (&1, &1), (&2, &2)]));
+
+    for (key, value) in list.iter_mut() {
+        *value += key * 10;
+    }
+
+    for (_, value) in &mut list {
+        *value += 1;
+    }
+
+    assert!((&list)
+        .into_iter()
+        .eq(vec![(&3, &34), (&1, &12), (&2, &23)]));
+    assert_eq!(
+        list.into_iter().collect::<Vec<_>>(),
+        vec![(3, 34), (1, 12), (2, 23)]
+    );
+
+    let list: LinkedList<i32, i32> = LinkedList::new();
+    assert_eq!(list.iter().next(), None);
+    assert_eq!(list.into_iter().next(), None);
+}
+
+#[test]
+fn test_append_split_off_linkedlist() {
+    let mut list: LinkedList<i32, i32> = (0..5).map(|i| (i, i)).collect();
+    let mut other: LinkedList<i32, i32> = (5..10).map(|i| (i, i)).collect();
+
+    list.append(&mut other);
+    assert_eq!(list.len(), 10);
+    assert!(other.is_empty());
+    assert!(list.iter().map(|(key, _)| *key).eq(0..10));
+
+    // both lists are still usable after splicing
+    assert_eq!(list.insert(&10, 10), Ok(()));
+    assert_eq!(other.insert(&0, 0), Ok(()));
+    assert_eq!(other.len(), 1);
+
+    let mut rest = list.split_off(4);
+    assert_eq!(list.len(), 4);
+    assert_eq!(rest.len(), 7);
+    assert!(list.iter().map(|(key, _)| *key).eq(0..4));
+    assert!(rest.iter().map(|(key, _)| *key).eq(4..11));
+
+    assert_eq!(rest.remove(&10), Ok(10));
+    assert_eq!(list.insert(&10, 10), Ok(()));
+    assert_eq!(rest.insert(&11, 11), Ok(()));
+    assert!(list.iter().map(|(key, _)| *key).eq((0..4).chain(10..11)));
+    assert!(rest.iter().map(|(key, _)| *key).eq((4..10).chain(11..12)));
+
+    let whole = list.split_off(0);
+    assert!(list.is_empty());
+    assert_eq!(whole.len(), 5);
+    assert!(rest.split_off(rest.len()).is_empty());
+
+    list.append(&mut rest);
+    assert_eq!(list.len(), 7);
+    assert_eq!(list.insert(&12, 12), Ok(()));
+    assert!(list.iter().map(|(key, _)| *key).eq((4..10).chain(11..13)));
+
+    // append after mutating the last node
+    *list.lookup_mut(&12).unwrap() = 120;
+    list.extend(vec![(12, 1200)]);
+    list.append(&mut (13..15).map(|i| (i, i)).collect());
+    assert_eq!(list.lookup(&12), Some(&1200));
+    assert!(list.iter().map(|(key, _)| *key).eq((4..10).chain(11..15)));
+}
+
 #[test]
 fn stress_linkedlist() {
     stress_sequential::<String, LinkedList<_, _>>(100_000);
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_linkedlist() {
+    let list: LinkedList<String, i32> = vec![("c", 3), ("a", 1), ("b", 2)]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+
+    // keep the order of the list
+    let json = serde_json::to_string(&list).unwrap();
+    assert_eq!(json, r#"{"c":3,"a":1,"b":2}"#);
+
+    let list: LinkedList<String, i32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(serde_json::to_string(&list).unwrap(), json);
+
+    for (key, value) in [("a", 1), ("b", 2), ("c", 3)] {
+        assert_eq!(list.lookup(&key.to_string()), Some(&value));
+    }
+}
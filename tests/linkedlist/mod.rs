@@ -1 +1,7 @@
+mod doubly;
+mod harris;
+mod harris_michael;
+mod intrusive;
+mod lazy;
 mod linkedlist;
+mod unrolled;
@@ -0,0 +1,66 @@
+use crate::util::map::stress_sequential;
+use cds::linkedlist::UnrolledLinkedList;
+use cds::map::SequentialMap;
+use rand::{prelude::SliceRandom, thread_rng};
+
+fn test_unrolled_linkedlist<const B: usize>() {
+    let num = 1024;
+    let mut keys: Vec<i32> = (0..num).collect();
+    keys.shuffle(&mut thread_rng());
+
+    let mut list: UnrolledLinkedList<i32, i32, B> = UnrolledLinkedList::new();
+
+    for &key in &keys {
+        assert_eq!(list.insert(&key, key), Ok(()));
+    }
+
+    for &key in &keys {
+        assert_eq!(list.insert(&key, key), Err(key));
+    }
+
+    assert_eq!(list.len(), num as usize);
+    assert!(list.iter().map(|(key, _)| *key).eq(keys.iter().cloned()));
+
+    // remove the half, keeping the order of the rest
+    let (removed, rest) = keys.split_at(keys.len() / 2);
+    let mut removed = removed.to_vec();
+    removed.shuffle(&mut thread_rng());
+
+    for &key in &removed {
+        assert_eq!(list.remove(&key), Ok(key));
+        assert_eq!(list.remove(&key), Err(()));
+        assert_eq!(list.lookup(&key), None);
+    }
+
+    assert_eq!(list.len(), rest.len());
+    assert!(list.iter().map(|(key, _)| *key).eq(rest.iter().cloned()));
+
+    for (key, value) in list.iter_mut() {
+        *value = key * 10;
+    }
+
+    for &key in rest {
+        assert_eq!(list.lookup(&key), Some(&(key * 10)));
+    }
+
+    *list.lookup_mut(&rest[0]).unwrap() = -1;
+    assert_eq!(list.lookup(&rest[0]), Some(&-1));
+
+    let entries: Vec<(i32, i32)> = list.into_iter().collect();
+    assert_eq!(entries.len(), rest.len());
+    assert!(entries.iter().map(|(key, _)| *key).eq(rest.iter().cloned()));
+}
+
+#[test]
+fn test_unrolled_linkedlist_various_capacity() {
+    test_unrolled_linkedlist::<2>();
+    test_unrolled_linkedlist::<3>();
+    test_unrolled_linkedlist::<16>();
+    test_unrolled_linkedlist::<64>();
+}
+
+#[test]
+fn stress_unrolled_linkedlist() {
+    stress_sequential::<String, UnrolledLinkedList<_, _>>(100_000);
+    stress_sequential::<u8, UnrolledLinkedList<_, _, 3>>(100_000);
+}
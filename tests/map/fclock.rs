@@ -0,0 +1,61 @@
+use cds::{
+    avltree::AVLTree,
+    btree::BTree,
+    lock::{spinlock::RawSpinLock, RawMutex},
+    map::{ConcurrentMap, FCMap},
+};
+
+use crate::util::map::{stress_concurrent, stress_concurrent_as_sequential};
+
+#[test]
+fn test_fc_map() {
+    let num = 64;
+    let map: FCMap<i32, i32, RawSpinLock, AVLTree<_, _>> = FCMap::new();
+
+    for i in 0..num {
+        assert_eq!(map.insert(&i, i), Ok(()));
+    }
+
+    for i in 0..num {
+        assert_eq!(map.insert(&i, i), Err(i));
+    }
+
+    for i in 0..num {
+        assert_eq!(map.get(&i), Some(i));
+        assert_eq!(map.lookup(&i, |value| value.map(|v| v * 2)), Some(i * 2));
+    }
+
+    for i in 0..num {
+        assert_eq!(map.remove(&i), Ok(i));
+    }
+
+    for i in 0..num {
+        assert_eq!(map.remove(&i), Err(()));
+    }
+}
+
+#[test]
+fn test_fc_map_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<FCMap<i32, String, RawSpinLock, AVLTree<_, _>>>();
+    assert_send_sync::<FCMap<i32, String, RawMutex, BTree<_, _>>>();
+}
+
+#[test]
+fn stress_fc_map_sequential() {
+    stress_concurrent_as_sequential::<u8, FCMap<_, _, RawSpinLock, AVLTree<_, _>>>(100_000);
+    stress_concurrent_as_sequential::<u8, FCMap<_, _, RawMutex, BTree<_, _>>>(100_000);
+}
+
+#[test]
+fn stress_fc_map_concurrent() {
+    stress_concurrent::<u32, FCMap<_, _, RawSpinLock, AVLTree<_, _>>>(200_000, 16, false);
+    stress_concurrent::<u32, FCMap<_, _, RawMutex, BTree<_, _>>>(200_000, 16, false);
+}
+
+#[test]
+fn assert_fc_map_concurrent() {
+    stress_concurrent::<u8, FCMap<_, _, RawSpinLock, AVLTree<_, _>>>(100_000, 32, true);
+    stress_concurrent::<u64, FCMap<_, _, RawMutex, BTree<_, _>>>(100_000, 32, true);
+}
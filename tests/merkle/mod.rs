@@ -0,0 +1,156 @@
+use std::{collections::hash_map::DefaultHasher, hash::Hasher as _};
+
+use cds::merkle::{Hasher, MerkleTree, Proof};
+use rand::{thread_rng, Rng};
+
+struct SipHasher;
+
+impl Hasher for SipHasher {
+    type Hash = [u8; 8];
+
+    fn digest(parts: &[&[u8]]) -> [u8; 8] {
+        let mut hasher = DefaultHasher::new();
+
+        for part in parts {
+            hasher.write(part);
+        }
+
+        hasher.finish().to_le_bytes()
+    }
+}
+
+/// get the root by splitting at the largest power of 2 less than the number of the leaves(RFC 6962)
+fn root(leaves: &[[u8; 8]]) -> [u8; 8] {
+    if leaves.len() == 1 {
+        return SipHasher::leaf(&leaves[0]);
+    }
+
+    let split = leaves.len().next_power_of_two() / 2;
+
+    SipHasher::node(&root(&leaves[..split]), &root(&leaves[split..]))
+}
+
+#[test]
+fn test_merkle_tree() {
+    let mut tree = MerkleTree::<SipHasher>::new();
+    assert!(tree.is_empty());
+    assert_eq!(tree.root(), None);
+    assert!(tree.proof(0).is_none());
+
+    tree.append(b"a");
+    assert_eq!(tree.root(), Some(&SipHasher::leaf(b"a")));
+    assert!(tree.proof(0).unwrap().path().is_empty());
+
+    tree.append(b"b");
+    tree.append(b"c");
+    assert_eq!(tree.len(), 3);
+    assert_eq!(tree.leaf(2), Some(&SipHasher::leaf(b"c")));
+
+    let ab = SipHasher::node(&SipHasher::leaf(b"a"), &SipHasher::leaf(b"b"));
+    let expected = SipHasher::node(&ab, &SipHasher::leaf(b"c"));
+    assert_eq!(tree.root(), Some(&expected));
+
+    // the last leaf is promoted without its sibling on the lowest level
+    let proof = tree.proof(2).unwrap();
+    assert_eq!((proof.index(), proof.tree_size()), (2, 3));
+    assert_eq!(proof.path(), &[ab]);
+    assert!(proof.verify(b"c", &expected));
+    assert!(!proof.verify(b"d", &expected));
+
+    let proof = tree.proof(0).unwrap();
+    assert!(proof.verify(b"a", &expected));
+    assert!(!proof.verify(b"b", &expected));
+
+    // the same path for the other index or size fails
+    let path = proof.path().to_vec();
+    assert!(!Proof::<SipHasher>::new(1, 3, path.clone()).verify(b"a", &expected));
+    assert!(!Proof::<SipHasher>::new(0, 2, path.clone()).verify(b"a", &expected));
+    assert!(!Proof::<SipHasher>::new(3, 3, path).verify(b"a", &expected));
+
+    // the proof is for the root at that time
+    tree.append(b"d");
+    assert!(!proof.verify(b"a", tree.root().unwrap()));
+    assert!(tree.proof(0).unwrap().verify(b"a", tree.root().unwrap()));
+}
+
+#[test]
+fn test_merkle_tree_leaf_hash() {
+    let data = (0..10u8).map(|i| [i; 8]).collect::<Vec<_>>();
+    let leaves = data
+        .iter()
+        .map(|data| SipHasher::leaf(data))
+        .collect::<Vec<_>>();
+
+    // the tree of the leaf hashes is the same as that of the data
+    let mut tree = MerkleTree::<SipHasher>::new();
+
+    for leaf in &leaves {
+        tree.append_leaf_hash(*leaf);
+    }
+
+    let expected = root(&data);
+    assert_eq!(tree.root(), Some(&expected));
+
+    for (i, leaf) in leaves.iter().enumerate() {
+        let proof = tree.proof(i).unwrap();
+        assert!(proof.verify_leaf_hash(leaf, &expected));
+        assert!(proof.verify(&data[i], &expected));
+
+        // the leaf hash is not hashed again
+        assert!(!proof.verify(leaf, &expected));
+        assert!(!proof.verify_leaf_hash(&leaves[(i + 1) % 10], &expected));
+    }
+}
+
+#[test]
+fn test_merkle_tree_second_preimage() {
+    let mut tree = MerkleTree::<SipHasher>::new();
+
+    for data in [b"a", b"b", b"c", b"d"].iter() {
+        tree.append(*data);
+    }
+
+    let root = *tree.root().unwrap();
+    let proof = tree.proof(0).unwrap();
+
+    // the internal node of a and b with the shorter path does not verify as a leaf
+    let ab = SipHasher::node(tree.leaf(0).unwrap(), tree.leaf(1).unwrap());
+    let short = proof.path()[1..].to_vec();
+
+    assert!(!Proof::<SipHasher>::new(0, 4, short.clone()).verify(&ab, &root));
+    assert!(!Proof::<SipHasher>::new(0, 2, short).verify(&ab, &root));
+
+    let mut concatenated = tree.leaf(0).unwrap().to_vec();
+    concatenated.extend_from_slice(tree.leaf(1).unwrap());
+    assert!(!Proof::<SipHasher>::new(0, 2, proof.path()[1..].to_vec()).verify(&concatenated, &root));
+}
+
+#[test]
+fn stress_merkle_tree() {
+    let mut rng = thread_rng();
+    let mut tree = MerkleTree::<SipHasher>::new();
+    let mut leaves = Vec::new();
+
+    for _ in 0..300 {
+        let leaf: [u8; 8] = rng.gen();
+        tree.append(&leaf);
+        leaves.push(leaf);
+
+        let expected = root(&leaves);
+        assert_eq!(tree.root(), Some(&expected));
+
+        let index = rng.gen_range(0..leaves.len());
+        let proof = tree.proof(index).unwrap();
+        assert!(proof.verify(&leaves[index], &expected));
+
+        let mut other = leaves[index];
+        other[0] = other[0].wrapping_add(1);
+        assert!(!proof.verify(&other, &expected));
+
+        // the path for the other index fails unless the leaves are the same
+        let moved = Proof::<SipHasher>::new(index ^ 1, leaves.len(), proof.path().to_vec());
+        if index ^ 1 < leaves.len() && leaves[index ^ 1] != leaves[index] {
+            assert!(!moved.verify(&leaves[index], &expected));
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::{
+    collections::HashMap as StdHashMap,
+    hash::{BuildHasherDefault, Hasher},
+};
+
+use cds::persistent::HashMap;
+use rand::{thread_rng, Rng};
+
+/// the hasher which maps the keys to 4 hashes, so many keys collide
+#[derive(Default)]
+struct CollidingHasher(u64);
+
+impl Hasher for CollidingHasher {
+    fn finish(&self) -> u64 {
+        self.0 % 4
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = self.0.wrapping_mul(31).wrapping_add(*byte as u64);
+        }
+    }
+}
+
+#[test]
+fn test_persistent_hashmap() {
+    let empty = HashMap::new();
+    let one = empty.insert(&1, "one").unwrap();
+    let two = one.insert(&2, "two").unwrap();
+
+    assert!(empty.is_empty());
+    assert_eq!(one.len(), 1);
+    assert_eq!(two.len(), 2);
+    assert_eq!(one.lookup(&2), None);
+    assert_eq!(two.lookup(&2), Some(&"two"));
+    assert_eq!(two.insert(&1, "uno").err(), Some("uno"));
+
+    let (removed, value) = two.remove(&1).unwrap();
+    assert_eq!(value, "one");
+    assert!(!removed.contains_key(&1));
+    assert!(two.contains_key(&1));
+    assert!(removed.remove(&1).is_err());
+
+    let mut pairs: Vec<_> = two.iter().map(|(k, v)| (*k, *v)).collect();
+    pairs.sort_unstable();
+    assert_eq!(pairs, vec![(1, "one"), (2, "two")]);
+}
+
+#[test]
+fn test_persistent_hashmap_collision() {
+    let mut map: HashMap<u32, u32, BuildHasherDefault<CollidingHasher>> = HashMap::default();
+
+    for i in 0..100 {
+        map = map.insert(&i, i * 10).unwrap();
+    }
+
+    assert_eq!(map.len(), 100);
+    assert!((0..100).all(|i| map.lookup(&i) == Some(&(i * 10))));
+    assert_eq!(map.iter().count(), 100);
+
+    for i in 0..100 {
+        let (next, value) = map.remove(&i).unwrap();
+        assert_eq!(value, i * 10);
+        assert_eq!(next.lookup(&i), None);
+        map = next;
+    }
+
+    assert!(map.is_empty());
+    assert_eq!(map.iter().count(), 0);
+}
+
+#[test]
+fn stress_persistent_hashmap() {
+    let mut rng = thread_rng();
+    let mut versions = vec![(HashMap::new(), StdHashMap::new())];
+
+    for _ in 0..20_000 {
+        // continue from a random version
+        let index = rng
+            .gen_range(0..versions.len())
+            .max(versions.len().saturating_sub(3));
+        let (map, expected) = versions[index].clone();
+        let key = rng.gen_range(0..2_000u32);
+
+        let (map, expected) = if rng.gen_bool(0.6) {
+            let value = rng.gen::<u64>();
+
+            match map.insert(&key, value) {
+                Ok(map) => {
+                    assert!(!expected.contains_key(&key));
+                    let mut expected = expected;
+                    expected.insert(key, value);
+                    (map, expected)
+                }
+                Err(v) => {
+                    assert_eq!(v, value);
+                    assert!(expected.contains_key(&key));
+                    (map, expected)
+                }
+            }
+        } else {
+            match map.remove(&key) {
+                Ok((map, value)) => {
+                    let mut expected = expected;
+                    assert_eq!(expected.remove(&key), Some(value));
+                    (map, expected)
+                }
+                Err(()) => {
+                    assert!(!expected.contains_key(&key));
+                    (map, expected)
+                }
+            }
+        };
+
+        assert_eq!(map.len(), expected.len());
+        versions.push((map, expected));
+    }
+
+    // the old versions are not changed
+    for (map, expected) in versions.iter().step_by(100) {
+        assert_eq!(map.len(), expected.len());
+        assert!(expected.iter().all(|(k, v)| map.lookup(k) == Some(v)));
+        assert_eq!(map.iter().count(), expected.len());
+    }
+}
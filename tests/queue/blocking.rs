@@ -0,0 +1,141 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use cds::queue::BlockingQueue;
+
+#[test]
+fn test_blocking_queue_sequential() {
+    let queue = BlockingQueue::new(3);
+    assert_eq!(queue.capacity(), 3);
+    assert!(queue.is_empty());
+    assert_eq!(queue.try_pop(), None);
+
+    for round in 0..100 {
+        for i in 0..3 {
+            assert_eq!(queue.try_push(round * 3 + i), Ok(()));
+        }
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.try_push(-1), Err(-1));
+
+        for i in 0..3 {
+            assert_eq!(queue.try_pop(), Some(round * 3 + i));
+        }
+        assert_eq!(queue.try_pop(), None);
+    }
+}
+
+#[test]
+fn test_blocking_queue_timeout() {
+    let queue = BlockingQueue::new(1);
+    let timeout = Duration::from_millis(50);
+
+    let start = Instant::now();
+    assert_eq!(queue.pop_timeout(timeout), None);
+    assert!(start.elapsed() >= timeout);
+
+    assert_eq!(queue.push_timeout(1, timeout), Ok(()));
+
+    let start = Instant::now();
+    assert_eq!(queue.push_timeout(2, timeout), Err(2));
+    assert!(start.elapsed() >= timeout);
+
+    assert_eq!(queue.pop_timeout(timeout), Some(1));
+
+    // the waiting pop is woken up by the push
+    thread::scope(|s| {
+        s.spawn(|| assert_eq!(queue.pop_timeout(Duration::from_secs(10)), Some(3)));
+
+        thread::sleep(timeout);
+        queue.push(3);
+    });
+}
+
+#[test]
+fn test_blocking_queue_backpressure() {
+    let queue = BlockingQueue::new(2);
+    let pushed = AtomicUsize::new(0);
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            for i in 0..4 {
+                queue.push(i);
+                pushed.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        // the producer is blocked until the consumer pops
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(pushed.load(Ordering::SeqCst), 2);
+
+        for i in 0..4 {
+            assert_eq!(queue.pop(), i);
+        }
+    });
+
+    assert_eq!(pushed.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+fn test_blocking_queue_concurrent() {
+    let thread_num = 8;
+    let per_thread = 100_000;
+    let queue = BlockingQueue::new(64);
+    let sum = AtomicUsize::new(0);
+
+    thread::scope(|s| {
+        for t in 0..thread_num {
+            let queue = &queue;
+            s.spawn(move || {
+                for i in 0..per_thread {
+                    queue.push(t * per_thread + i);
+                }
+            });
+        }
+
+        for _ in 0..thread_num {
+            let (queue, sum) = (&queue, &sum);
+            s.spawn(move || {
+                let mut last = vec![None; thread_num];
+
+                for _ in 0..per_thread {
+                    let value = queue.pop();
+                    let (t, i) = (value / per_thread, value % per_thread);
+
+                    // FIFO per producer
+                    assert!(last[t].map_or(true, |last| last < i));
+                    last[t] = Some(i);
+
+                    sum.fetch_add(value, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+
+    let total = thread_num * per_thread;
+    assert_eq!(sum.load(Ordering::Relaxed), total * (total - 1) / 2);
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn test_blocking_queue_drop() {
+    let value = Arc::new(0);
+    let queue = BlockingQueue::new(16);
+
+    for _ in 0..10 {
+        queue.push(value.clone());
+    }
+
+    for _ in 0..3 {
+        drop(queue.pop());
+    }
+
+    assert_eq!(Arc::strong_count(&value), 8);
+    drop(queue);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
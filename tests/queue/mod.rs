@@ -1,7 +1,11 @@
+mod blocking;
 mod fclock;
 mod lockfree;
+mod mpmc;
+mod mpsc;
 mod mutex;
 mod spinlock;
+mod spsc;
 
 use cds::queue::{FatNodeQueue, Queue};
 
@@ -0,0 +1,107 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use cds::queue::MPMCQueue;
+
+#[test]
+fn test_mpmc_queue_sequential() {
+    let queue = MPMCQueue::new(3);
+    assert_eq!(queue.capacity(), 3);
+    assert_eq!(queue.try_pop(), None);
+
+    for round in 0..100 {
+        for i in 0..3 {
+            assert_eq!(queue.try_push(round * 3 + i), Ok(()));
+        }
+        assert_eq!(queue.try_push(-1), Err(-1));
+
+        for i in 0..3 {
+            assert_eq!(queue.try_pop(), Some(round * 3 + i));
+        }
+        assert_eq!(queue.try_pop(), None);
+    }
+}
+
+#[test]
+fn test_mpmc_queue_small_capacity() {
+    for (capacity, expected) in [(1, 2), (2, 2)] {
+        let queue = MPMCQueue::new(capacity);
+        assert_eq!(queue.capacity(), expected);
+
+        for round in 0..10 {
+            for i in 0..expected {
+                assert_eq!(queue.try_push(round * expected + i), Ok(()));
+            }
+            assert_eq!(queue.try_push(usize::MAX), Err(usize::MAX));
+
+            for i in 0..expected {
+                assert_eq!(queue.try_pop(), Some(round * expected + i));
+            }
+            assert_eq!(queue.try_pop(), None);
+        }
+    }
+}
+
+#[test]
+fn test_mpmc_queue_concurrent() {
+    let thread_num = 8;
+    let per_thread = 100_000;
+    let queue = MPMCQueue::new(64);
+    let sum = AtomicUsize::new(0);
+
+    thread::scope(|s| {
+        for t in 0..thread_num {
+            let queue = &queue;
+            s.spawn(move || {
+                for i in 0..per_thread {
+                    queue.push(t * per_thread + i);
+                }
+            });
+        }
+
+        for _ in 0..thread_num {
+            let (queue, sum) = (&queue, &sum);
+            s.spawn(move || {
+                let mut last = vec![None; thread_num];
+
+                for _ in 0..per_thread {
+                    let value = queue.pop();
+                    let (t, i) = (value / per_thread, value % per_thread);
+
+                    // FIFO per producer
+                    assert!(last[t].map_or(true, |last| last < i));
+                    last[t] = Some(i);
+
+                    sum.fetch_add(value, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+
+    let total = thread_num * per_thread;
+    assert_eq!(sum.load(Ordering::Relaxed), total * (total - 1) / 2);
+    assert_eq!(queue.try_pop(), None);
+}
+
+#[test]
+fn test_mpmc_queue_drop() {
+    let value = Arc::new(0);
+    let queue = MPMCQueue::new(16);
+
+    for _ in 0..10 {
+        queue.push(value.clone());
+    }
+
+    for _ in 0..3 {
+        drop(queue.pop());
+    }
+
+    assert_eq!(Arc::strong_count(&value), 8);
+    drop(queue);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
@@ -0,0 +1,77 @@
+use std::{sync::Arc, thread};
+
+use cds::queue::MPSCQueue;
+
+#[test]
+fn test_mpsc_queue_sequential() {
+    let (producer, mut consumer) = MPSCQueue::new();
+    assert_eq!(consumer.try_pop(), None);
+
+    for i in 0..100 {
+        producer.push(i);
+    }
+
+    for i in 0..50 {
+        assert_eq!(consumer.try_pop(), Some(i));
+    }
+
+    for i in 100..150 {
+        producer.push(i);
+    }
+
+    for i in 50..150 {
+        assert_eq!(consumer.pop(), i);
+    }
+    assert_eq!(consumer.try_pop(), None);
+}
+
+#[test]
+fn test_mpsc_queue_concurrent() {
+    let thread_num = 8;
+    let per_thread = 100_000;
+    let (producer, mut consumer) = MPSCQueue::new();
+
+    thread::scope(|s| {
+        for t in 0..thread_num {
+            let producer = producer.clone();
+            s.spawn(move || {
+                for i in 0..per_thread {
+                    producer.push((t, i));
+                }
+            });
+        }
+
+        s.spawn(move || {
+            let mut next = vec![0; thread_num];
+
+            for _ in 0..thread_num * per_thread {
+                let (t, i) = consumer.pop();
+
+                // FIFO per producer
+                assert_eq!(next[t], i);
+                next[t] += 1;
+            }
+
+            assert_eq!(consumer.try_pop(), None);
+        });
+    });
+}
+
+#[test]
+fn test_mpsc_queue_drop() {
+    let value = Arc::new(0);
+    let (producer, mut consumer) = MPSCQueue::new();
+
+    for _ in 0..10 {
+        producer.push(value.clone());
+    }
+
+    for _ in 0..3 {
+        drop(consumer.pop());
+    }
+
+    assert_eq!(Arc::strong_count(&value), 8);
+    drop(producer);
+    drop(consumer);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
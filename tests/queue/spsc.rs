@@ -0,0 +1,119 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use cds::queue::SPSCQueue;
+
+#[test]
+fn test_spsc_queue_sequential() {
+    let (mut producer, mut consumer) = SPSCQueue::new(4);
+    assert_eq!(producer.capacity(), 4);
+
+    assert_eq!(consumer.pop(), None);
+
+    for i in 0..4 {
+        assert_eq!(producer.push(i), Ok(()));
+    }
+    assert_eq!(producer.push(4), Err(4));
+
+    for round in 0..100 {
+        assert_eq!(consumer.pop(), Some(round));
+        assert_eq!(producer.push(round + 4), Ok(()));
+    }
+
+    for i in 100..104 {
+        assert_eq!(consumer.pop(), Some(i));
+    }
+    assert_eq!(consumer.pop(), None);
+}
+
+#[test]
+fn test_spsc_queue_slice() {
+    let (mut producer, mut consumer) = SPSCQueue::new(5);
+    let mut buffer = [0; 4];
+
+    assert_eq!(producer.push_slice(&[0, 1, 2]), 3);
+    assert_eq!(consumer.pop_slice(&mut buffer), 3);
+    assert_eq!(buffer[..3], [0, 1, 2]);
+
+    // wrap around the buffer
+    assert_eq!(producer.push_slice(&[3, 4, 5, 6, 7, 8, 9]), 5);
+    assert_eq!(producer.push_slice(&[8]), 0);
+    assert_eq!(consumer.pop_slice(&mut buffer), 4);
+    assert_eq!(buffer, [3, 4, 5, 6]);
+    assert_eq!(consumer.pop(), Some(7));
+    assert_eq!(consumer.pop_slice(&mut buffer), 0);
+}
+
+#[test]
+fn test_spsc_queue_concurrent() {
+    let num = 1_000_000;
+    let (mut producer, mut consumer) = SPSCQueue::new(1024);
+
+    thread::scope(|s| {
+        s.spawn(move || {
+            let mut next = 0;
+
+            while next < num {
+                if next % 3 == 0 {
+                    let values: Vec<usize> = (next..(next + 100).min(num)).collect();
+                    next += producer.push_slice(&values);
+                } else if producer.push(next).is_ok() {
+                    next += 1;
+                }
+            }
+        });
+
+        s.spawn(move || {
+            let mut expected = 0;
+            let mut buffer = [0; 64];
+
+            while expected < num {
+                if expected % 2 == 0 {
+                    let count = consumer.pop_slice(&mut buffer);
+
+                    for &value in &buffer[..count] {
+                        assert_eq!(value, expected);
+                        expected += 1;
+                    }
+                } else if let Some(value) = consumer.pop() {
+                    assert_eq!(value, expected);
+                    expected += 1;
+                }
+            }
+
+            assert_eq!(consumer.pop(), None);
+        });
+    });
+}
+
+#[test]
+fn test_spsc_queue_drop() {
+    struct Counter(Arc<AtomicUsize>);
+
+    impl Drop for Counter {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let (mut producer, mut consumer) = SPSCQueue::new(8);
+
+    for _ in 0..12 {
+        let _ = producer.push(Counter(dropped.clone()));
+        drop(consumer.pop());
+        let _ = producer.push(Counter(dropped.clone()));
+    }
+
+    // 12 popped, 8 left in the queue, and 4 rejected
+    assert_eq!(dropped.load(Ordering::Relaxed), 16);
+
+    drop(producer);
+    drop(consumer);
+    assert_eq!(dropped.load(Ordering::Relaxed), 24);
+}
@@ -0,0 +1,140 @@
+use cds::rope::Rope;
+use rand::{thread_rng, Rng};
+
+/// get the byte offset of the char index in the string
+fn byte(text: &str, index: usize) -> usize {
+    text.char_indices()
+        .nth(index)
+        .map_or(text.len(), |(byte, _)| byte)
+}
+
+#[test]
+fn test_rope() {
+    let mut rope = Rope::from("hello world");
+
+    rope.insert(5, ",");
+    rope.insert(12, "!");
+    rope.insert(0, "안녕, ");
+    assert_eq!(rope.to_string(), "안녕, hello, world!");
+    assert_eq!(rope.len_chars(), 17);
+    assert_eq!(rope.len_bytes(), 21);
+    assert_eq!(rope.char(1), '녕');
+    assert_eq!(rope.char(4), 'h');
+
+    let slice = rope.slice(4..9);
+    assert_eq!(slice.to_string(), "hello");
+
+    rope.remove(0..4);
+    assert_eq!(rope.to_string(), "hello, world!");
+    assert_eq!(slice.to_string(), "hello");
+
+    let world = rope.split_off(7);
+    assert_eq!(rope.to_string(), "hello, ");
+    assert_eq!(world.to_string(), "world!");
+
+    rope.append(world);
+    assert_eq!(rope, Rope::from("hello, world!"));
+
+    rope.remove(0..rope.len_chars());
+    assert!(rope.is_empty());
+    assert_eq!(rope.chars().count(), 0);
+}
+
+#[test]
+fn test_rope_lines() {
+    let rope = Rope::from("first\nsecond\n\nfourth\n");
+
+    assert_eq!(rope.len_lines(), 5);
+    assert_eq!(
+        rope.lines().collect::<Vec<_>>(),
+        vec!["first", "second", "", "fourth", ""]
+    );
+    assert_eq!(rope.line_to_char(0), 0);
+    assert_eq!(rope.line_to_char(1), 6);
+    assert_eq!(rope.line_to_char(3), 14);
+    assert_eq!(rope.line_to_char(4), 21);
+
+    assert_eq!(Rope::new().lines().collect::<Vec<_>>(), vec![""]);
+}
+
+#[test]
+fn test_rope_large() {
+    let line = "the quick brown fox jumps over the lazy dog, 다람쥐 헌 쳇바퀴에 타고파\n";
+    let text = line.repeat(1_000);
+    let mut rope = Rope::from(text.as_str());
+
+    assert_eq!(rope.to_string(), text);
+    assert_eq!(rope.len_lines(), 1_001);
+    assert!(rope.chunks().count() > 1);
+
+    let line_chars = line.chars().count();
+
+    for i in 0..1_000 {
+        assert_eq!(rope.line_to_char(i), i * line_chars);
+    }
+
+    // the clone shares the nodes, and is not changed by the edit of the original
+    let clone = rope.clone();
+    rope.remove(line_chars..rope.len_chars() - line_chars);
+    assert_eq!(rope.to_string(), line.repeat(2));
+    assert_eq!(clone.to_string(), text);
+}
+
+#[test]
+fn test_rope_char_inserts() {
+    let mut rng = thread_rng();
+    let mut rope = Rope::new();
+    let mut text = String::new();
+
+    for i in 0..20_000 {
+        let index = rng.gen_range(0..=i);
+        let c = if rng.gen_bool(0.5) { "a" } else { "가" };
+
+        rope.insert(index, c);
+        text.insert_str(byte(&text, index), c);
+    }
+
+    assert_eq!(rope.to_string(), text);
+
+    // the small leaves on the seams are merged, so the leaves are not tiny
+    assert!(rope.chunks().count() <= 2 * rope.len_bytes() / 1024 + 1);
+}
+
+#[test]
+fn stress_rope() {
+    let mut rng = thread_rng();
+    let mut rope = Rope::new();
+    let mut expected = String::new();
+    let pieces = ["a", "bc", "가나다", "\n", "xyz\nw", &"long".repeat(100)];
+
+    for _ in 0..3_000 {
+        let len = expected.chars().count();
+
+        if rng.gen_bool(0.6) || len == 0 {
+            let index = rng.gen_range(0..=len);
+            let piece = pieces[rng.gen_range(0..pieces.len())];
+
+            rope.insert(index, piece);
+            expected.insert_str(byte(&expected, index), piece);
+        } else {
+            let start = rng.gen_range(0..len);
+            let end = rng.gen_range(start..=len.min(start + 500));
+
+            if rng.gen_bool(0.5) {
+                rope.remove(start..end);
+                expected.replace_range(byte(&expected, start)..byte(&expected, end), "");
+            } else {
+                assert_eq!(
+                    rope.slice(start..end).to_string(),
+                    expected[byte(&expected, start)..byte(&expected, end)]
+                );
+            }
+        }
+
+        assert_eq!(rope.len_chars(), expected.chars().count());
+        assert_eq!(rope.len_lines(), expected.matches('\n').count() + 1);
+    }
+
+    assert_eq!(rope.to_string(), expected);
+    assert!(rope.lines().eq(expected.split('\n').map(String::from)));
+}
@@ -0,0 +1,72 @@
+use crate::util::map::stress_sequential_with_check;
+use cds::{map::SequentialMap, scapegoattree::ScapegoatTree};
+use rand::{prelude::SliceRandom, thread_rng};
+
+/// the height of the scapegoat tree with α = 2/3 is at most floor(log_{3/2}(len)) + 1
+fn max_height(len: usize) -> usize {
+    ((len as f64).ln() / 1.5f64.ln()).floor() as usize + 1
+}
+
+#[test]
+fn test_scapegoat_tree() {
+    let num = 4096;
+    let mut keys: Vec<i32> = (0..num).collect();
+    keys.shuffle(&mut thread_rng());
+
+    let mut tree: ScapegoatTree<i32, i32> = ScapegoatTree::new();
+
+    for &key in &keys {
+        assert_eq!(tree.insert(&key, key), Ok(()));
+        assert!(tree.get_height() <= max_height(tree.len()));
+    }
+
+    for &key in &keys {
+        assert_eq!(tree.insert(&key, key), Err(key));
+    }
+
+    assert_eq!(tree.len(), num as usize);
+
+    for &key in &keys {
+        assert_eq!(tree.lookup(&key), Some(&key));
+        *tree.lookup_mut(&key).unwrap() += 1;
+    }
+
+    keys.shuffle(&mut thread_rng());
+
+    for &key in &keys {
+        assert_eq!(tree.remove(&key), Ok(key + 1));
+        assert_eq!(tree.remove(&key), Err(()));
+    }
+
+    assert!(tree.is_empty());
+    assert_eq!(tree.get_height(), 0);
+}
+
+#[test]
+fn test_scapegoat_tree_sorted() {
+    let num = 100_000;
+    let mut tree: ScapegoatTree<i32, i32> = ScapegoatTree::new();
+
+    for i in 0..num {
+        assert_eq!(tree.insert(&i, i), Ok(()));
+    }
+
+    assert!(tree.get_height() <= max_height(num as usize));
+
+    // removing more than 1/3 of the keys rebuilds the whole tree
+    for i in 0..(num / 3 + 1) {
+        assert_eq!(tree.remove(&i), Ok(i));
+    }
+
+    assert_eq!(
+        tree.get_height(),
+        f64::log2(tree.len() as f64).floor() as usize + 1
+    );
+}
+
+#[test]
+fn stress_scapegoat_tree() {
+    stress_sequential_with_check::<String, ScapegoatTree<_, _>, _>(100_000, |tree| {
+        assert!(tree.get_height() <= max_height(tree.len()) + 1)
+    });
+}
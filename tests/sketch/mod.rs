@@ -0,0 +1,91 @@
+mod spacesaving;
+
+use cds::sketch::TDigest;
+use rand::{prelude::SliceRandom, thread_rng, Rng};
+
+/// check the estimated quantiles of the sorted values by the error of the rank
+fn assert_accurate(digest: &TDigest, sorted: &[f64], max_error: f64) {
+    for &q in &[0.001, 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99, 0.999] {
+        let estimate = digest.quantile(q).unwrap();
+        let rank = sorted.partition_point(|value| *value < estimate) as f64 / sorted.len() as f64;
+
+        assert!(
+            (rank - q).abs() <= max_error,
+            "the quantile {} is estimated as the rank {}",
+            q,
+            rank
+        );
+    }
+}
+
+#[test]
+fn test_tdigest() {
+    let mut digest = TDigest::default();
+
+    assert!(digest.is_empty());
+    assert_eq!(digest.quantile(0.5), None);
+
+    digest.add(3.0);
+    assert_eq!(digest.quantile(0.0), Some(3.0));
+    assert_eq!(digest.quantile(0.5), Some(3.0));
+    assert_eq!(digest.quantile(1.0), Some(3.0));
+
+    for value in 1..=5 {
+        digest.add(value as f64);
+    }
+
+    assert_eq!(digest.count(), 6);
+    assert_eq!(digest.min(), Some(1.0));
+    assert_eq!(digest.max(), Some(5.0));
+    assert_eq!(digest.quantile(0.5), Some(3.0));
+}
+
+#[test]
+fn test_tdigest_uniform() {
+    let mut values: Vec<f64> = (0..100_000).map(|value| value as f64).collect();
+    values.shuffle(&mut thread_rng());
+
+    let mut digest = TDigest::new(100.0);
+
+    for &value in &values {
+        digest.add(value);
+    }
+
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    assert_eq!(digest.quantile(0.0), Some(0.0));
+    assert_eq!(digest.quantile(1.0), Some(99_999.0));
+    assert_accurate(&digest, &values, 0.005);
+}
+
+#[test]
+fn test_tdigest_merge() {
+    let mut rng = thread_rng();
+
+    // the exponential distribution like latencies, split over the threads
+    let mut values: Vec<f64> = (0..100_000)
+        .map(|_| -(1.0 - rng.gen::<f64>()).ln())
+        .collect();
+
+    let digests: Vec<_> = values
+        .chunks(10_000)
+        .map(|chunk| {
+            let mut digest = TDigest::default();
+            chunk.iter().for_each(|value| digest.add(*value));
+            digest
+        })
+        .collect();
+
+    let mut merged = TDigest::default();
+
+    for digest in &digests {
+        merged.merge(digest);
+    }
+
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    assert_eq!(merged.count(), 100_000);
+    assert_eq!(merged.min(), values.first().copied());
+    assert_eq!(merged.max(), values.last().copied());
+    assert_accurate(&merged, &values, 0.01);
+}
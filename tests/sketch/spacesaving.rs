@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use cds::sketch::SpaceSaving;
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_space_saving() {
+    let mut summary = SpaceSaving::new(3);
+
+    for item in ["a", "b", "a", "c", "a", "b"] {
+        summary.add(&item);
+    }
+
+    assert_eq!(summary.len(), 3);
+    assert_eq!(summary.estimate(&"a"), Some((3, 0)));
+    assert_eq!(summary.estimate(&"b"), Some((2, 0)));
+
+    // d replaces c whose count is the smallest
+    summary.add(&"d");
+    assert_eq!(summary.estimate(&"c"), None);
+    assert_eq!(summary.estimate(&"d"), Some((2, 1)));
+    assert_eq!(summary.total(), 7);
+
+    let top: Vec<_> = summary
+        .top(2)
+        .into_iter()
+        .map(|(item, _, _)| *item)
+        .collect();
+    assert_eq!(top[0], "a");
+    assert_eq!(summary.top(10).len(), 3);
+}
+
+#[test]
+fn stress_space_saving() {
+    let capacity = 100;
+    let mut rng = thread_rng();
+    let mut summary = SpaceSaving::new(capacity);
+    let mut counts: HashMap<u32, u64> = HashMap::new();
+
+    // the skewed stream where the small items are frequent
+    for _ in 0..100_000 {
+        let item = (rng.gen::<f64>().powi(4) * 10_000.0) as u32;
+
+        summary.add(&item);
+        *counts.entry(item).or_insert(0) += 1;
+    }
+
+    let total = summary.total();
+    let bound = total / capacity as u64;
+
+    for (item, count) in &counts {
+        match summary.estimate(item) {
+            Some((estimate, error)) => {
+                assert!(estimate - error <= *count && *count <= estimate);
+                assert!(error <= bound);
+            }
+            None => assert!(*count <= bound),
+        }
+    }
+
+    // the frequent items are reported with their true counts in the error bounds
+    let top = summary.top(10);
+    assert!(top.windows(2).all(|w| w[0].1 >= w[1].1));
+
+    for (item, estimate, error) in top {
+        let count = counts[item];
+        assert!(estimate - error <= count && count <= estimate);
+    }
+}
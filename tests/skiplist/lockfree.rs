@@ -0,0 +1,116 @@
+use std::thread;
+
+use cds::{map::ConcurrentMap, skiplist::LockFreeSkipList};
+use rand::{prelude::SliceRandom, thread_rng};
+
+use crate::util::map::{stress_concurrent, stress_concurrent_as_sequential};
+
+#[test]
+fn test_lockfree_skiplist() {
+    let num = 64;
+    let list: LockFreeSkipList<i32, i32> = LockFreeSkipList::new();
+
+    for i in 0..num {
+        assert_eq!(list.insert(&i, i), Ok(()));
+    }
+
+    for i in 0..num {
+        assert_eq!(list.insert(&i, i), Err(i));
+    }
+
+    for i in 0..num {
+        assert_eq!(list.get(&i), Some(i));
+    }
+
+    for i in 0..num {
+        assert_eq!(list.remove(&i), Ok(i));
+    }
+
+    for i in 0..num {
+        assert_eq!(list.remove(&i), Err(()));
+    }
+}
+
+#[test]
+fn test_lockfree_skiplist_iter() {
+    let num = 4096;
+    let mut keys: Vec<i32> = (0..num).collect();
+    keys.shuffle(&mut thread_rng());
+
+    let list: LockFreeSkipList<i32, i32> = LockFreeSkipList::new();
+
+    thread::scope(|s| {
+        for chunk in keys.chunks(num as usize / 8) {
+            let list = &list;
+            s.spawn(move || {
+                for &key in chunk {
+                    assert_eq!(list.insert(&key, key), Ok(()));
+                }
+            });
+        }
+    });
+
+    for key in (0..num).step_by(2) {
+        assert_eq!(list.remove(&key), Ok(key));
+    }
+
+    assert!(list.iter().eq((1..num).step_by(2).map(|key| (key, key))));
+}
+
+#[test]
+fn test_lockfree_skiplist_pop_first() {
+    let num = 4096;
+    let list: LockFreeSkipList<i32, i32> = LockFreeSkipList::new();
+
+    for key in (0..num).rev() {
+        assert_eq!(list.insert(&key, -key), Ok(()));
+    }
+
+    assert_eq!(list.pop_first(), Some((0, 0)));
+    assert_eq!(list.remove(&1), Ok(-1));
+    assert_eq!(list.pop_first(), Some((2, -2)));
+
+    // every key is popped by exactly one thread in the order of each thread
+    let mut popped: Vec<i32> = thread::scope(|s| {
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut popped = Vec::new();
+
+                    while let Some((key, value)) = list.pop_first() {
+                        assert_eq!(value, -key);
+                        popped.push(key);
+                    }
+
+                    assert!(popped.windows(2).all(|keys| keys[0] < keys[1]));
+                    popped
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    });
+
+    popped.sort_unstable();
+    assert!(popped.into_iter().eq(3..num));
+    assert_eq!(list.pop_first(), None);
+}
+
+#[test]
+fn stress_lockfree_skiplist_sequential() {
+    stress_concurrent_as_sequential::<u8, LockFreeSkipList<_, _>>(100_000);
+}
+
+#[test]
+fn stress_lockfree_skiplist_concurrent() {
+    stress_concurrent::<u32, LockFreeSkipList<_, _>>(200_000, 16, false);
+}
+
+#[test]
+fn assert_lockfree_skiplist_concurrent() {
+    stress_concurrent::<u8, LockFreeSkipList<_, _>>(100_000, 32, true);
+    stress_concurrent::<u64, LockFreeSkipList<_, _>>(100_000, 32, true);
+}
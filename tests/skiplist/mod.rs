@@ -0,0 +1,80 @@
+mod lockfree;
+
+use std::{collections::BTreeMap, ops::Bound};
+
+use crate::util::map::stress_sequential;
+use cds::{map::SequentialMap, skiplist::SkipList};
+use rand::{prelude::SliceRandom, thread_rng, Rng};
+
+#[test]
+fn test_skiplist() {
+    let num = 4096;
+    let mut keys: Vec<i32> = (0..num).collect();
+    keys.shuffle(&mut thread_rng());
+
+    let mut list: SkipList<i32, i32> = SkipList::new();
+
+    for &key in &keys {
+        assert_eq!(list.insert(&key, key), Ok(()));
+    }
+
+    for &key in &keys {
+        assert_eq!(list.insert(&key, key), Err(key));
+    }
+
+    assert_eq!(list.len(), num as usize);
+    assert!(list
+        .iter()
+        .map(|(k, v)| (*k, *v))
+        .eq((0..num).map(|i| (i, i))));
+
+    for &key in &keys {
+        assert_eq!(list.lookup(&key), Some(&key));
+        *list.lookup_mut(&key).unwrap() += 1;
+    }
+
+    keys.shuffle(&mut thread_rng());
+
+    for &key in &keys {
+        assert_eq!(list.remove(&key), Ok(key + 1));
+        assert_eq!(list.remove(&key), Err(()));
+    }
+
+    assert!(list.is_empty());
+    assert_eq!(list.iter().next(), None);
+}
+
+#[test]
+fn test_range_skiplist() {
+    let mut rng = thread_rng();
+    let mut list: SkipList<i32, i32> = SkipList::new();
+    let mut ref_map = BTreeMap::new();
+
+    for _ in 0..2000 {
+        let key = rng.gen_range(0..5000);
+
+        if list.insert(&key, -key).is_ok() {
+            ref_map.insert(key, -key);
+        }
+    }
+
+    assert!(list.iter().eq(ref_map.iter()));
+
+    for _ in 0..1000 {
+        let (a, b) = (rng.gen_range(-100..5100), rng.gen_range(-100..5100));
+        let (start, end) = (a.min(b), a.max(b));
+
+        assert!(list.range(start..end).eq(ref_map.range(start..end)));
+        assert!(list.range(start..=end).eq(ref_map.range(start..=end)));
+        assert!(list.range(start..).eq(ref_map.range(start..)));
+        assert!(list.range(..end).eq(ref_map.range(..end)));
+
+        let bounds = (Bound::Excluded(start), Bound::Included(end));
+        assert!(list.range(bounds).eq(ref_map.range(bounds)));
+    }
+}
+
+#[test]
+fn stress_skiplist() {
+    stress_sequential::<String, SkipList<_, _>>(100_000);
+}
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use cds::slab::Arena;
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_arena() {
+    let mut arena = Arena::new();
+
+    let a = arena.insert(1);
+    let b = arena.insert(2);
+    assert_eq!(arena.len(), 2);
+    assert_eq!(arena.get(a), Some(&1));
+
+    assert_eq!(arena.remove(a), Some(1));
+    assert_eq!(arena.remove(a), None);
+
+    // the slot is reused, but the stale handle does not reach the new value
+    let c = arena.insert(3);
+    assert_ne!(a, c);
+    assert_eq!(arena.get(a), None);
+    assert!(!arena.contains(a));
+    assert_eq!(arena.get(c), Some(&3));
+
+    *arena.get_mut(b).unwrap() += 10;
+    assert_eq!(arena.get_mut(a), None);
+
+    let mut values = arena.iter().map(|(_, value)| *value).collect::<Vec<_>>();
+    values.sort_unstable();
+    assert_eq!(values, vec![3, 12]);
+}
+
+#[test]
+fn stress_arena() {
+    let mut rng = thread_rng();
+    let mut arena = Arena::new();
+    let mut map = HashMap::new();
+    let mut removed = Vec::new();
+
+    for value in 0..100_000u64 {
+        if map.is_empty() || rng.gen_bool(0.5) {
+            let index = arena.insert(value);
+            assert_eq!(map.insert(index, value), None);
+        } else {
+            let index = *map.keys().next().unwrap();
+            assert_eq!(arena.remove(index), map.remove(&index));
+            removed.push(index);
+        }
+
+        assert_eq!(arena.len(), map.len());
+    }
+
+    for index in removed {
+        assert_eq!(arena.get(index), None);
+    }
+
+    for (index, value) in arena.iter() {
+        assert_eq!(map.get(&index), Some(value));
+    }
+}
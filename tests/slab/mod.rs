@@ -0,0 +1,61 @@
+mod arena;
+
+use std::collections::HashMap;
+
+use cds::slab::Slab;
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_slab() {
+    let mut slab = Slab::new();
+
+    let a = slab.insert("a");
+    let b = slab.insert("b");
+    let c = slab.insert("c");
+    assert_eq!((a, b, c), (0, 1, 2));
+    assert_eq!(slab.len(), 3);
+
+    assert_eq!(slab.remove(b), Some("b"));
+    assert_eq!(slab.remove(b), None);
+    assert!(!slab.contains(b));
+    assert_eq!(slab.get(a), Some(&"a"));
+    assert_eq!(slab[c], "c");
+
+    // the removed key is reused
+    assert_eq!(slab.vacant_key(), b);
+    assert_eq!(slab.insert("d"), b);
+    assert_eq!(slab.insert("e"), 3);
+
+    *slab.get_mut(a).unwrap() = "f";
+    assert_eq!(
+        slab.iter().collect::<Vec<_>>(),
+        vec![(0, &"f"), (1, &"d"), (2, &"c"), (3, &"e")]
+    );
+
+    slab.clear();
+    assert!(slab.is_empty());
+    assert_eq!(slab.get(a), None);
+}
+
+#[test]
+fn stress_slab() {
+    let mut rng = thread_rng();
+    let mut slab = Slab::new();
+    let mut map = HashMap::new();
+
+    for value in 0..100_000u64 {
+        if map.is_empty() || rng.gen_bool(0.6) {
+            let key = slab.insert(value);
+            assert_eq!(map.insert(key, value), None);
+        } else {
+            let key = *map.keys().next().unwrap();
+            assert_eq!(slab.remove(key), map.remove(&key));
+        }
+
+        assert_eq!(slab.len(), map.len());
+    }
+
+    for (key, value) in slab.iter() {
+        assert_eq!(map.get(&key), Some(value));
+    }
+}
@@ -0,0 +1,124 @@
+use cds::spatial::KdTree;
+use rand::{thread_rng, Rng};
+
+fn distance(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+#[test]
+fn test_kdtree() {
+    let points = vec![
+        ([2.0, 3.0], 'a'),
+        ([5.0, 4.0], 'b'),
+        ([9.0, 6.0], 'c'),
+        ([4.0, 7.0], 'd'),
+        ([8.0, 1.0], 'e'),
+        ([7.0, 2.0], 'f'),
+    ];
+    let mut tree = KdTree::build(points);
+    assert_eq!(tree.len(), 6);
+
+    let nearest: Vec<_> = tree
+        .nearest(&[9.0, 2.0], 2)
+        .into_iter()
+        .map(|(_, value)| *value)
+        .collect();
+    assert_eq!(nearest, vec!['e', 'f']);
+
+    let mut inside: Vec<_> = tree
+        .range(&[4.0, 2.0], &[8.0, 7.0])
+        .into_iter()
+        .map(|(_, value)| *value)
+        .collect();
+    inside.sort_unstable();
+    assert_eq!(inside, vec!['b', 'd', 'f']);
+
+    tree.insert([9.0, 2.5], 'g');
+    assert_eq!(tree.len(), 7);
+    assert_eq!(tree.nearest(&[9.0, 2.0], 1), vec![(&[9.0, 2.5], &'g')]);
+    assert_eq!(tree.nearest(&[0.0, 0.0], 10).len(), 7);
+    assert!(tree.nearest(&[0.0, 0.0], 0).is_empty());
+
+    let empty = KdTree::<(), 2>::new();
+    assert!(empty.nearest(&[0.0, 0.0], 3).is_empty());
+    assert!(empty.range(&[0.0, 0.0], &[1.0, 1.0]).is_empty());
+}
+
+#[test]
+fn test_kdtree_sorted_insert() {
+    // the sorted points make a deep tree without rebalancing
+    let n = 20_000;
+    let mut tree = KdTree::new();
+
+    for i in 0..n {
+        tree.insert([i as f64, i as f64], i);
+    }
+
+    assert_eq!(
+        tree.nearest(&[n as f64, n as f64], 2),
+        vec![
+            (&[(n - 1) as f64, (n - 1) as f64], &(n - 1)),
+            (&[(n - 2) as f64, (n - 2) as f64], &(n - 2)),
+        ]
+    );
+    assert_eq!(tree.range(&[-1.0, -1.0], &[n as f64, n as f64]).len(), n);
+}
+
+#[test]
+fn stress_kdtree() {
+    let mut rng = thread_rng();
+    let mut points: Vec<[f64; 3]> = (0..1_000)
+        .map(|_| [rng.gen(), rng.gen(), rng.gen()])
+        .collect();
+
+    let mut tree = KdTree::build(points.iter().copied().zip(0..).collect());
+
+    // the inserted points go without rebalancing
+    for index in points.len()..1_500 {
+        let point = [rng.gen(), rng.gen(), rng.gen()];
+        tree.insert(point, index);
+        points.push(point);
+    }
+
+    for _ in 0..100 {
+        let query = [rng.gen(), rng.gen(), rng.gen()];
+        let k = rng.gen_range(1..20);
+
+        let mut expected: Vec<f64> = points.iter().map(|point| distance(&query, point)).collect();
+        expected.sort_by(f64::total_cmp);
+        expected.truncate(k);
+
+        let nearest: Vec<f64> = tree
+            .nearest(&query, k)
+            .into_iter()
+            .map(|(point, value)| {
+                assert_eq!(&points[*value], point);
+                distance(&query, point)
+            })
+            .collect();
+        assert_eq!(nearest, expected);
+
+        let (a, b): ([f64; 3], [f64; 3]) = (
+            [rng.gen(), rng.gen(), rng.gen()],
+            [rng.gen(), rng.gen(), rng.gen()],
+        );
+        let min = [a[0].min(b[0]), a[1].min(b[1]), a[2].min(b[2])];
+        let max = [a[0].max(b[0]), a[1].max(b[1]), a[2].max(b[2])];
+
+        let mut expected: Vec<usize> = (0..points.len())
+            .filter(|&index| {
+                (0..3).all(|axis| {
+                    min[axis] <= points[index][axis] && points[index][axis] <= max[axis]
+                })
+            })
+            .collect();
+        let mut inside: Vec<usize> = tree
+            .range(&min, &max)
+            .into_iter()
+            .map(|(_, value)| *value)
+            .collect();
+        expected.sort_unstable();
+        inside.sort_unstable();
+        assert_eq!(inside, expected);
+    }
+}
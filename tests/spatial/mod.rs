@@ -0,0 +1,2 @@
+mod kdtree;
+mod orthtree;
@@ -0,0 +1,117 @@
+use cds::spatial::{Octree, QuadTree};
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_quadtree() {
+    let mut tree = QuadTree::new([0.0, 0.0], [8.0, 8.0], 2);
+
+    assert_eq!(tree.insert([1.0, 1.0], 'a'), Ok(()));
+    assert_eq!(tree.insert([2.0, 2.0], 'b'), Ok(()));
+    assert_eq!(tree.cells().count(), 1);
+
+    // the third point splits the root into 4 cells
+    assert_eq!(tree.insert([6.0, 6.0], 'c'), Ok(()));
+    assert_eq!(tree.insert([9.0, 1.0], 'd'), Err('d'));
+    assert_eq!(tree.len(), 3);
+
+    let cells: Vec<_> = tree
+        .cells()
+        .map(|(min, max, points)| (min, max, points.len()))
+        .collect();
+    assert_eq!(
+        cells,
+        vec![([0.0, 0.0], [4.0, 4.0], 2), ([4.0, 4.0], [8.0, 8.0], 1)]
+    );
+
+    let mut inside: Vec<_> = tree
+        .query(&[1.5, 0.0], &[8.0, 8.0])
+        .into_iter()
+        .map(|(_, value)| *value)
+        .collect();
+    inside.sort_unstable();
+    assert_eq!(inside, vec!['b', 'c']);
+
+    // the cells fitting in the capacity are merged back
+    assert_eq!(tree.remove(&[6.0, 6.0]), Ok('c'));
+    assert_eq!(tree.remove(&[6.0, 6.0]), Err(()));
+    assert_eq!(tree.remove(&[9.0, 9.0]), Err(()));
+    assert_eq!(tree.cells().count(), 1);
+    assert_eq!(tree.len(), 2);
+}
+
+#[test]
+fn test_quadtree_same_points() {
+    let mut tree = QuadTree::new([0.0, 0.0], [1.0, 1.0], 1);
+
+    for value in 0..10 {
+        assert_eq!(tree.insert([0.5, 0.5], value), Ok(()));
+    }
+
+    assert_eq!(tree.query(&[0.5, 0.5], &[0.5, 0.5]).len(), 10);
+
+    for _ in 0..10 {
+        assert!(tree.remove(&[0.5, 0.5]).is_ok());
+    }
+
+    assert!(tree.is_empty());
+    assert_eq!(tree.cells().count(), 0);
+}
+
+#[test]
+fn stress_octree() {
+    let mut rng = thread_rng();
+    let mut tree = Octree::new([0.0; 3], [1.0; 3], 4);
+    let mut points: Vec<([f64; 3], usize)> = Vec::new();
+
+    for value in 0..5_000 {
+        if points.is_empty() || rng.gen_bool(0.7) {
+            let point = [rng.gen(), rng.gen(), rng.gen()];
+            assert_eq!(tree.insert(point, value), Ok(()));
+            points.push((point, value));
+        } else {
+            let (point, _) = points.swap_remove(rng.gen_range(0..points.len()));
+            assert!(tree.remove(&point).is_ok());
+        }
+
+        assert_eq!(tree.len(), points.len());
+    }
+
+    let mut count = 0;
+
+    for (min, max, bucket) in tree.cells() {
+        assert!(!bucket.is_empty());
+
+        for (point, _) in bucket {
+            assert!((0..3).all(|axis| min[axis] <= point[axis] && point[axis] <= max[axis]));
+        }
+
+        count += bucket.len();
+    }
+
+    assert_eq!(count, points.len());
+
+    for _ in 0..100 {
+        let (a, b): ([f64; 3], [f64; 3]) = (
+            [rng.gen(), rng.gen(), rng.gen()],
+            [rng.gen(), rng.gen(), rng.gen()],
+        );
+        let low = [a[0].min(b[0]), a[1].min(b[1]), a[2].min(b[2])];
+        let high = [a[0].max(b[0]), a[1].max(b[1]), a[2].max(b[2])];
+
+        let mut expected: Vec<usize> = points
+            .iter()
+            .filter(|(point, _)| {
+                (0..3).all(|axis| low[axis] <= point[axis] && point[axis] <= high[axis])
+            })
+            .map(|(_, value)| *value)
+            .collect();
+        let mut inside: Vec<usize> = tree
+            .query(&low, &high)
+            .into_iter()
+            .map(|(_, value)| *value)
+            .collect();
+        expected.sort_unstable();
+        inside.sort_unstable();
+        assert_eq!(inside, expected);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::util::map::stress_sequential;
+use cds::{map::SequentialMap, splaytree::SplayTree};
+use rand::{prelude::SliceRandom, thread_rng};
+
+#[test]
+fn test_splay_tree() {
+    let num = 4096;
+    let mut keys: Vec<i32> = (0..num).collect();
+    keys.shuffle(&mut thread_rng());
+
+    let mut tree: SplayTree<i32, i32> = SplayTree::new();
+
+    for &key in &keys {
+        assert_eq!(tree.insert(&key, key), Ok(()));
+        assert_eq!(tree.root(), Some((&key, &key)));
+    }
+
+    for &key in &keys {
+        assert_eq!(tree.insert(&key, key), Err(key));
+    }
+
+    assert_eq!(tree.len(), num as usize);
+
+    keys.shuffle(&mut thread_rng());
+
+    for &key in &keys {
+        assert_eq!(tree.lookup(&key), Some(&key));
+        assert_eq!(tree.root(), Some((&key, &key)));
+    }
+
+    assert_eq!(tree.lookup(&num), None);
+
+    for &key in &keys {
+        *tree.lookup_mut(&key).unwrap() += 1;
+    }
+
+    keys.shuffle(&mut thread_rng());
+
+    for &key in &keys {
+        assert_eq!(tree.remove(&key), Ok(key + 1));
+        assert_eq!(tree.remove(&key), Err(()));
+    }
+
+    assert!(tree.is_empty());
+    assert_eq!(tree.root(), None);
+}
+
+#[test]
+fn test_splay_tree_sorted() {
+    let num = 100_000;
+    let mut tree: SplayTree<i32, i32> = SplayTree::new();
+
+    // makes the path-like tree, then the lookups shorten it
+    for i in 0..num {
+        assert_eq!(tree.insert(&i, i), Ok(()));
+    }
+
+    for i in 0..num {
+        assert_eq!(tree.lookup(&i), Some(&i));
+    }
+
+    for i in (0..num).rev() {
+        assert_eq!(tree.remove(&i), Ok(i));
+    }
+
+    assert!(tree.is_empty());
+}
+
+#[test]
+fn stress_splay_tree() {
+    stress_sequential::<String, SplayTree<_, _>>(100_000);
+}
@@ -0,0 +1,49 @@
+use cds::lock::{spinlock::RawSpinLock, RawMutex};
+use cds::stack::{ConcurrentStack, FCStack};
+use crossbeam_utils::thread::scope;
+
+fn test_fc_stack<L: cds::lock::RawSimpleLock>() {
+    let stack: FCStack<_, L> = FCStack::new();
+
+    for i in 0..100 {
+        stack.push(i);
+    }
+
+    for i in (0..100).rev() {
+        assert_eq!(stack.try_pop(), Some(i));
+    }
+
+    assert_eq!(stack.try_pop(), None);
+
+    scope(|scope| {
+        for _ in 0..10 {
+            scope.spawn(|_| {
+                for i in 0..10_000 {
+                    stack.push(i);
+                    assert!(stack.try_pop().is_some());
+                }
+            });
+        }
+    })
+    .unwrap();
+
+    assert!(stack.try_pop().is_none());
+}
+
+#[test]
+fn test_fc_stack_spinlock() {
+    test_fc_stack::<RawSpinLock>();
+}
+
+#[test]
+fn test_fc_stack_mutex() {
+    test_fc_stack::<RawMutex>();
+}
+
+#[test]
+fn test_fc_stack_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<FCStack<String, RawSpinLock>>();
+    assert_send_sync::<FCStack<String, RawMutex>>();
+}
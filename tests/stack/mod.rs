@@ -1,4 +1,5 @@
 mod eb;
+mod fclock;
 mod mutex;
 mod spinlock;
 mod stack;
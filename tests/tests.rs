@@ -1,7 +1,30 @@
 mod avltree;
+mod bitset;
+mod bplustree;
+mod bst;
 mod btree;
+mod cache;
+mod dsu;
+mod filter;
+mod hashmap;
+mod heap;
+mod intervaltree;
 mod linkedlist;
 mod lock;
+mod map;
+mod merkle;
+mod persistent;
 mod queue;
+mod rope;
+mod scapegoattree;
+mod sketch;
+mod skiplist;
+mod slab;
+mod spatial;
+mod splaytree;
 mod stack;
+mod treap;
+mod trie;
 mod util;
+mod wavelet;
+mod wbtree;
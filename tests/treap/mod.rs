@@ -0,0 +1,101 @@
+use crate::util::map::stress_sequential_with_check;
+use cds::{map::SequentialMap, treap::Treap};
+use rand::{prelude::SliceRandom, thread_rng};
+
+#[test]
+fn test_treap() {
+    let num = 4096;
+    let mut keys: Vec<i32> = (0..num).collect();
+    keys.shuffle(&mut thread_rng());
+
+    let mut treap: Treap<i32, i32> = Treap::new();
+
+    for &key in &keys {
+        assert_eq!(treap.insert(&key, key), Ok(()));
+    }
+
+    for &key in &keys {
+        assert_eq!(treap.insert(&key, key), Err(key));
+    }
+
+    assert_eq!(treap.len(), num as usize);
+    assert_eq!(treap.validate(), Ok(()));
+
+    for &key in &keys {
+        assert_eq!(treap.lookup(&key), Some(&key));
+        *treap.lookup_mut(&key).unwrap() += 1;
+    }
+
+    for &key in &keys {
+        assert_eq!(treap.remove(&key), Ok(key + 1));
+        assert_eq!(treap.remove(&key), Err(()));
+    }
+
+    assert!(treap.is_empty());
+}
+
+#[test]
+fn test_treap_sorted() {
+    let num = 100_000;
+    let mut treap: Treap<i32, i32> = Treap::new();
+
+    for i in 0..num {
+        assert_eq!(treap.insert(&i, i), Ok(()));
+    }
+
+    // expected height is about 3 * log2(n)
+    assert!(treap.get_height() < 4 * f32::log2(num as f32) as usize);
+    assert_eq!(treap.validate(), Ok(()));
+}
+
+#[test]
+fn test_split_off_merge_treap() {
+    let num = 1000;
+
+    for at in [-1, 0, 1, 333, 500, 999, 1000, 1001] {
+        let mut treap: Treap<i32, i32> = (0..num).fold(Treap::new(), |mut treap, i| {
+            treap.insert(&i, i).unwrap();
+            treap
+        });
+
+        let mut other = treap.split_off(&at);
+        assert_eq!(treap.validate(), Ok(()));
+        assert_eq!(other.validate(), Ok(()));
+
+        let split = at.clamp(0, num);
+        assert_eq!(treap.len(), split as usize);
+        assert_eq!(other.len(), (num - split) as usize);
+
+        for i in 0..num {
+            if i < at {
+                assert_eq!(treap.lookup(&i), Some(&i));
+                assert_eq!(other.lookup(&i), None);
+            } else {
+                assert_eq!(treap.lookup(&i), None);
+                assert_eq!(other.lookup(&i), Some(&i));
+            }
+        }
+
+        if split > 0 && split < num {
+            // the keys are overlapped
+            assert_eq!(other.insert(&0, 0), Ok(()));
+            other = treap.merge(other).err().unwrap();
+            assert_eq!(other.remove(&0), Ok(0));
+        }
+
+        assert!(treap.merge(other).is_ok());
+        assert_eq!(treap.validate(), Ok(()));
+        assert_eq!(treap.len(), num as usize);
+
+        for i in 0..num {
+            assert_eq!(treap.lookup(&i), Some(&i));
+        }
+    }
+}
+
+#[test]
+fn stress_treap() {
+    stress_sequential_with_check::<String, Treap<_, _>, _>(100_000, |treap| {
+        treap.validate().unwrap()
+    });
+}
@@ -0,0 +1,92 @@
+mod radix;
+
+use crate::util::map::stress_sequential;
+use cds::trie::Trie;
+
+#[test]
+fn test_trie() {
+    let mut trie = Trie::new();
+
+    for (i, word) in ["tea", "ten", "to", "inn", "in", "i"].iter().enumerate() {
+        assert_eq!(trie.insert(word, i), Ok(()));
+    }
+
+    assert_eq!(trie.insert("to", 10), Err(10));
+    assert_eq!(trie.len(), 6);
+
+    assert!(trie.contains("tea"));
+    assert!(trie.contains("in"));
+    assert!(!trie.contains("te"));
+    assert!(!trie.contains("team"));
+    assert_eq!(trie.get("ten"), Some(&1));
+
+    *trie.get_mut("ten").unwrap() = 11;
+    assert_eq!(trie.get("ten"), Some(&11));
+
+    let keys: Vec<_> = trie.iter().map(|(key, _)| key).collect();
+    assert_eq!(
+        keys,
+        ["i", "in", "inn", "tea", "ten", "to"]
+            .iter()
+            .map(|key| key.as_bytes().to_vec())
+            .collect::<Vec<_>>()
+    );
+
+    let keys: Vec<_> = trie.iter_prefix("te").collect();
+    assert_eq!(keys, vec![(b"tea".to_vec(), &0), (b"ten".to_vec(), &11)]);
+    assert_eq!(trie.iter_prefix("in").count(), 2);
+    assert_eq!(trie.iter_prefix("x").count(), 0);
+
+    assert_eq!(trie.longest_prefix("inner"), Some((3, &3)));
+    assert_eq!(trie.longest_prefix("ix"), Some((1, &5)));
+    assert_eq!(trie.longest_prefix("tex"), None);
+
+    assert_eq!(trie.remove("in"), Ok(4));
+    assert_eq!(trie.remove("in"), Err(()));
+    assert_eq!(trie.remove("te"), Err(()));
+    assert!(trie.contains("inn"));
+    assert_eq!(trie.longest_prefix("inner"), Some((3, &3)));
+
+    for word in ["tea", "ten", "to", "inn", "i"] {
+        assert!(trie.remove(word).is_ok());
+    }
+
+    assert!(trie.is_empty());
+    assert_eq!(trie.iter().count(), 0);
+}
+
+#[test]
+fn test_trie_empty_key() {
+    let mut trie = Trie::new();
+
+    assert_eq!(trie.insert("", 0), Ok(()));
+    assert_eq!(trie.insert("a", 1), Ok(()));
+    assert_eq!(trie.longest_prefix("b"), Some((0, &0)));
+    assert_eq!(trie.iter().count(), 2);
+    assert_eq!(trie.remove(""), Ok(0));
+    assert_eq!(trie.longest_prefix("b"), None);
+}
+
+#[test]
+fn test_trie_long_key() {
+    // a node for each byte of the key
+    let key = vec![b'a'; 1_000_000];
+    let mut trie = Trie::new();
+
+    assert_eq!(trie.insert(&key, 0), Ok(()));
+    assert_eq!(trie.insert(&key[..10], 1), Ok(()));
+    assert_eq!(trie.get(&key), Some(&0));
+
+    assert_eq!(trie.remove(&key), Ok(0));
+    assert_eq!(trie.remove(&key), Err(()));
+    assert_eq!(trie.get(&key[..10]), Some(&1));
+    assert_eq!(trie.iter().count(), 1);
+
+    assert_eq!(trie.insert(&key, 2), Ok(()));
+    drop(trie);
+}
+
+#[test]
+fn stress_trie() {
+    stress_sequential::<String, Trie<_>>(100_000);
+}
@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+
+use crate::util::map::stress_sequential;
+use cds::trie::RadixTrie;
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_radix_trie() {
+    let mut trie = RadixTrie::new();
+
+    for (i, word) in [
+        "romane",
+        "romanus",
+        "romulus",
+        "rubens",
+        "ruber",
+        "rubicon",
+        "rubicundus",
+    ]
+    .iter()
+    .enumerate()
+    {
+        assert_eq!(trie.insert(word, i), Ok(()));
+    }
+
+    assert_eq!(trie.insert("rubens", 10), Err(10));
+    assert_eq!(trie.len(), 7);
+
+    assert!(trie.contains("romulus"));
+    assert!(!trie.contains("rom"));
+    assert!(!trie.contains("romanes"));
+    assert_eq!(trie.get("ruber"), Some(&4));
+
+    // the prefix ends in the middle of the edge
+    let keys: Vec<_> = trie.iter_prefix("rubi").map(|(key, _)| key).collect();
+    assert_eq!(keys, vec![b"rubicon".to_vec(), b"rubicundus".to_vec()]);
+    assert_eq!(trie.iter_prefix("rom").count(), 3);
+    assert_eq!(trie.iter_prefix("rox").count(), 0);
+    assert_eq!(trie.iter_prefix("romanesque").count(), 0);
+
+    assert_eq!(trie.longest_prefix("rubensx"), Some((6, &3)));
+    assert_eq!(trie.longest_prefix("rube"), None);
+
+    assert_eq!(trie.remove("ruber"), Ok(4));
+    assert_eq!(trie.remove("ruber"), Err(()));
+    assert_eq!(trie.remove("rub"), Err(()));
+    assert_eq!(trie.get("rubens"), Some(&3));
+
+    assert_eq!(trie.insert("rub", 20), Ok(()));
+    assert_eq!(trie.longest_prefix("rubber"), Some((3, &20)));
+
+    let keys: Vec<_> = trie.iter().map(|(key, _)| key).collect();
+    let mut sorted = keys.clone();
+    sorted.sort();
+    assert_eq!(keys, sorted);
+    assert_eq!(keys.len(), 7);
+}
+
+#[test]
+fn test_radix_trie_with_btreemap() {
+    let mut rng = thread_rng();
+    let mut trie = RadixTrie::new();
+    let mut map = BTreeMap::new();
+
+    for _ in 0..100_000 {
+        // short keys on the small alphabet make many splits and merges
+        let len = rng.gen_range(0..8);
+        let key: Vec<u8> = (0..len).map(|_| rng.gen_range(b'a'..b'd')).collect();
+
+        if rng.gen_bool(0.5) {
+            assert_eq!(
+                trie.insert(&key, len).is_ok(),
+                map.insert(key, len).is_none()
+            );
+        } else {
+            assert_eq!(trie.remove(&key).ok(), map.remove(&key));
+        }
+
+        assert_eq!(trie.len(), map.len());
+    }
+
+    assert!(trie
+        .iter()
+        .map(|(key, value)| (key, *value))
+        .eq(map.clone()));
+
+    let prefix = b"ab";
+    assert!(trie
+        .iter_prefix(prefix)
+        .map(|(key, value)| (key, *value))
+        .eq(map.into_iter().filter(|(key, _)| key.starts_with(prefix))));
+}
+
+#[test]
+fn test_radix_trie_long_key() {
+    let key = vec![b'a'; 1_000_000];
+    let mut trie = RadixTrie::new();
+
+    assert_eq!(trie.insert(&key, 0), Ok(()));
+    assert_eq!(trie.insert(&key[..10], 1), Ok(()));
+    assert_eq!(trie.get(&key), Some(&0));
+    assert_eq!(trie.remove(&key), Ok(0));
+    assert_eq!(trie.remove(&key), Err(()));
+    assert_eq!(trie.get(&key[..10]), Some(&1));
+}
+
+#[test]
+fn test_radix_trie_deep_chain() {
+    // the nested keys make a chain of the nodes, and the shorter key splits only the first edge
+    let n = 20_000;
+    let key = vec![b'a'; n];
+    let mut trie = RadixTrie::new();
+
+    for len in (1..=n).rev() {
+        assert_eq!(trie.insert(&key[..len], len), Ok(()));
+    }
+
+    assert_eq!(trie.get(&key), Some(&n));
+    assert_eq!(trie.longest_prefix(&key), Some((n, &n)));
+
+    // remove the middle of the chain, which merges the edges
+    for len in (n / 4..n / 2).rev() {
+        assert_eq!(trie.remove(&key[..len]), Ok(len));
+    }
+
+    assert_eq!(trie.get(&key[..n / 3]), None);
+    assert_eq!(
+        trie.longest_prefix(&key[..n / 3]),
+        Some((n / 4 - 1, &(n / 4 - 1)))
+    );
+    assert_eq!(trie.remove(&key), Ok(n));
+    assert_eq!(trie.len(), n - n / 4 - 1);
+    drop(trie);
+}
+
+#[test]
+fn stress_radix_trie() {
+    stress_sequential::<String, RadixTrie<_>>(100_000);
+}
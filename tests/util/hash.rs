@@ -0,0 +1,13 @@
+use std::hash::Hasher;
+
+/// the hasher that maps every key to the same hash to force collisions
+#[derive(Default)]
+pub struct ConstantHasher;
+
+impl Hasher for ConstantHasher {
+    fn finish(&self) -> u64 {
+        0
+    }
+
+    fn write(&mut self, _: &[u8]) {}
+}
@@ -0,0 +1,50 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use cds::heap::PriorityQueue;
+use rand::{thread_rng, Rng};
+
+pub fn test_simple_priority_queue<H: PriorityQueue<u64>>() {
+    let mut heap = H::new();
+    assert!(heap.is_empty());
+    assert_eq!(heap.pop(), None);
+
+    for value in [5, 3, 8, 1, 9, 2, 7] {
+        heap.push(value);
+    }
+
+    assert_eq!(heap.len(), 7);
+    assert_eq!(heap.peek(), Some(&1));
+
+    for value in [1, 2, 3, 5, 7, 8, 9] {
+        assert_eq!(heap.pop(), Some(value));
+    }
+
+    assert_eq!(heap.pop(), None);
+    assert!(heap.is_empty());
+}
+
+/// run the random push and pop, and compare the result with the binary heap of std
+pub fn stress_priority_queue<H: PriorityQueue<u64>>(iter: usize) {
+    let mut heap = H::new();
+    let mut reference = BinaryHeap::new();
+    let mut rng = thread_rng();
+
+    for _ in 0..iter {
+        if rng.gen_bool(0.6) {
+            let value = rng.gen_range(0..1_000);
+            heap.push(value);
+            reference.push(Reverse(value));
+        } else {
+            assert_eq!(heap.pop(), reference.pop().map(|Reverse(v)| v));
+        }
+
+        assert_eq!(heap.len(), reference.len());
+        assert_eq!(heap.peek(), reference.peek().map(|Reverse(v)| v));
+    }
+
+    while let Some(Reverse(value)) = reference.pop() {
+        assert_eq!(heap.pop(), Some(value));
+    }
+
+    assert_eq!(heap.pop(), None);
+}
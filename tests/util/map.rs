@@ -29,11 +29,61 @@ enum OperationType {
     None, // the operation for not existing key on the map
 }
 
+/// the operations of the map which the sequential stress test runs
+trait StressMap<K, V> {
+    fn new() -> Self;
+    fn insert(&mut self, key: &K, value: V) -> Result<(), V>;
+    fn lookup(&self, key: &K) -> Option<&V>;
+    fn remove(&mut self, key: &K) -> Result<V, ()>;
+}
+
+/// the sequential map under the stress test
+struct Sequential<M>(M);
+
+impl<K: Eq, V, M: SequentialMap<K, V>> StressMap<K, V> for Sequential<M> {
+    fn new() -> Self {
+        Self(M::new())
+    }
+
+    fn insert(&mut self, key: &K, value: V) -> Result<(), V> {
+        self.0.insert(key, value)
+    }
+
+    fn lookup(&self, key: &K) -> Option<&V> {
+        self.0.lookup(key)
+    }
+
+    fn remove(&mut self, key: &K) -> Result<V, ()> {
+        self.0.remove(key)
+    }
+}
+
 pub fn stress_sequential<K, M>(iter: u64)
 where
     K: Ord + Clone + Random + Debug,
     M: SequentialMap<K, u64>,
 {
+    stress_sequential_with_check::<K, M, _>(iter, |_| {});
+}
+
+/// stress the sequential map, and run the check on the map for every CHECK_PERIOD operations
+pub fn stress_sequential_with_check<K, M, F>(iter: u64, check: F)
+where
+    K: Ord + Clone + Random + Debug,
+    M: SequentialMap<K, u64>,
+    F: Fn(&M),
+{
+    stress_map::<K, Sequential<M>, _>(iter, |map| check(&map.0));
+}
+
+fn stress_map<K, M, F>(iter: u64, check: F)
+where
+    K: Ord + Clone + Random + Debug,
+    M: StressMap<K, u64>,
+    F: Fn(&M),
+{
+    const CHECK_PERIOD: u64 = 1_000;
+
     // 10 times try to get not existing key, or return if failing
     let gen_not_existing_key = |rng: &mut ThreadRng, map: &BTreeMap<K, u64>| {
         let mut key = K::gen(rng);
@@ -56,7 +106,11 @@ where
     let mut ref_map: BTreeMap<K, u64> = BTreeMap::new();
     let mut rng = thread_rng();
 
-    for _ in 1..=iter {
+    for i in 1..=iter {
+        if i % CHECK_PERIOD == 0 {
+            check(&map);
+        }
+
         let t = types.choose(&mut rng).unwrap();
         let ref_map_keys = ref_map.keys().collect::<Vec<&K>>();
         let existing_key = ref_map_keys.choose(&mut rng);
@@ -150,7 +204,7 @@ where
     _marker: PhantomData<(*const K, V)>,
 }
 
-impl<K, V, M> SequentialMap<K, V> for Sequentialized<K, V, M>
+impl<K, V, M> StressMap<K, V> for Sequentialized<K, V, M>
 where
     K: Eq,
     V: Clone,
@@ -190,7 +244,7 @@ where
     K: Ord + Clone + Random + Debug,
     M: ConcurrentMap<K, u64>,
 {
-    stress_sequential::<K, Sequentialized<K, u64, M>>(iter)
+    stress_map::<K, Sequentialized<K, u64, M>, _>(iter, |_| {})
 }
 
 #[derive(Clone, Debug)]
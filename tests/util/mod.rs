@@ -1,2 +1,4 @@
+pub mod hash;
+pub mod heap;
 pub mod map;
 pub mod queue;
@@ -0,0 +1,70 @@
+use cds::wavelet::WaveletTree;
+use rand::{thread_rng, Rng};
+
+#[test]
+fn test_wavelet_tree() {
+    let values = [5, 4, 5, 5, 2, 1, 5, 6, 1, 3, 5, 0];
+    let tree = WaveletTree::new(&values);
+
+    assert_eq!(tree.len(), 12);
+    assert!((0..12).all(|i| tree.get(i) == values[i]));
+
+    assert_eq!(tree.rank(5, 12), 5);
+    assert_eq!(tree.rank(5, 3), 2);
+    assert_eq!(tree.rank(7, 12), 0);
+    assert_eq!(tree.rank(100, 12), 0);
+
+    assert_eq!(tree.select(5, 0), Some(0));
+    assert_eq!(tree.select(5, 4), Some(10));
+    assert_eq!(tree.select(5, 5), None);
+    assert_eq!(tree.select(1, 1), Some(8));
+
+    // [4, 5, 5, 2, 1, 5] sorted is [1, 2, 4, 5, 5, 5]
+    assert_eq!(tree.quantile(1..7, 0), Some(1));
+    assert_eq!(tree.quantile(1..7, 2), Some(4));
+    assert_eq!(tree.quantile(1..7, 5), Some(5));
+    assert_eq!(tree.quantile(1..7, 6), None);
+
+    let large = WaveletTree::new(&[u64::MAX, 0, u64::MAX]);
+    assert_eq!(large.get(0), u64::MAX);
+    assert_eq!(large.rank(u64::MAX, 3), 2);
+    assert_eq!(large.select(u64::MAX, 1), Some(2));
+    assert_eq!(large.quantile(0..3, 1), Some(u64::MAX));
+}
+
+#[test]
+fn stress_wavelet_tree() {
+    let mut rng = thread_rng();
+    let values: Vec<u64> = (0..10_000).map(|_| rng.gen_range(0..100)).collect();
+    let tree = WaveletTree::new(&values);
+
+    for (i, value) in values.iter().enumerate() {
+        assert_eq!(tree.get(i), *value);
+    }
+
+    for symbol in 0..101 {
+        let positions: Vec<_> = (0..values.len()).filter(|i| values[*i] == symbol).collect();
+
+        for _ in 0..10 {
+            let index = rng.gen_range(0..=values.len());
+            let rank = positions.iter().filter(|p| **p < index).count();
+            assert_eq!(tree.rank(symbol, index), rank);
+        }
+
+        for (k, position) in positions.iter().enumerate() {
+            assert_eq!(tree.select(symbol, k), Some(*position));
+        }
+
+        assert_eq!(tree.select(symbol, positions.len()), None);
+    }
+
+    for _ in 0..1_000 {
+        let start = rng.gen_range(0..values.len());
+        let end = rng.gen_range(start..=values.len());
+        let mut sorted = values[start..end].to_vec();
+        sorted.sort_unstable();
+
+        let k = rng.gen_range(0..=sorted.len());
+        assert_eq!(tree.quantile(start..end, k), sorted.get(k).copied());
+    }
+}
@@ -0,0 +1,110 @@
+use crate::util::map::stress_sequential_with_check;
+use cds::{map::SequentialMap, wbtree::WBTree};
+use rand::{prelude::SliceRandom, thread_rng};
+
+#[test]
+fn test_insert_lookup_wb_tree() {
+    let num = 4095;
+    let mut tree: WBTree<i32, i32> = WBTree::new();
+
+    for i in 0..num {
+        assert_eq!(tree.insert(&i, i), Ok(()));
+        tree.validate().unwrap();
+    }
+
+    for i in 0..num {
+        assert_eq!(tree.insert(&i, i), Err(i));
+    }
+
+    assert_eq!(tree.len(), num as usize);
+
+    for i in 0..num {
+        assert_eq!(tree.lookup(&i), Some(&i));
+    }
+
+    for i in 0..num {
+        *tree.lookup_mut(&i).unwrap() += 1;
+    }
+
+    for i in 0..num {
+        assert_eq!(tree.remove(&i), Ok(i + 1));
+        assert_eq!(tree.remove(&i), Err(()));
+        tree.validate().unwrap();
+    }
+
+    assert!(tree.is_empty());
+}
+
+#[test]
+fn test_select_rank_wb_tree() {
+    let num = 1000;
+    let mut keys: Vec<i32> = (0..num).map(|i| i * 2).collect();
+    keys.shuffle(&mut thread_rng());
+
+    let mut tree: WBTree<i32, i32> = WBTree::new();
+
+    for key in keys {
+        assert_eq!(tree.insert(&key, -key), Ok(()));
+    }
+
+    for i in 0..num {
+        assert_eq!(tree.select(i as usize), Some((&(i * 2), &(-i * 2))));
+        assert_eq!(tree.rank(&(i * 2)), i as usize);
+        assert_eq!(tree.rank(&(i * 2 + 1)), i as usize + 1);
+    }
+
+    assert_eq!(tree.select(num as usize), None);
+}
+
+#[test]
+fn test_split_off_join_wb_tree() {
+    let num = 1000;
+
+    for at in [-1, 0, 1, 333, 500, 999, 1000, 1001] {
+        let mut tree: WBTree<i32, i32> = WBTree::new();
+
+        for i in 0..num {
+            assert_eq!(tree.insert(&i, i), Ok(()));
+        }
+
+        let mut other = tree.split_off(&at);
+        tree.validate().unwrap();
+        other.validate().unwrap();
+
+        let split = at.clamp(0, num);
+        assert_eq!(tree.len(), split as usize);
+        assert_eq!(other.len(), (num - split) as usize);
+
+        for i in 0..num {
+            if i < at {
+                assert_eq!(tree.lookup(&i), Some(&i));
+                assert_eq!(other.lookup(&i), None);
+            } else {
+                assert_eq!(tree.lookup(&i), None);
+                assert_eq!(other.lookup(&i), Some(&i));
+            }
+        }
+
+        if split > 0 && split < num {
+            // the keys are overlapped
+            assert_eq!(other.insert(&0, 0), Ok(()));
+            other = tree.join(other).err().unwrap();
+            assert_eq!(other.remove(&0), Ok(0));
+        }
+
+        assert!(tree.join(other).is_ok());
+        tree.validate().unwrap();
+        assert_eq!(tree.len(), num as usize);
+
+        for i in 0..num {
+            assert_eq!(tree.select(i as usize), Some((&i, &i)));
+        }
+    }
+}
+
+#[test]
+fn stress_wb_tree() {
+    stress_sequential_with_check::<String, WBTree<_, _>, _>(100_000, |tree| {
+        tree.validate().unwrap()
+    });
+}